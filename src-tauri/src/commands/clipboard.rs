@@ -1,10 +1,13 @@
 use crate::services::database::{
     clear_clipboard_history as db_clear_clipboard_history,
     delete_clipboard_item as db_delete_clipboard_item, get_clipboard_count,
-    get_clipboard_item_by_id, limit_clipboard_history, move_clipboard_item_to_top,
+    delete_clipboard_items as db_delete_clipboard_items,
+    get_clipboard_item_by_id, get_clipboard_items_by_ids, limit_clipboard_history, move_clipboard_item_to_top,
     move_clipboard_item_by_id as db_move_clipboard_item_by_id,
     query_clipboard_items, update_clipboard_item as db_update_clipboard_item,
+    update_clipboard_item_note as db_update_clipboard_item_note,
     toggle_pin_clipboard_item as db_toggle_pin,
+    set_clipboard_items_pinned as db_set_clipboard_items_pinned,
     ClipboardItem, PaginatedResult, QueryParams,
 };
 use serde::{Deserialize, Serialize};
@@ -36,7 +39,7 @@ struct FilesData {
     operation: String,
 }
 
-fn fill_file_exists(items: &mut [ClipboardItem]) {
+pub(crate) fn fill_file_exists(items: &mut [ClipboardItem]) {
     for item in items.iter_mut() {
         if item.content_type == "file" || item.content_type == "image" {
             check_and_fill_file_exists(item);
@@ -71,6 +74,7 @@ pub async fn get_clipboard_history(
     limit: Option<i64>,
     search: Option<String>,
     content_type: Option<String>,
+    tags: Option<Vec<String>>,
 ) -> Result<PaginatedResult<ClipboardItem>, String> {
     let result = tokio::task::spawn_blocking(move || {
         let params = QueryParams {
@@ -78,6 +82,8 @@ pub async fn get_clipboard_history(
             limit: limit.unwrap_or(50),
             search,
             content_type,
+            tags: tags.unwrap_or_default(),
+            ..Default::default()
         };
         query_clipboard_items(params)
     })
@@ -146,6 +152,41 @@ pub fn apply_history_limit(limit: u64) -> Result<(), String> {
     limit_clipboard_history(limit)
 }
 
+// 获取剪贴板项保存的全部格式类型（用于"粘贴为"）
+#[tauri::command]
+pub fn get_clipboard_item_formats(id: i64) -> Result<Vec<String>, String> {
+    crate::services::database::get_clipboard_format_types(id)
+}
+
+// 获取收藏项保存的全部格式类型（用于"粘贴为"）
+#[tauri::command]
+pub fn get_quick_text_formats(id: String) -> Result<Vec<String>, String> {
+    crate::services::database::get_favorite_format_types(&id)
+}
+
+// 获取可用的文本转换步骤
+#[tauri::command]
+pub fn get_paste_transforms() -> Vec<String> {
+    crate::services::paste::transform::ALL_TRANSFORMS
+        .iter()
+        .map(|t| t.name().to_string())
+        .collect()
+}
+
+// 预览转换结果
+#[tauri::command]
+pub fn preview_paste_transform(text: String, steps: Vec<String>) -> Result<String, String> {
+    use crate::services::paste::transform::{apply_transforms, parse_transform_steps};
+    apply_transforms(&text, &parse_transform_steps(&steps)?)
+}
+
+// 取消正在进行的键入粘贴
+#[tauri::command]
+pub fn cancel_type_paste() -> Result<(), String> {
+    crate::services::paste::keyboard::cancel_typing();
+    Ok(())
+}
+
 // 粘贴参数
 #[derive(Debug, serde::Deserialize)]
 pub struct PasteParams {
@@ -155,60 +196,123 @@ pub struct PasteParams {
     pub favorite_id: Option<String>,
     #[serde(default)]
     pub format: Option<String>,
+    // 转换链ID（设置中的命名转换链）
+    #[serde(default)]
+    pub transform_chain: Option<String>,
+    // 临时指定的转换步骤
+    #[serde(default)]
+    pub transform_steps: Option<Vec<String>>,
+    // 本次粘贴的 HTML 清理级别（structure / no_colors / original），覆盖设置
+    #[serde(default)]
+    pub sanitize_level: Option<String>,
+    // 图片重新编码的质量（1-100，仅 JPEG 使用；PNG、WebP 为无损编码）和最长边像素
+    #[serde(default)]
+    pub image_quality: Option<u8>,
+    #[serde(default)]
+    pub image_max_dimension: Option<u32>,
+    // 以相对路径粘贴文件时的基准目录
+    #[serde(default)]
+    pub base_dir: Option<String>,
 }
 
 // 粘贴剪贴板项或收藏项
 #[tauri::command]
-pub fn paste_content(params: PasteParams, app: tauri::AppHandle) -> Result<(), String> {
+pub async fn paste_content(params: PasteParams, app: tauri::AppHandle) -> Result<(), String> {
     use crate::services::database::get_favorite_by_id;
     use crate::services::paste::paste_handler::{
         paste_clipboard_item_with_format, paste_clipboard_item_with_update,
         paste_favorite_item_with_format, paste_favorite_item_with_update,
     };
-    use crate::services::paste::PasteFormat;
+    use crate::services::paste::{file::PathTextFormat, image::ImagePasteFormat, keyboard, template, transform, PasteFormat};
 
     let paste_format = params.format.as_ref().and_then(|f| match f.as_str() {
         "plain" => Some(PasteFormat::PlainText),
         "formatted" => Some(PasteFormat::WithFormat),
+        "typed" => Some(PasteFormat::Typed),
+        "markdown" => Some(PasteFormat::Markdown),
+        "markdown_html" => Some(PasteFormat::MarkdownAsHtml),
+        image if image.starts_with("image_") => ImagePasteFormat::from_name(image, params.image_quality, params.image_max_dimension)
+            .map(PasteFormat::Image),
+        paths if paths.starts_with("paths") || paths == "file_names" => {
+            PathTextFormat::from_name(paths, params.base_dir.clone()).map(PasteFormat::FilePaths)
+        }
+        mime if mime.contains('/') => Some(PasteFormat::Mime(mime.to_string())),
         _ => None,
     });
+    
+    let paste_format = if let Some(chain_id) = &params.transform_chain {
+        Some(PasteFormat::Transformed(transform::resolve_transform_chain(chain_id)?))
+    } else if let Some(steps) = &params.transform_steps {
+        Some(PasteFormat::Transformed(transform::parse_transform_steps(steps)?))
+    } else {
+        paste_format
+    };
+
+    // 指定清理级别时按带格式粘贴
+    let paste_format = match (&params.sanitize_level, paste_format) {
+        (Some(level), None | Some(PasteFormat::WithFormat)) => {
+            Some(PasteFormat::Sanitized(crate::utils::html_sanitize::SanitizeLevel::from_name(level)))
+        }
+        (_, format) => format,
+    };
 
     // 根据参数类型处理粘贴
     if let Some(clipboard_id) = params.clipboard_id {
         let item = get_clipboard_item_by_id(clipboard_id)?
             .ok_or_else(|| format!("剪贴板项不存在: {}", clipboard_id))?;
 
-        if paste_format.is_some() {
-            paste_clipboard_item_with_format(&item, paste_format)?;
-        } else {
-            paste_clipboard_item_with_update(&item)?;
-        }
+        // 键入粘贴可能持续较长时间，放到阻塞线程执行
+        tokio::task::spawn_blocking(move || {
+            if paste_format.is_some() {
+                paste_clipboard_item_with_format(&item, paste_format)
+            } else {
+                paste_clipboard_item_with_update(&item)
+            }
+        })
+        .await
+        .map_err(|e| format!("任务执行失败: {}", e))??;
     } else if let Some(favorite_id) = params.favorite_id {
         let favorite = get_favorite_by_id(&favorite_id)?
             .ok_or_else(|| format!("收藏项不存在: {}", favorite_id))?;
 
         // 将收藏项转换为剪贴板项格式
-        let item = ClipboardItem {
-            id: 0,
-            content: favorite.content,
-            html_content: favorite.html_content,
-            content_type: favorite.content_type,
-            image_id: favorite.image_id,
-            item_order: favorite.item_order,
-            is_pinned: false,
-            paste_count: 0,
-            source_app: None,
-            source_icon_hash: None,
-            char_count: favorite.char_count,
-            created_at: favorite.created_at,
-            updated_at: favorite.updated_at,
+        let mut item = favorite.into_clipboard_item();
+
+        // 展开片段模板占位符，展开后按纯文本粘贴
+        let primary_type = item.content_type.split(',').next().unwrap_or("").to_string();
+        let mut cursor_offset = None;
+        let parsed = match primary_type.as_str() {
+            "text" | "rich_text" => template::parse_if_template(&item.content).unwrap_or_else(|e| {
+                // 模板有误时按原文粘贴，并提示错误
+                eprintln!("片段模板解析失败，按原文粘贴: {}", e);
+                let _ = crate::services::notification::show_notification(&app, "片段模板有误，已按原文粘贴", &e);
+                None
+            }),
+            _ => None,
         };
-
-        if paste_format.is_some() {
-            paste_favorite_item_with_format(&item, &favorite_id, paste_format)?;
-        } else {
-            paste_favorite_item_with_update(&item, &favorite_id)?;
+        if let Some(segments) = parsed {
+            let inputs = template::collect_template_inputs(&app, &template::input_prompts(&segments)).await?;
+            let expanded = template::expand_template(&segments, &template::RuntimeContext { inputs })?;
+            item.content = expanded.text;
+            item.html_content = None;
+            item.rtf_content = None;
+            item.content_type = "text".to_string();
+            cursor_offset = expanded.cursor_offset;
         }
+
+        tokio::task::spawn_blocking(move || {
+            if paste_format.is_some() {
+                paste_favorite_item_with_format(&item, &favorite_id, paste_format)?;
+            } else {
+                paste_favorite_item_with_update(&item, &favorite_id)?;
+            }
+            if let Some(offset) = cursor_offset {
+                keyboard::move_caret_left(offset)?;
+            }
+            Ok::<(), String>(())
+        })
+        .await
+        .map_err(|e| format!("任务执行失败: {}", e))??;
     } else {
         return Err("必须 clipboard_id 或 favorite_id".to_string());
     };
@@ -221,6 +325,52 @@ pub fn paste_content(params: PasteParams, app: tauri::AppHandle) -> Result<(), S
     Ok(())
 }
 
+// 合并多项：按顺序拼接后粘贴（action 为 "save" 时保存为新项并返回ID）
+#[tauri::command]
+pub fn merge_clipboard_items(
+    items: Vec<crate::services::paste::merge::MergeItemRef>,
+    options: crate::services::paste::merge::MergeOptions,
+    action: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<Option<i64>, String> {
+    use crate::services::paste::merge;
+
+    let merged = merge::merge_items(&items, &options)?;
+    if action.as_deref() == Some("save") {
+        return merge::save_merged(merged).map(Some);
+    }
+
+    merge::paste_merged(&merged)?;
+    if !crate::get_window_state().is_pinned {
+        if let Some(window) = crate::get_main_window(&app) {
+            crate::hide_main_window(&window);
+        }
+    }
+    Ok(None)
+}
+
+// 转换剪贴板项（markdown / html），save 为 true 时另存为新条目并返回其 ID
+#[tauri::command]
+pub fn convert_clipboard_item(
+    id: i64,
+    target: String,
+    save: Option<bool>,
+) -> Result<crate::services::paste::markdown::ConvertedContent, String> {
+    let item = get_clipboard_item_by_id(id)?
+        .ok_or_else(|| format!("剪贴板项不存在: {}", id))?;
+    let mut converted = crate::services::paste::markdown::convert_item(&item, &target)?;
+
+    if save.unwrap_or(false) {
+        let content_type = if converted.html.is_some() { "rich_text" } else { "text" };
+        converted.item_id = Some(crate::services::clipboard::store_composed_item(
+            converted.text.clone(),
+            converted.html.clone(),
+            content_type,
+        )?);
+    }
+    Ok(converted)
+}
+
 // 删除单个剪贴板项
 #[tauri::command]
 pub fn delete_clipboard_item(id: i64) -> Result<(), String> {
@@ -252,12 +402,51 @@ pub fn update_clipboard_item_cmd(id: i64, content: String) -> Result<(), String>
     db_update_clipboard_item(id, content)
 }
 
+// 设置剪贴板项的标题和备注
+#[tauri::command]
+pub fn update_clipboard_item_note(id: i64, title: Option<String>, note: Option<String>) -> Result<(), String> {
+    db_update_clipboard_item_note(id, title, note)
+}
+
 // 切换剪贴板项置顶状态
 #[tauri::command]
 pub fn toggle_pin_clipboard_item(id: i64) -> Result<bool, String> {
     db_toggle_pin(id)
 }
 
+// 批量删除剪贴板项（移到回收站），返回删除的数量
+#[tauri::command]
+pub fn delete_clipboard_items(ids: Vec<i64>) -> Result<usize, String> {
+    let deleted = db_delete_clipboard_items(&ids)?;
+    if deleted > 0 {
+        crate::services::clipboard::clear_last_content_cache();
+        let _ = crate::services::clipboard::emit_clipboard_updated();
+    }
+    Ok(deleted)
+}
+
+// 批量置顶/取消置顶剪贴板项，返回状态有变化的数量
+#[tauri::command]
+pub fn set_clipboard_items_pinned(ids: Vec<i64>, pinned: bool) -> Result<usize, String> {
+    let changed = db_set_clipboard_items_pinned(&ids, pinned)?;
+    if changed > 0 {
+        let _ = crate::services::clipboard::emit_clipboard_updated();
+    }
+    Ok(changed)
+}
+
+// 导出选中的剪贴板项为 zip 文件（包含引用的图片和文件快照），返回导出的数量
+#[tauri::command]
+pub fn export_clipboard_items(ids: Vec<i64>, target_path: String) -> Result<usize, String> {
+    use crate::services::data_management::{export_items_zip, referenced_data_files};
+    let items = get_clipboard_items_by_ids(&ids)?;
+    let data_files: Vec<String> = items.iter()
+        .flat_map(|item| referenced_data_files(&item.content, item.image_id.as_deref()))
+        .collect();
+    export_items_zip(&items, &data_files, Path::new(&target_path))?;
+    Ok(items.len())
+}
+
 // 复制图片文件到剪贴板
 #[tauri::command]
 pub fn copy_image_to_clipboard(file_path: String) -> Result<(), String> {
@@ -306,10 +495,13 @@ pub fn copy_image_to_clipboard(file_path: String) -> Result<(), String> {
 
 // 直接粘贴文本
 #[tauri::command]
-pub fn paste_text_direct(text: String, app: tauri::AppHandle) -> Result<(), String> {
+pub async fn paste_text_direct(text: String, app: tauri::AppHandle) -> Result<(), String> {
     use crate::services::paste::paste_handler::paste_text_direct as do_paste;
     
-    do_paste(&text)?;
+    // 可能按应用规则键入输入，不能占用主线程
+    tokio::task::spawn_blocking(move || do_paste(&text))
+        .await
+        .map_err(|e| format!("任务执行失败: {}", e))??;
     
     if !crate::get_window_state().is_pinned {
         if let Some(window) = crate::get_main_window(&app) {
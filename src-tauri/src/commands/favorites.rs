@@ -1,12 +1,16 @@
 use crate::services::database::{
     query_favorites, get_favorites_count,move_favorite_by_id,
     add_clipboard_to_favorites as db_add_clipboard_to_favorites,
+    add_clipboard_items_to_favorites as db_add_clipboard_items_to_favorites,
     move_favorite_to_group as db_move_favorite_to_group,
+    move_favorites_to_group as db_move_favorites_to_group,
     delete_favorite as db_delete_favorite,
-    get_favorite_by_id,
+    delete_favorites as db_delete_favorites,
+    get_favorite_by_id, get_favorites_by_ids,
     add_favorite as db_add_favorite,
     update_favorite as db_update_favorite,
-    FavoritesQueryParams, PaginatedResult, FavoriteItem
+    update_favorite_note as db_update_favorite_note,
+    resolve_group, FavoritesQueryParams, PaginatedResult, FavoriteItem
 };
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -37,7 +41,7 @@ struct FilesData {
     operation: String,
 }
 
-fn fill_file_exists_for_favorites(items: &mut [FavoriteItem]) {
+pub(crate) fn fill_file_exists_for_favorites(items: &mut [FavoriteItem]) {
     for item in items.iter_mut() {
         if item.content_type == "file" || item.content_type == "image" {
             check_and_fill_file_exists(item);
@@ -72,13 +76,19 @@ pub fn get_favorites_history(
     group_name: Option<String>,
     search: Option<String>,
     content_type: Option<String>,
+    include_descendants: Option<bool>,
+    group_id: Option<i64>,
+    tags: Option<Vec<String>>,
 ) -> Result<PaginatedResult<FavoriteItem>, String> {
     let params = FavoritesQueryParams {
         offset: offset.unwrap_or(0),
         limit: limit.unwrap_or(50),
-        group_name,
+        group_id: resolve_group(group_id, group_name.as_deref())?,
         search,
         content_type,
+        include_descendants: include_descendants.unwrap_or(false),
+        tags: tags.unwrap_or_default(),
+        ..Default::default()
     };
     
     let mut result = query_favorites(params)?;
@@ -88,8 +98,13 @@ pub fn get_favorites_history(
 
 // 获取收藏总数
 #[tauri::command]
-pub fn get_favorites_total_count(group_name: Option<String>) -> Result<i64, String> {
-    get_favorites_count(group_name)
+pub fn get_favorites_total_count(
+    group_name: Option<String>,
+    include_descendants: Option<bool>,
+    group_id: Option<i64>,
+) -> Result<i64, String> {
+    let group_id = resolve_group(group_id, group_name.as_deref())?;
+    get_favorites_count(group_id, include_descendants.unwrap_or(false))
 }
 
 // 移动收藏项（拖拽排序）
@@ -98,20 +113,24 @@ pub fn move_favorite_item_by_id(
     group_name: Option<String>,
     from_id: String,
     to_id: String,
+    group_id: Option<i64>,
 ) -> Result<(), String> {
-    move_favorite_by_id(group_name, from_id, to_id)
+    let group_id = resolve_group(group_id, group_name.as_deref())?;
+    move_favorite_by_id(group_id, from_id, to_id)
 }
 
 // 从剪贴板历史添加到收藏
 #[tauri::command]
-pub fn add_clipboard_to_favorites(id: i64, group_name: Option<String>) -> Result<FavoriteItem, String> {
-    db_add_clipboard_to_favorites(id, group_name)
+pub fn add_clipboard_to_favorites(id: i64, group_name: Option<String>, group_id: Option<i64>) -> Result<FavoriteItem, String> {
+    let group_id = resolve_group(group_id, group_name.as_deref())?;
+    db_add_clipboard_to_favorites(id, group_id)
 }
 
 // 移动收藏项到分组
 #[tauri::command]
-pub fn move_quick_text_to_group(id: String, group_name: String) -> Result<(), String> {
-    db_move_favorite_to_group(id, group_name)
+pub fn move_quick_text_to_group(id: String, group_name: Option<String>, group_id: Option<i64>) -> Result<(), String> {
+    let group_id = resolve_group(group_id, group_name.as_deref())?;
+    db_move_favorite_to_group(id, group_id)
 }
 
 // 删除收藏项
@@ -120,6 +139,72 @@ pub fn delete_quick_text(id: String) -> Result<(), String> {
     db_delete_favorite(id)
 }
 
+// 设置收藏项的备注
+#[tauri::command]
+pub fn update_quick_text_note(id: String, note: Option<String>) -> Result<(), String> {
+    db_update_favorite_note(&id, note)
+}
+
+// 收藏列表批量修改后只发送一次更新事件
+fn notify_favorites_changed(app: &tauri::AppHandle) {
+    use tauri::Emitter;
+    let _ = app.emit("quick-texts-updated", ());
+}
+
+// 批量添加剪贴板项到收藏（保持选中顺序）
+#[tauri::command]
+pub fn add_clipboard_items_to_favorites(
+    ids: Vec<i64>,
+    group_name: Option<String>,
+    group_id: Option<i64>,
+    app: tauri::AppHandle,
+) -> Result<Vec<FavoriteItem>, String> {
+    let group_id = resolve_group(group_id, group_name.as_deref())?;
+    let items = db_add_clipboard_items_to_favorites(&ids, group_id)?;
+    if !items.is_empty() {
+        notify_favorites_changed(&app);
+    }
+    Ok(items)
+}
+
+// 批量移动收藏项到分组，返回实际移动的数量
+#[tauri::command]
+pub fn move_quick_texts_to_group(
+    ids: Vec<String>,
+    group_name: Option<String>,
+    group_id: Option<i64>,
+    app: tauri::AppHandle,
+) -> Result<usize, String> {
+    let group_id = resolve_group(group_id, group_name.as_deref())?;
+    let moved = db_move_favorites_to_group(&ids, group_id)?;
+    if moved > 0 {
+        notify_favorites_changed(&app);
+    }
+    Ok(moved)
+}
+
+// 批量删除收藏项（移到回收站），返回删除的数量
+#[tauri::command]
+pub fn delete_quick_texts(ids: Vec<String>, app: tauri::AppHandle) -> Result<usize, String> {
+    let deleted = db_delete_favorites(&ids)?;
+    if deleted > 0 {
+        notify_favorites_changed(&app);
+    }
+    Ok(deleted)
+}
+
+// 导出选中的收藏项为 zip 文件（包含引用的图片和文件快照），返回导出的数量
+#[tauri::command]
+pub fn export_quick_texts(ids: Vec<String>, target_path: String) -> Result<usize, String> {
+    use crate::services::data_management::{export_items_zip, referenced_data_files};
+    let items = get_favorites_by_ids(&ids)?;
+    let data_files: Vec<String> = items.iter()
+        .flat_map(|item| referenced_data_files(&item.content, item.image_id.as_deref()))
+        .collect();
+    export_items_zip(&items, &data_files, Path::new(&target_path))?;
+    Ok(items.len())
+}
+
 // 根据 ID 获取单个收藏项
 #[tauri::command]
 pub fn get_favorite_item_by_id_cmd(id: String) -> Result<FavoriteItem, String> {
@@ -129,13 +214,21 @@ pub fn get_favorite_item_by_id_cmd(id: String) -> Result<FavoriteItem, String> {
 
 // 添加收藏项
 #[tauri::command]
-pub fn add_quick_text(title: String, content: String, group_name: Option<String>) -> Result<FavoriteItem, String> {
-    db_add_favorite(title, content, group_name)
+pub fn add_quick_text(title: String, content: String, group_name: Option<String>, group_id: Option<i64>) -> Result<FavoriteItem, String> {
+    let group_id = resolve_group(group_id, group_name.as_deref())?;
+    db_add_favorite(title, content, group_id)
 }
 
 // 更新收藏项
 #[tauri::command]
-pub fn update_quick_text(id: String, title: String, content: String, group_name: Option<String>) -> Result<FavoriteItem, String> {
-    db_update_favorite(id, title, content, group_name)
+pub fn update_quick_text(
+    id: String,
+    title: String,
+    content: String,
+    group_name: Option<String>,
+    group_id: Option<i64>,
+) -> Result<FavoriteItem, String> {
+    let group_id = resolve_group(group_id, group_name.as_deref())?;
+    db_update_favorite(id, title, content, group_id)
 }
 
@@ -1,26 +1,134 @@
-use crate::services::database::{get_all_groups, add_group as db_add_group, update_group as db_update_group, delete_group as db_delete_group, GroupInfo};
+use crate::services::database::{
+    get_all_groups, resolve_group,
+    add_group as db_add_group,
+    update_group as db_update_group,
+    move_group as db_move_group,
+    delete_group as db_delete_group,
+    merge_groups as db_merge_groups,
+    add_smart_group as db_add_smart_group,
+    update_smart_group as db_update_smart_group,
+    delete_smart_group as db_delete_smart_group,
+    get_all_smart_groups, query_smart_group,
+    GroupInfo, SmartGroupItems, SmartGroupQuery, DEFAULT_GROUP_NAME,
+};
 
-// 获取所有分组
+// 分组参数：优先使用 ID，兼容按名称（或 "父分组/子分组" 路径）指定；默认分组不能作为操作对象
+fn required_group_id(id: Option<i64>, name: Option<String>, action: &str) -> Result<i64, String> {
+    resolve_group(id, name.as_deref())?
+        .ok_or_else(|| format!("不能{}'{}'分组", action, DEFAULT_GROUP_NAME))
+}
+
+// 获取所有分组（树形）
 #[tauri::command]
 pub fn get_groups() -> Result<Vec<GroupInfo>, String> {
     get_all_groups()
 }
 
+// 获取所有智能分组
+#[tauri::command]
+pub fn get_smart_groups() -> Result<Vec<GroupInfo>, String> {
+    get_all_smart_groups()
+}
+
 // 添加分组
 #[tauri::command]
-pub fn add_group(name: String, icon: String, color: String) -> Result<GroupInfo, String> {
-    db_add_group(name, icon, color)
+pub fn add_group(
+    name: String,
+    icon: String,
+    color: String,
+    parent: Option<String>,
+    parent_id: Option<i64>,
+) -> Result<GroupInfo, String> {
+    let parent_id = resolve_group(parent_id, parent.as_deref())?;
+    db_add_group(name, icon, color, parent_id)
 }
 
 // 更新分组
 #[tauri::command]
-pub fn update_group(old_name: String, new_name: String, new_icon: String, new_color: String) -> Result<GroupInfo, String> {
-    db_update_group(old_name, new_name, new_icon, new_color)
+pub fn update_group(
+    old_name: Option<String>,
+    new_name: String,
+    new_icon: String,
+    new_color: String,
+    id: Option<i64>,
+) -> Result<GroupInfo, String> {
+    let id = required_group_id(id, old_name, "修改")?;
+    db_update_group(id, new_name, new_icon, new_color)
+}
+
+// 移动分组到新的父分组下（父分组为空时移到顶层）
+#[tauri::command]
+pub fn move_group(
+    name: Option<String>,
+    parent: Option<String>,
+    id: Option<i64>,
+    parent_id: Option<i64>,
+) -> Result<(), String> {
+    let id = required_group_id(id, name, "移动")?;
+    let parent_id = resolve_group(parent_id, parent.as_deref())?;
+    db_move_group(id, parent_id)
+}
+
+// 删除分组（连同子分组）
+#[tauri::command]
+pub fn delete_group(name: Option<String>, id: Option<i64>) -> Result<(), String> {
+    let id = required_group_id(id, name, "删除")?;
+    db_delete_group(id)
 }
 
-// 删除分组
+// 合并分组：源分组的收藏项和子分组并入目标分组
 #[tauri::command]
-pub fn delete_group(name: String) -> Result<(), String> {
-    db_delete_group(name)
+pub fn merge_groups(
+    source: Option<String>,
+    target: Option<String>,
+    source_id: Option<i64>,
+    target_id: Option<i64>,
+) -> Result<GroupInfo, String> {
+    let source_id = required_group_id(source_id, source, "合并")?;
+    let target_id = required_group_id(target_id, target, "合并到")?;
+    db_merge_groups(source_id, target_id)
 }
 
+// 添加智能分组（保存的搜索）
+#[tauri::command]
+pub fn add_smart_group(name: String, icon: String, color: String, query: SmartGroupQuery) -> Result<GroupInfo, String> {
+    db_add_smart_group(name, icon, color, query)
+}
+
+// 更新智能分组
+#[tauri::command]
+pub fn update_smart_group(
+    id: i64,
+    name: String,
+    icon: String,
+    color: String,
+    query: SmartGroupQuery,
+) -> Result<GroupInfo, String> {
+    db_update_smart_group(id, name, icon, color, query)
+}
+
+// 删除智能分组
+#[tauri::command]
+pub fn delete_smart_group(id: i64) -> Result<(), String> {
+    db_delete_smart_group(id)
+}
+
+// 分页获取智能分组当前匹配的项目
+#[tauri::command]
+pub async fn get_smart_group_items(
+    id: i64,
+    offset: Option<i64>,
+    limit: Option<i64>,
+) -> Result<SmartGroupItems, String> {
+    let mut result = tokio::task::spawn_blocking(move || {
+        query_smart_group(id, offset.unwrap_or(0), limit.unwrap_or(50))
+    })
+    .await
+    .map_err(|e| format!("任务执行失败: {}", e))??;
+
+    match result {
+        SmartGroupItems::History(ref mut page) => super::clipboard::fill_file_exists(&mut page.items),
+        SmartGroupItems::Favorites(ref mut page) => super::favorites::fill_file_exists_for_favorites(&mut page.items),
+    }
+    Ok(result)
+}
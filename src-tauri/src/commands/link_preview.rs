@@ -0,0 +1,13 @@
+use crate::services::database::{get_link_metadata, LinkMetadata};
+
+// 获取链接预览元数据
+#[tauri::command]
+pub fn get_link_preview(url: String) -> Result<Option<LinkMetadata>, String> {
+    get_link_metadata(&url)
+}
+
+// 重新获取链接预览
+#[tauri::command]
+pub fn refresh_link_preview(url: String) -> Result<(), String> {
+    crate::services::link_preview::refresh_link_preview(&url)
+}
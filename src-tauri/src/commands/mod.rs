@@ -1,6 +1,9 @@
 pub mod clipboard;
 pub mod favorites;
 pub mod groups;
+pub mod tags;
+pub mod revisions;
+pub mod trash;
 pub mod window;
 pub mod settings;
 pub mod system;
@@ -8,10 +11,15 @@ pub mod sound;
 pub mod data_management;
 pub mod screenshot;
 pub mod image_library;
+pub mod link_preview;
+pub mod paste_queue;
 
 pub use clipboard::*;
 pub use favorites::*;
 pub use groups::*;
+pub use tags::*;
+pub use revisions::*;
+pub use trash::*;
 pub use window::*;
 pub use settings::*;
 pub use system::*;
@@ -19,4 +27,6 @@ pub use sound::*;
 pub use data_management::*;
 pub use screenshot::*;
 pub use image_library::*;
+pub use link_preview::*;
+pub use paste_queue::*;
 
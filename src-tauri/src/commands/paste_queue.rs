@@ -0,0 +1,41 @@
+use crate::services::paste::queue::{self, PasteQueueStatus, QueueOrder};
+
+// 开启粘贴队列模式
+#[tauri::command]
+pub fn start_paste_queue(order: Option<String>) -> Result<PasteQueueStatus, String> {
+    queue::start(order.as_deref().map(QueueOrder::from_name));
+    Ok(queue::status())
+}
+
+// 关闭粘贴队列模式
+#[tauri::command]
+pub fn stop_paste_queue() -> Result<(), String> {
+    queue::stop();
+    Ok(())
+}
+
+// 获取粘贴队列状态
+#[tauri::command]
+pub fn get_paste_queue_status() -> Result<PasteQueueStatus, String> {
+    Ok(queue::status())
+}
+
+// 将历史记录中的项加入粘贴队列
+#[tauri::command]
+pub fn add_to_paste_queue(ids: Vec<i64>) -> Result<PasteQueueStatus, String> {
+    queue::enqueue_items(&ids)?;
+    Ok(queue::status())
+}
+
+// 清空粘贴队列
+#[tauri::command]
+pub fn clear_paste_queue() -> Result<(), String> {
+    queue::clear();
+    Ok(())
+}
+
+// 粘贴队列中的下一项
+#[tauri::command]
+pub fn paste_queue_next() -> Result<bool, String> {
+    queue::paste_next()
+}
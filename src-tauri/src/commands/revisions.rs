@@ -0,0 +1,24 @@
+use crate::services::database::{
+    get_item_revisions as db_get_item_revisions,
+    diff_item_revisions as db_diff_item_revisions,
+    restore_item_revision as db_restore_item_revision,
+    ItemRevision, RevisionDiff,
+};
+
+// 获取条目的历史版本（item_type: clipboard / favorite），最新的在前
+#[tauri::command]
+pub fn get_item_revisions(item_type: String, item_id: String) -> Result<Vec<ItemRevision>, String> {
+    db_get_item_revisions(&item_type, &item_id)
+}
+
+// 对比两个历史版本，to_id 为空时与当前内容对比
+#[tauri::command]
+pub fn diff_item_revisions(from_id: i64, to_id: Option<i64>) -> Result<RevisionDiff, String> {
+    db_diff_item_revisions(from_id, to_id)
+}
+
+// 恢复到指定历史版本
+#[tauri::command]
+pub fn restore_item_revision(revision_id: i64) -> Result<(), String> {
+    db_restore_item_revision(revision_id)
+}
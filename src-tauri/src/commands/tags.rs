@@ -0,0 +1,38 @@
+use crate::services::database::{
+    get_all_tags,
+    add_item_tags as db_add_item_tags,
+    remove_item_tags as db_remove_item_tags,
+    rename_tag as db_rename_tag,
+    delete_tag as db_delete_tag,
+    TagInfo,
+};
+
+// 获取所有标签
+#[tauri::command]
+pub fn get_tags() -> Result<Vec<TagInfo>, String> {
+    get_all_tags()
+}
+
+// 为条目添加标签（item_type: clipboard / favorite），返回条目当前的标签
+#[tauri::command]
+pub fn add_item_tags(item_type: String, item_id: String, tags: Vec<String>) -> Result<Vec<String>, String> {
+    db_add_item_tags(&item_type, &item_id, &tags)
+}
+
+// 移除条目的标签，返回条目当前的标签
+#[tauri::command]
+pub fn remove_item_tags(item_type: String, item_id: String, tags: Vec<String>) -> Result<Vec<String>, String> {
+    db_remove_item_tags(&item_type, &item_id, &tags)
+}
+
+// 重命名标签（新名称已存在时合并）
+#[tauri::command]
+pub fn rename_tag(old_name: String, new_name: String) -> Result<(), String> {
+    db_rename_tag(&old_name, &new_name)
+}
+
+// 删除标签
+#[tauri::command]
+pub fn delete_tag(name: String) -> Result<(), String> {
+    db_delete_tag(&name)
+}
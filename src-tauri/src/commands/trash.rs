@@ -0,0 +1,42 @@
+use crate::services::database::{
+    get_trash_items as db_get_trash_items,
+    restore_trash_item as db_restore_trash_item,
+    purge_trash_item as db_purge_trash_item,
+    empty_trash as db_empty_trash,
+    undo_last_delete as db_undo_last_delete,
+    PaginatedResult, TrashBatch, TrashItem,
+};
+
+// 分页获取回收站中的条目
+#[tauri::command]
+pub fn get_trash_items(offset: Option<i64>, limit: Option<i64>) -> Result<PaginatedResult<TrashItem>, String> {
+    db_get_trash_items(offset.unwrap_or(0), limit.unwrap_or(50))
+}
+
+// 从回收站恢复条目（item_type: clipboard / favorite）
+#[tauri::command]
+pub fn restore_trash_item(item_type: String, item_id: String) -> Result<(), String> {
+    db_restore_trash_item(&item_type, &item_id)
+}
+
+// 彻底删除回收站中的条目
+#[tauri::command]
+pub fn purge_trash_item(item_type: String, item_id: String) -> Result<(), String> {
+    db_purge_trash_item(&item_type, &item_id)
+}
+
+// 清空回收站，返回删除的条目数
+#[tauri::command]
+pub fn empty_trash() -> Result<usize, String> {
+    db_empty_trash()
+}
+
+// 撤销最近一次删除，没有可撤销的操作时返回 null
+#[tauri::command]
+pub fn undo_last_delete() -> Result<Option<TrashBatch>, String> {
+    let result = db_undo_last_delete();
+    if matches!(result, Ok(Some(_))) {
+        crate::services::clipboard::clear_last_content_cache();
+    }
+    result
+}
@@ -79,17 +79,36 @@ pub fn run() {
                 commands::get_clipboard_item_by_id_cmd,
                 commands::update_clipboard_item_cmd,
                 commands::toggle_pin_clipboard_item,
+                commands::update_clipboard_item_note,
+                commands::delete_clipboard_items,
+                commands::set_clipboard_items_pinned,
+                commands::export_clipboard_items,
                 commands::paste_text_direct,
                 commands::paste_image_file,
                 commands::move_clipboard_item,
                 commands::move_clipboard_item_by_id,
                 commands::apply_history_limit,
                 commands::paste_content,
+                commands::merge_clipboard_items,
+                commands::convert_clipboard_item,
+                commands::get_clipboard_item_formats,
+                commands::get_quick_text_formats,
+                commands::get_paste_transforms,
+                commands::preview_paste_transform,
+                commands::cancel_type_paste,
                 commands::delete_clipboard_item,
                 commands::clear_clipboard_history,
                 commands::save_image_from_path,
                 commands::copy_image_to_clipboard,
                 commands::resolve_image_path,
+                commands::get_link_preview,
+                commands::refresh_link_preview,
+                commands::start_paste_queue,
+                commands::stop_paste_queue,
+                commands::get_paste_queue_status,
+                commands::add_to_paste_queue,
+                commands::clear_paste_queue,
+                commands::paste_queue_next,
                 commands::get_favorites_history,
                 commands::get_favorites_total_count,
                 commands::get_favorite_item_by_id_cmd,
@@ -99,10 +118,35 @@ pub fn run() {
                 commands::add_clipboard_to_favorites,
                 commands::move_quick_text_to_group,
                 commands::delete_quick_text,
+                commands::update_quick_text_note,
+                commands::add_clipboard_items_to_favorites,
+                commands::move_quick_texts_to_group,
+                commands::delete_quick_texts,
+                commands::export_quick_texts,
                 commands::get_groups,
                 commands::add_group,
                 commands::update_group,
+                commands::move_group,
+                commands::merge_groups,
+                commands::get_smart_groups,
+                commands::add_smart_group,
+                commands::update_smart_group,
+                commands::delete_smart_group,
+                commands::get_smart_group_items,
                 commands::delete_group,
+                commands::get_tags,
+                commands::add_item_tags,
+                commands::remove_item_tags,
+                commands::rename_tag,
+                commands::delete_tag,
+                commands::get_item_revisions,
+                commands::diff_item_revisions,
+                commands::restore_item_revision,
+                commands::get_trash_items,
+                commands::restore_trash_item,
+                commands::purge_trash_item,
+                commands::empty_trash,
+                commands::undo_last_delete,
                 commands::reload_settings,
                 commands::save_settings,
                 commands::reset_settings_to_default,
@@ -275,6 +319,7 @@ pub fn run() {
                     }
                 }
                 let _ = services::database::limit_clipboard_history(settings.history_limit);
+                let _ = services::database::purge_expired_trash(settings.trash_retention_days);
                 
                 utils::init_screen_utils(app.handle().clone());
                 hotkey::init_hotkey_manager(app.handle().clone(), window.clone());
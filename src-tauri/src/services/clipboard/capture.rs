@@ -1,4 +1,4 @@
-use clipboard_rs::{Clipboard, ClipboardContext, common::RustImage};
+use clipboard_rs::{Clipboard, ClipboardContext, ContentFormat, common::RustImage};
 
 // 剪贴板内容类型
 #[derive(Debug, Clone, PartialEq)]
@@ -14,31 +14,42 @@ pub struct ClipboardContent {
     pub content_type: ContentType,
     pub text: Option<String>,
     pub html: Option<String>,
+    pub rtf: Option<String>,
+    // 与文本同时复制的图片（存储路径）
+    pub image: Option<String>,
     pub files: Option<Vec<String>>,
 }
 
-// 保存剪贴板图片到缓存目录
-fn save_clipboard_image(rust_image: &impl RustImage) -> Result<String, String> {
+// 将剪贴板图片编码为 PNG，返回按内容哈希命名的文件名和 PNG 数据（不写入文件）
+fn encode_clipboard_image(rust_image: &impl RustImage) -> Result<(String, Vec<u8>), String> {
     use sha2::{Sha256, Digest};
+    
+    let png_data = rust_image.to_png().map_err(|e| e.to_string())?.get_bytes().to_vec();
+    let hash = format!("{:x}", Sha256::digest(&png_data));
+    Ok((format!("{}.png", &hash[..16]), png_data))
+}
+
+// 剪贴板图片保存后的存储路径（只计算哈希，不写入文件）
+pub(super) fn clipboard_image_path(rust_image: &impl RustImage) -> Result<String, String> {
+    let (filename, _) = encode_clipboard_image(rust_image)?;
+    Ok(format!("clipboard_images/{}", filename))
+}
+
+// 保存剪贴板图片到缓存目录
+pub(super) fn save_clipboard_image(rust_image: &impl RustImage) -> Result<String, String> {
     use uuid::Uuid;
     use crate::services::get_data_directory;
     
     let images_dir = get_data_directory()?.join("clipboard_images");
     std::fs::create_dir_all(&images_dir).map_err(|e| format!("创建目录失败: {}", e))?;
     
-    // 保存到临时文件
-    let temp_file = images_dir.join(format!("temp_{}.png", Uuid::new_v4()));
-    rust_image.save_to_path(temp_file.to_str().ok_or("路径转换失败")?).map_err(|e| e.to_string())?;
-    
-    // 计算哈希并重命名
-    let png_data = std::fs::read(&temp_file).map_err(|e| e.to_string())?;
-    let hash = format!("{:x}", Sha256::digest(&png_data));
-    let filename = format!("{}.png", &hash[..16]);
+    let (filename, png_data) = encode_clipboard_image(rust_image)?;
     let final_path = images_dir.join(&filename);
     
-    if final_path.exists() {
-        std::fs::remove_file(&temp_file).ok();
-    } else {
+    // 先写入临时文件再重命名，避免读到未写完的图片
+    if !final_path.exists() {
+        let temp_file = images_dir.join(format!("temp_{}.png", Uuid::new_v4()));
+        std::fs::write(&temp_file, &png_data).map_err(|e| e.to_string())?;
         std::fs::rename(&temp_file, &final_path).map_err(|e| e.to_string())?;
     }
     
@@ -46,62 +57,65 @@ fn save_clipboard_image(rust_image: &impl RustImage) -> Result<String, String> {
 }
 
 impl ClipboardContent {
-    // 从剪贴板捕获内容
-    pub fn capture() -> Result<Vec<Self>, String> {
+    // 从剪贴板捕获内容（一次复制的全部格式合并为一项）
+    pub fn capture() -> Result<Option<Self>, String> {
         let ctx = ClipboardContext::new()
             .map_err(|e| format!("创建剪贴板上下文失败: {}", e))?;
         
-        let mut results = Vec::new();
-        
         // 获取文件路径
         if let Ok(files) = ctx.get_files() {
             if !files.is_empty() {
-                return Ok(vec![ClipboardContent {
+                return Ok(Some(ClipboardContent {
                     content_type: ContentType::Files,
                     text: Some(files.join("\n")),
                     html: None,
+                    rtf: None,
+                    image: None,
                     files: Some(files),
-                }]);
+                }));
             }
         }
         
-        // 获取HTML（富文本）
-        if let Ok(html) = ctx.get_html() {
-            if !html.trim().is_empty() {
-                let text = ctx.get_text().ok();
-                
-                results.push(ClipboardContent {
-                    content_type: ContentType::RichText,
-                    text,
-                    html: Some(html),
-                    files: None,
-                });
-            }
-        } else if let Ok(text) = ctx.get_text() {
-            // 获取纯文本
-            if !text.trim().is_empty() {
-                results.push(ClipboardContent {
-                    content_type: ContentType::Text,
-                    text: Some(text),
-                    html: None,
-                    files: None,
-                });
-            }
-        }
+        // 获取RTF和HTML（富文本）
+        let rtf = ctx.get_rich_text().ok().filter(|r| !r.trim().is_empty());
+        let html = ctx.get_html().ok().filter(|h| !h.trim().is_empty());
         
-        // 获取图片
-        if let Ok(rust_image) = ctx.get_image() {
-            if let Ok(image_path) = save_clipboard_image(&rust_image) {
-                results.push(ClipboardContent {
-                    content_type: ContentType::Files,
-                    text: Some(image_path.clone()),
-                    html: None,
-                    files: Some(vec![image_path]),
-                });
-            }
+        // 获取纯文本（没有文本格式时从RTF提取）
+        let text = ctx.get_text().ok()
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| rtf.as_deref().map(crate::utils::rtf::rtf_to_plain_text))
+            .filter(|t| !t.trim().is_empty());
+        
+        // 获取图片（只在剪贴板包含图片格式时读取，避免每次复制文本都解码位图）
+        let image = ctx.has(ContentFormat::Image)
+            .then(|| ctx.get_image().ok())
+            .flatten()
+            .and_then(|rust_image| save_clipboard_image(&rust_image).ok());
+        
+        if html.is_some() || text.is_some() {
+            let content_type = if html.is_some() || rtf.is_some() {
+                ContentType::RichText
+            } else {
+                ContentType::Text
+            };
+            return Ok(Some(ClipboardContent {
+                content_type,
+                text,
+                html,
+                rtf,
+                image,
+                files: None,
+            }));
         }
-
-        Ok(results)
+        
+        Ok(image.map(|image_path| ClipboardContent {
+            content_type: ContentType::Files,
+            text: Some(image_path.clone()),
+            html: None,
+            rtf: None,
+            image: None,
+            files: Some(vec![image_path]),
+        }))
     }
     
     // 计算内容的哈希值
@@ -117,8 +131,11 @@ impl ClipboardContent {
                 }
             }
             ContentType::RichText => {
+                // 以纯文本为准（粘贴时按文本预设哈希），仅有RTF时使用RTF
                 if let Some(text) = &self.text {
                     hasher.update(text.as_bytes());
+                } else if let Some(rtf) = &self.rtf {
+                    hasher.update(rtf.as_bytes());
                 }
             }
             ContentType::Files => {
@@ -0,0 +1,112 @@
+// 文件快照：将较小的文件按内容哈希复制到 file_snapshots/，源文件移走后仍可粘贴
+// 快照在后台线程中创建，完成后回写到剪贴板项，不阻塞剪贴板监听
+use crate::services::database::{delete_unreferenced_snapshots, link_file_snapshots};
+use crate::services::{get_data_directory, get_settings};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use sha2::{Sha256, Digest};
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc::{sync_channel, SyncSender, TrySendError};
+
+pub const SNAPSHOTS_DIR: &str = "file_snapshots";
+
+// 等待处理的条目上限，队列满时直接丢弃新任务
+const QUEUE_CAPACITY: usize = 16;
+// 单次复制最多创建快照的文件数
+const MAX_FILES_PER_ITEM: usize = 100;
+
+struct SnapshotTask {
+    item_id: i64,
+    paths: Vec<String>,
+}
+
+static TASK_SENDER: Lazy<Mutex<Option<SyncSender<SnapshotTask>>>> = Lazy::new(|| Mutex::new(None));
+
+// 提交剪贴板项的文件快照任务（不阻塞调用方）
+pub fn enqueue_file_snapshots(item_id: i64, paths: Vec<String>) {
+    if !get_settings().file_snapshot_enabled || paths.is_empty() {
+        return;
+    }
+
+    let mut sender = TASK_SENDER.lock();
+    if sender.is_none() {
+        *sender = Some(start_worker());
+    }
+    let Some(tx) = sender.as_ref() else { return };
+
+    match tx.try_send(SnapshotTask { item_id, paths }) {
+        Ok(()) => {}
+        Err(TrySendError::Full(_)) => eprintln!("文件快照队列已满，跳过该条目"),
+        Err(TrySendError::Disconnected(_)) => *sender = None,
+    }
+}
+
+fn start_worker() -> SyncSender<SnapshotTask> {
+    let (tx, rx) = sync_channel::<SnapshotTask>(QUEUE_CAPACITY);
+    std::thread::spawn(move || {
+        for task in rx {
+            if let Err(e) = process_task(&task) {
+                eprintln!("创建文件快照失败 [条目 {}]: {}", task.item_id, e);
+            }
+        }
+    });
+    tx
+}
+
+fn process_task(task: &SnapshotTask) -> Result<(), String> {
+    let settings = get_settings();
+    let mut budget = settings.file_snapshot_total_max_bytes;
+    let mut snapshots = Vec::new();
+
+    for path_str in task.paths.iter().take(MAX_FILES_PER_ITEM) {
+        let path = Path::new(path_str);
+        let Ok(metadata) = fs::metadata(path) else { continue };
+        if !metadata.is_file() || metadata.len() > settings.file_snapshot_max_bytes {
+            continue;
+        }
+        if metadata.len() > budget {
+            eprintln!("已超出单次复制的文件快照总量上限，跳过剩余文件");
+            break;
+        }
+
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("未知文件");
+        match create_file_snapshot(path, name) {
+            Ok(snapshot) => {
+                budget -= metadata.len();
+                snapshots.push((path_str.clone(), snapshot));
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+
+    if snapshots.is_empty() {
+        return Ok(());
+    }
+    if link_file_snapshots(task.item_id, &snapshots)? {
+        let _ = super::monitor::emit_clipboard_updated();
+    } else {
+        // 条目已被删除或不再是文件项
+        for (_, snapshot) in &snapshots {
+            let _ = delete_unreferenced_snapshots(snapshot);
+        }
+    }
+    Ok(())
+}
+
+// 为文件创建快照，返回相对存储路径（file_snapshots/{hash}/{文件名}）
+fn create_file_snapshot(path: &Path, name: &str) -> Result<String, String> {
+    let data = fs::read(path).map_err(|e| format!("读取文件失败 [{}]: {}", path.display(), e))?;
+    let hash = format!("{:x}", Sha256::digest(&data));
+    let snapshot_id = &hash[..16];
+
+    let snapshot_dir = get_data_directory()?.join(SNAPSHOTS_DIR).join(snapshot_id);
+    let snapshot_path = snapshot_dir.join(name);
+
+    if !snapshot_path.exists() {
+        fs::create_dir_all(&snapshot_dir).map_err(|e| format!("创建快照目录失败: {}", e))?;
+        fs::write(&snapshot_path, &data).map_err(|e| format!("保存文件快照失败: {}", e))?;
+    }
+
+    Ok(format!("{}/{}/{}", SNAPSHOTS_DIR, snapshot_id, name))
+}
@@ -0,0 +1,134 @@
+// 富文本远程图片本地化：在后台线程中下载图片，完成后回写到剪贴板项
+use super::processor::save_image_as_file;
+use crate::services::database::{delete_unreferenced_images, link_html_image};
+use crate::services::get_settings;
+use crate::utils::http::read_limited_body;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::time::Duration;
+
+// 等待下载的任务上限，队列满时直接丢弃新任务
+const QUEUE_CAPACITY: usize = 64;
+// 单条剪贴板项最多下载的远程图片数
+const MAX_IMAGES_PER_ITEM: usize = 50;
+
+struct ImageTask {
+    item_id: i64,
+    src: String,
+    // 该剪贴板项剩余的下载字节预算
+    budget: Arc<AtomicU64>,
+}
+
+static TASK_SENDER: Lazy<Mutex<Option<SyncSender<ImageTask>>>> = Lazy::new(|| Mutex::new(None));
+
+// 提交剪贴板项中的远程图片下载任务（不阻塞调用方）
+pub fn enqueue_remote_images(item_id: i64, sources: Vec<String>) {
+    let settings = get_settings();
+    if !settings.fetch_remote_images || sources.is_empty() {
+        return;
+    }
+
+    let budget = Arc::new(AtomicU64::new(settings.remote_image_total_max_bytes));
+    let mut sender = TASK_SENDER.lock();
+    if sender.is_none() {
+        *sender = Some(start_workers(settings.remote_image_concurrency.max(1)));
+    }
+    let Some(tx) = sender.as_ref() else { return };
+
+    for src in sources.into_iter().take(MAX_IMAGES_PER_ITEM) {
+        let task = ImageTask { item_id, src, budget: budget.clone() };
+        match tx.try_send(task) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                eprintln!("远程图片下载队列已满，跳过剩余图片");
+                break;
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                *sender = None;
+                break;
+            }
+        }
+    }
+}
+
+// 启动固定数量的下载线程（与剪贴板监听线程分离）
+fn start_workers(count: u32) -> SyncSender<ImageTask> {
+    let (tx, rx) = sync_channel::<ImageTask>(QUEUE_CAPACITY);
+    let rx = Arc::new(Mutex::new(rx));
+
+    for _ in 0..count {
+        let rx = rx.clone();
+        std::thread::spawn(move || run_worker(rx));
+    }
+
+    tx
+}
+
+fn run_worker(rx: Arc<Mutex<Receiver<ImageTask>>>) {
+    loop {
+        let task = match rx.lock().recv() {
+            Ok(task) => task,
+            Err(_) => return,
+        };
+
+        if let Err(e) = process_task(&task) {
+            eprintln!("下载远程图片失败 [{}]: {}", task.src, e);
+        }
+    }
+}
+
+fn process_task(task: &ImageTask) -> Result<(), String> {
+    let settings = get_settings();
+    let remaining = task.budget.load(Ordering::SeqCst);
+    let max_bytes = settings.remote_image_max_bytes.min(remaining);
+    if max_bytes == 0 {
+        return Err("已超出该条目的图片下载总量上限".to_string());
+    }
+
+    let timeout = Duration::from_secs(settings.remote_image_timeout_secs.max(1) as u64);
+    let data = fetch_remote_image(&task.src, max_bytes, timeout)?;
+
+    let len = data.len() as u64;
+    task.budget
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| left.checked_sub(len))
+        .map_err(|_| "已超出该条目的图片下载总量上限".to_string())?;
+
+    let file_path = save_image_as_file(&data)?;
+    let image_id = std::path::Path::new(&file_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or("无法获取图片ID")?
+        .to_string();
+
+    if link_html_image(task.item_id, &task.src, &image_id)? {
+        let _ = super::monitor::emit_clipboard_updated();
+    } else {
+        // 条目已被删除或不再包含该图片
+        let _ = delete_unreferenced_images(vec![image_id]);
+    }
+
+    Ok(())
+}
+
+// 下载网络图片（限制大小和耗时）
+fn fetch_remote_image(src: &str, max_bytes: u64, timeout: Duration) -> Result<Vec<u8>, String> {
+    let url = src.trim().replace("&amp;", "&");
+    let url = if url.starts_with("//") { format!("https:{}", url) } else { url };
+
+    let client = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .redirect(reqwest::redirect::Policy::limited(5))
+        .user_agent(concat!("QuickClipboard/", env!("CARGO_PKG_VERSION"), " (image fetch)"))
+        .build()
+        .map_err(|e| format!("创建HTTP客户端失败: {}", e))?;
+
+    let response = client.get(&url).send().map_err(|e| format!("请求失败: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP错误: {}", response.status()));
+    }
+
+    read_limited_body(response, max_bytes)
+}
@@ -3,6 +3,9 @@ mod capture;
 mod processor;
 mod storage;
 mod content_type;
+mod image_localizer;
+mod file_snapshot;
+mod restore;
 
 pub use monitor::{
     start_clipboard_monitor, 
@@ -13,6 +16,10 @@ pub use monitor::{
     set_last_hash_text,
     set_last_hash_files,
     set_last_hash_file,
+    set_last_hash_paths,
     clear_last_content_cache,
+    emit_clipboard_updated,
 };
+pub use storage::store_composed_item;
+pub use restore::{prepare_clipboard_restore, schedule_clipboard_restore};
 
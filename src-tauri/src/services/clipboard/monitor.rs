@@ -128,56 +128,41 @@ fn handle_clipboard_change() -> Result<(), String> {
         return Ok(());
     }
     
-    let contents = ClipboardContent::capture()?;
-    if contents.is_empty() {
+    let Some(content) = ClipboardContent::capture()? else {
         return Ok(());
-    }
-    
-    // 计算所有内容的哈希
-    let current_hashes: Vec<String> = contents.iter().map(|c| c.calculate_hash()).collect();
+    };
     
-    // 检查是否与上次完全相同
+    // 检查是否与上次相同
+    let current_hash = content.calculate_hash();
     {
-        let last_hashes = LAST_CONTENT_HASHES.lock();
-        if *last_hashes == current_hashes {
+        let mut last_hashes = LAST_CONTENT_HASHES.lock();
+        if last_hashes.contains(&current_hash) {
             return Ok(());
         }
+        *last_hashes = vec![current_hash];
     }
     
-    // 过滤出新内容
-    let new_contents: Vec<_> = contents.into_iter()
-        .filter(|c| {
-            let hash = c.calculate_hash();
-            let last_hashes = LAST_CONTENT_HASHES.lock();
-            !last_hashes.contains(&hash)
-        })
-        .collect();
-    
-    {
-        let mut last_hashes = LAST_CONTENT_HASHES.lock();
-        *last_hashes = current_hashes;
-    }
+    // 处理并存储（同一次复制的全部格式存为一项）
+    let processed = process_content(content)?;
+    let link_text = processed.content_type.contains("link").then(|| processed.content.clone());
+    let remote_images = processed.pending_remote_images.clone();
+    let snapshot_paths = processed.pending_file_snapshots.clone();
     
-    if new_contents.is_empty() {
-        return Ok(());
-    }
-    
-    // 处理并存储每个新内容项
-    let mut any_stored = false;
-    for content in new_contents {
-        let processed = process_content(content)?;
-        
-        match store_clipboard_item(processed) {
-            Ok(_) => any_stored = true,
-            Err(e) if e.contains("重复内容") || e.contains("已禁止保存图片") => {}
-            Err(e) => return Err(format!("存储剪贴板内容失败: {}", e)),
+    match store_clipboard_item(processed) {
+        Ok(id) => {
+            if let Some(text) = link_text {
+                crate::services::link_preview::enqueue_link_preview(&text);
+            }
+            super::image_localizer::enqueue_remote_images(id, remote_images);
+            super::file_snapshot::enqueue_file_snapshots(id, snapshot_paths);
+            crate::services::paste::queue::on_item_captured(id);
         }
+        Err(e) if e.contains("重复内容") || e.contains("已禁止保存图片") => return Ok(()),
+        Err(e) => return Err(format!("存储剪贴板内容失败: {}", e)),
     }
     
-    if any_stored {
-        let _ = emit_clipboard_updated();
-        crate::AppSounds::play_copy();
-    }
+    let _ = emit_clipboard_updated();
+    crate::AppSounds::play_copy();
     
     Ok(())
 }
@@ -194,7 +179,7 @@ pub fn get_app_handle() -> Option<tauri::AppHandle> {
     APP_HANDLE.lock().clone()
 }
 
-fn emit_clipboard_updated() -> Result<(), String> {
+pub fn emit_clipboard_updated() -> Result<(), String> {
     let app_handle = APP_HANDLE.lock();
     let handle = app_handle.as_ref().ok_or("应用未初始化")?;
     
@@ -206,6 +191,11 @@ fn emit_clipboard_updated() -> Result<(), String> {
     handle.emit("clipboard-updated", ()).map_err(|e| e.to_string())
 }
 
+// 当前记录的内容哈希（用于判断粘贴后用户是否复制了新内容）
+pub(super) fn current_last_hash() -> Option<String> {
+    LAST_CONTENT_HASHES.lock().first().cloned()
+}
+
 // 预设哈希缓存（文本类型）
 pub fn set_last_hash_text(text: &str) {
     use sha2::{Sha256, Digest};
@@ -241,6 +231,21 @@ pub fn set_last_hash_files(content: &str) {
     }
 }
 
+// 预设哈希缓存（文件路径列表）
+pub fn set_last_hash_paths(paths: &[String]) {
+    use sha2::{Sha256, Digest};
+    
+    let mut hasher = Sha256::new();
+    for path in paths {
+        let normalized = crate::services::normalize_path_for_hash(path);
+        hasher.update(normalized.as_bytes());
+    }
+    
+    let hash = format!("{:x}", hasher.finalize());
+    let mut last_hashes = LAST_CONTENT_HASHES.lock();
+    *last_hashes = vec![hash];
+}
+
 // 预设哈希缓存（单文件路径）
 pub fn set_last_hash_file(file_path: &str) {
     use sha2::{Sha256, Digest};
@@ -1,5 +1,7 @@
 use super::capture::{ClipboardContent, ContentType as CaptureType};
 use super::content_type::ContentType;
+use crate::services::database::ClipboardFormat;
+use crate::services::database::formats::{build_formats, MIME_IMAGE};
 use image::ImageFormat;
 use std::io::Cursor;
 use std::fs;
@@ -18,6 +20,9 @@ struct FileInfo {
     is_directory: bool,
     icon_data: Option<String>,
     file_type: String,
+    // 文件快照的存储路径（源文件不存在时粘贴快照）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    snapshot: Option<String>,
 }
 
 // 文件剪贴板数据
@@ -31,17 +36,25 @@ struct FileClipboardData {
 pub struct ProcessedContent {
     pub content: String,              
     pub html_content: Option<String>, 
+    pub rtf_content: Option<String>,
     pub content_type: String,         
     pub image_id: Option<String>,
     pub source_app: Option<String>,      
     pub source_icon_hash: Option<String>,
+    // 待后台下载的远程图片地址
+    pub pending_remote_images: Vec<String>,
+    // 待后台创建快照的文件路径
+    pub pending_file_snapshots: Vec<String>,
+    // 同一次复制的全部格式
+    pub formats: Vec<ClipboardFormat>,
 }
 
 // 处理剪贴板内容，将原始数据转换为可存储的格式
 pub fn process_content(content: ClipboardContent) -> Result<ProcessedContent, String> {
     let (source_app, source_icon_hash) = get_source_info();
+    let attached_image = content.image.clone();
     
-    match content.content_type {
+    let mut processed = match content.content_type {
         // 纯文本处理
         CaptureType::Text => {
             let text = content.text.ok_or("文本内容为空")?;
@@ -57,36 +70,53 @@ pub fn process_content(content: ClipboardContent) -> Result<ProcessedContent, St
             Ok(ProcessedContent {
                 content: text,
                 html_content: None,
+                rtf_content: None,
                 content_type: ct.to_db_string(),
                 image_id: None,
                 source_app,
                 source_icon_hash,
+                pending_remote_images: Vec::new(),
+                pending_file_snapshots: Vec::new(),
+                formats: Vec::new(),
             })
         }
         
-            // 富文本处理（HTML）
+            // 富文本处理（HTML / RTF）
             CaptureType::RichText => {
-                let html = content.html.ok_or("HTML内容为空")?;
-                let text = content.text.unwrap_or_else(|| strip_html(&html));
+                let rtf = content.rtf;
+                let text = content.text
+                    .or_else(|| content.html.as_deref().map(strip_html))
+                    .or_else(|| rtf.as_deref().map(crate::utils::rtf::rtf_to_plain_text))
+                    .ok_or("富文本内容为空")?;
                 
                 let mut ct = ContentType::new("rich_text");
             
                 if is_url(&text) {
                     ct.add_type("link");
-                } else if contains_links(&text) || contains_links(&html) {
+                } else if contains_links(&text) || content.html.as_deref().is_some_and(contains_links) {
                     ct.add_type("link");
                 }
                 
-                let (processed_html, image_ids) = process_html_images(&html)?;
+                let (processed_html, image_ids, remote_images) = match content.html {
+                    Some(html) => {
+                        let (processed, ids, remote) = process_html_images(&html)?;
+                        (Some(processed), ids, remote)
+                    }
+                    None => (None, Vec::new(), Vec::new()),
+                };
                 let image_id = if image_ids.is_empty() { None } else { Some(image_ids.join(",")) };
                 
                 Ok(ProcessedContent {
                     content: text,
-                    html_content: Some(processed_html),
+                    html_content: processed_html,
+                    rtf_content: rtf,
                     content_type: ct.to_db_string(),
                     image_id,
                     source_app,
                     source_icon_hash,
+                    pending_remote_images: remote_images,
+                    pending_file_snapshots: Vec::new(),
+                    formats: Vec::new(),
                 })
             }
         
@@ -115,16 +145,46 @@ pub fn process_content(content: ClipboardContent) -> Result<ProcessedContent, St
                 extract_image_id_from_path(&file_infos[0].path)
             } else { None };
             
+            // 文件快照交给后台线程创建（应用内部图片和文件夹除外）
+            let pending_file_snapshots = file_infos.iter()
+                .filter(|f| !f.is_directory && !f.path.starts_with("clipboard_images/"))
+                .map(|f| f.path.clone())
+                .collect();
+            
             Ok(ProcessedContent {
                 content: format!("files:{}", json_str),
                 html_content: None,
+                rtf_content: None,
                 content_type: ct.to_db_string(),
                 image_id,
                 source_app,
                 source_icon_hash,
+                pending_remote_images: Vec::new(),
+                pending_file_snapshots,
+                formats: Vec::new(),
             })
         }
+    }?;
+    
+    processed.formats = build_formats(
+        &processed.content,
+        &processed.content_type,
+        processed.html_content.as_deref(),
+        processed.rtf_content.as_deref(),
+    );
+    
+    // 与文本一起复制的图片作为附加格式保存
+    if let Some(image_path) = attached_image.filter(|_| crate::services::get_settings().save_images) {
+        if let Some(id) = extract_image_id_from_path(&image_path) {
+            processed.image_id = Some(match processed.image_id.take() {
+                Some(ids) => format!("{},{}", ids, id),
+                None => id,
+            });
+        }
+        processed.formats.push(ClipboardFormat { mime_type: MIME_IMAGE.to_string(), data: image_path });
     }
+    
+    Ok(processed)
 }
 
 // 获取剪贴板来源信息
@@ -214,6 +274,7 @@ fn collect_file_info(file_paths: &[String]) -> Result<Vec<FileInfo>, String> {
             is_directory: metadata.is_dir(),
             icon_data,
             file_type,
+            snapshot: None,
         });
     }
     
@@ -248,14 +309,14 @@ fn strip_html(html: &str) -> String {
     whitespace_regex.replace_all(&text, " ").trim().to_string()
 }
 
-// 处理HTML中的图片
-fn process_html_images(html: &str) -> Result<(String, Vec<String>), String> {
-    use regex::Regex;
-    
+// 处理HTML中的图片（本地和 Data URL 图片立即保存，远程图片交给后台下载）
+fn process_html_images(html: &str) -> Result<(String, Vec<String>, Vec<String>), String> {
     let mut processed_html = html.to_string();
     let mut image_ids = Vec::new();
+    let mut remote_images = Vec::new();
     
-    if let Ok(re) = Regex::new(r#"(<img\b[^>]*?\bsrc\s*=\s*")([^"]+)(")"#) {
+    for pattern in [r#"(<img\b[^>]*?\bsrc\s*=\s*")([^"]+)(")"#, r#"(<img\b[^>]*?\bsrc\s*=\s*')([^']+)(')"#] {
+        let Ok(re) = Regex::new(pattern) else { continue };
         processed_html = re.replace_all(&processed_html, |caps: &regex::Captures| {
             let full_tag = &caps[0];
             let src = &caps[2];
@@ -264,22 +325,11 @@ fn process_html_images(html: &str) -> Result<(String, Vec<String>), String> {
                 return full_tag.to_string();
             }
             
-            if let Some(image_id) = try_save_image_from_url(src) {
-                image_ids.push(image_id.clone());
-                // 在 <img 后插入 data-image-id 属性
-                full_tag.replacen("<img", &format!(r#"<img data-image-id="{}""#, image_id), 1)
-            } else {
-                full_tag.to_string()
-            }
-        }).to_string();
-    }
-    
-    if let Ok(re) = Regex::new(r#"(<img\b[^>]*?\bsrc\s*=\s*')([^']+)(')"#) {
-        processed_html = re.replace_all(&processed_html, |caps: &regex::Captures| {
-            let full_tag = &caps[0];
-            let src = &caps[2];
-            
-            if full_tag.contains("data-image-id") {
+            if is_remote_image_src(src) {
+                let src = src.trim().to_string();
+                if !remote_images.contains(&src) {
+                    remote_images.push(src);
+                }
                 return full_tag.to_string();
             }
             
@@ -287,6 +337,7 @@ fn process_html_images(html: &str) -> Result<(String, Vec<String>), String> {
                 if !image_ids.contains(&image_id) {
                     image_ids.push(image_id.clone());
                 }
+                // 在 <img 后插入 data-image-id 属性
                 full_tag.replacen("<img", &format!(r#"<img data-image-id="{}""#, image_id), 1)
             } else {
                 full_tag.to_string()
@@ -294,7 +345,13 @@ fn process_html_images(html: &str) -> Result<(String, Vec<String>), String> {
         }).to_string();
     }
     
-    Ok((processed_html, image_ids))
+    Ok((processed_html, image_ids, remote_images))
+}
+
+// 是否为远程图片地址
+fn is_remote_image_src(src: &str) -> bool {
+    let src = src.trim();
+    src.starts_with("http://") || src.starts_with("https://") || src.starts_with("//")
 }
 
 // 尝试从URL保存图片并返回图片ID
@@ -322,17 +379,11 @@ fn try_save_image_from_url(src: &str) -> Option<String> {
 }
 
 
-// 获取图片数据（网络或本地）
+// 获取本地图片数据（Data URL 或本地文件，远程图片由后台下载）
 fn fetch_image_data(src: &str) -> Result<Vec<u8>, String> {
-    let src = if src.starts_with("//") {
-        format!("https:{}", src)
-    } else {
-        src.to_string()
-    };
+    let src = src.to_string();
     
-    if src.starts_with("http://") || src.starts_with("https://") {
-        fetch_remote_image(&src)
-    } else if src.starts_with("data:image/") {
+    if src.starts_with("data:image/") {
         parse_data_url(&src)
     } else if src.starts_with("file://") {
         let path = src.trim_start_matches("file://");
@@ -345,30 +396,6 @@ fn fetch_image_data(src: &str) -> Result<Vec<u8>, String> {
     }
 }
 
-// 下载网络图片
-fn fetch_remote_image(url: &str) -> Result<Vec<u8>, String> {
-    use reqwest::blocking::Client;
-    use std::time::Duration;
-    
-    let client = Client::builder()
-        .timeout(Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("创建HTTP客户端失败: {}", e))?;
-    
-    let response = client.get(url)
-        .send()
-        .map_err(|e| format!("下载图片失败: {}", e))?;
-    
-    if !response.status().is_success() {
-        return Err(format!("下载图片失败: HTTP {}", response.status()));
-    }
-    
-    let bytes = response.bytes()
-        .map_err(|e| format!("读取图片数据失败: {}", e))?;
-    
-    Ok(bytes.to_vec())
-}
-
 // 解析Data URL
 fn parse_data_url(data_url: &str) -> Result<Vec<u8>, String> {
     use base64::{Engine as _, engine::general_purpose};
@@ -383,7 +410,7 @@ fn parse_data_url(data_url: &str) -> Result<Vec<u8>, String> {
 }
 
 // 保存图片到本地文件和数据库，返回图片ID
-fn save_image_as_file(image_data: &[u8]) -> Result<String, String> {
+pub(super) fn save_image_as_file(image_data: &[u8]) -> Result<String, String> {
     // 解码图片
     let cursor = Cursor::new(image_data);
     let img = image::ImageReader::new(cursor)
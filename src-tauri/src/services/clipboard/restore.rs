@@ -0,0 +1,124 @@
+// 粘贴后恢复用户原有剪贴板内容
+use clipboard_rs::{Clipboard, ClipboardContent, ClipboardContext};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// 剪贴板快照
+struct ClipboardSnapshot {
+    contents: Vec<ClipboardContent>,
+    // 恢复时用于预设哈希，避免被记录为新的复制
+    hash_text: Option<String>,
+    hash_paths: Option<Vec<String>>,
+}
+
+// 等待恢复的快照及是否已安排恢复（连续粘贴时保留最初的快照）
+static PENDING_SNAPSHOT: Lazy<Mutex<Option<(ClipboardSnapshot, bool)>>> = Lazy::new(|| Mutex::new(None));
+
+static RESTORE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+impl ClipboardSnapshot {
+    fn take(ctx: &ClipboardContext) -> Self {
+        let mut contents = Vec::new();
+
+        if let Ok(files) = ctx.get_files() {
+            if !files.is_empty() {
+                contents.push(ClipboardContent::Files(files.clone()));
+                return ClipboardSnapshot { contents, hash_text: None, hash_paths: Some(files) };
+            }
+        }
+
+        let text = ctx.get_text().ok().filter(|t| !t.is_empty());
+        let rtf = ctx.get_rich_text().ok().filter(|r| !r.trim().is_empty());
+        let html = ctx.get_html().ok().filter(|h| !h.trim().is_empty());
+        let image = ctx.get_image().ok();
+
+        // 与捕获时的哈希规则一致：优先文本，其次 RTF 提取的文本，仅有图片时使用图片路径（只计算，不保存图片）
+        let hash_text = text
+            .clone()
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| rtf.as_deref().map(crate::utils::rtf::rtf_to_plain_text))
+            .filter(|t| !t.trim().is_empty());
+        let hash_paths = match (&hash_text, &image) {
+            (None, Some(image)) => super::capture::clipboard_image_path(image).ok().map(|path| vec![path]),
+            _ => None,
+        };
+
+        if let Some(text) = text {
+            contents.push(ClipboardContent::Text(text));
+        }
+        if let Some(html) = html {
+            contents.push(ClipboardContent::Html(crate::services::paste::generate_cf_html(&html)));
+        }
+        if let Some(rtf) = rtf {
+            contents.push(ClipboardContent::Rtf(rtf));
+        }
+        if let Some(image) = image {
+            contents.push(ClipboardContent::Image(image));
+        }
+
+        ClipboardSnapshot { contents, hash_text, hash_paths }
+    }
+
+    fn restore(self) -> Result<(), String> {
+        let ctx = ClipboardContext::new()
+            .map_err(|e| format!("创建剪贴板上下文失败: {}", e))?;
+
+        if let Some(text) = &self.hash_text {
+            super::set_last_hash_text(text);
+        } else if let Some(paths) = &self.hash_paths {
+            super::set_last_hash_paths(paths);
+        }
+
+        if self.contents.is_empty() {
+            return ctx.clear().map_err(|e| format!("清空剪贴板失败: {}", e));
+        }
+        ctx.set(self.contents)
+            .map_err(|e| format!("恢复剪贴板失败: {}", e))
+    }
+}
+
+// 设置剪贴板前保存当前内容（未开启恢复或已有待恢复快照时跳过）
+pub fn prepare_clipboard_restore() {
+    if !crate::services::get_settings().restore_clipboard_after_paste {
+        return;
+    }
+
+    // 已安排恢复时沿用原快照；未安排的快照来自失败的粘贴，重新获取
+    let mut pending = PENDING_SNAPSHOT.lock();
+    if matches!(*pending, Some((_, true))) {
+        return;
+    }
+    *pending = ClipboardContext::new()
+        .ok()
+        .map(|ctx| (ClipboardSnapshot::take(&ctx), false));
+}
+
+// 模拟粘贴后延迟恢复快照；期间有新的粘贴或用户复制了新内容时不恢复
+pub fn schedule_clipboard_restore() {
+    match PENDING_SNAPSHOT.lock().as_mut() {
+        Some((_, scheduled)) => *scheduled = true,
+        None => return,
+    }
+
+    let generation = RESTORE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let expected_hash = super::monitor::current_last_hash();
+    let delay = crate::services::get_settings().restore_clipboard_delay_ms as u64;
+
+    std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(delay));
+        if RESTORE_GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+
+        let Some((snapshot, _)) = PENDING_SNAPSHOT.lock().take() else {
+            return;
+        };
+        if super::monitor::current_last_hash() != expected_hash {
+            return;
+        }
+        if let Err(e) = snapshot.restore() {
+            eprintln!("{}", e);
+        }
+    });
+}
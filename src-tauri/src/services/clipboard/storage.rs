@@ -1,6 +1,7 @@
 use super::processor::ProcessedContent;
 use crate::services::database::connection::with_connection;
-use crate::services::database::clipboard::limit_clipboard_history;
+use crate::services::database::clipboard::{limit_clipboard_history, delete_unreferenced_images, delete_unreferenced_snapshots};
+use crate::services::database::formats::{build_formats, save_clipboard_formats};
 use crate::services::settings::get_settings;
 use rusqlite::params;
 use chrono;
@@ -31,8 +32,8 @@ pub fn store_clipboard_item(content: ProcessedContent) -> Result<i64, String> {
         let now = chrono::Local::now().timestamp();
         
         match check_and_handle_duplicate(&content, conn, now) {
-            Ok(Some(existing_id)) => {
-                return Ok(existing_id);
+            Ok(Some((existing_id, discarded))) => {
+                return Ok((existing_id, Some(discarded)));
             }
             Ok(None) => {}
             Err(e) => {
@@ -41,33 +42,38 @@ pub fn store_clipboard_item(content: ProcessedContent) -> Result<i64, String> {
         }
         
         let max_order: i64 = conn
-            .query_row("SELECT COALESCE(MAX(item_order), 0) FROM clipboard", [], |row| row.get(0))
+            .query_row("SELECT COALESCE(MAX(item_order), 0) FROM clipboard WHERE deleted_at IS NULL", [], |row| row.get(0))
             .unwrap_or(0);
         let new_order = max_order + 1;
         let char_count = calculate_char_count(&content.content, &content.content_type);
         
         conn.execute(
-            "INSERT INTO clipboard (content, html_content, content_type, image_id, item_order, source_app, source_icon_hash, char_count, created_at, updated_at) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            "INSERT INTO clipboard (content, html_content, rtf_content, content_type, image_id, item_order, source_app, source_icon_hash, char_count, created_at, updated_at, last_copied_at) 
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?10, ?10)",
             params![
                 content.content,
                 content.html_content,
+                content.rtf_content,
                 content.content_type,
                 content.image_id,
                 new_order,
                 content.source_app,
                 content.source_icon_hash,
                 char_count,
-                now,
                 now
             ],
         )?;
         
-        Ok(conn.last_insert_rowid())
+        let id = conn.last_insert_rowid();
+        save_clipboard_formats(conn, id, &content.formats)?;
+        Ok((id, None))
     });
     
     match result {
-        Ok(id) => {
+        Ok((id, discarded)) => {
+            if let Some(discarded) = discarded {
+                discarded.delete_unreferenced_files();
+            }
             let _ = limit_clipboard_history(settings.history_limit);
             Ok(id)
         },
@@ -75,16 +81,52 @@ pub fn store_clipboard_item(content: ProcessedContent) -> Result<i64, String> {
     }
 }
 
-// 智能去重
+// 保存由应用生成的新条目（如多项合并），不经过剪贴板捕获
+pub fn store_composed_item(content: String, html_content: Option<String>, content_type: &str) -> Result<i64, String> {
+    let formats = build_formats(&content, content_type, html_content.as_deref(), None);
+    let id = store_clipboard_item(ProcessedContent {
+        content,
+        html_content,
+        rtf_content: None,
+        content_type: content_type.to_string(),
+        image_id: None,
+        source_app: None,
+        source_icon_hash: None,
+        pending_remote_images: Vec::new(),
+        pending_file_snapshots: Vec::new(),
+        formats,
+    })?;
+    let _ = super::monitor::emit_clipboard_updated();
+    Ok(id)
+}
+
+// 去重时被丢弃的那份内容（旧条目或新捕获的），其引用的图片和文件快照可能已无其他引用
+struct DiscardedPayload {
+    content: String,
+    image_id: Option<String>,
+}
+
+impl DiscardedPayload {
+    fn delete_unreferenced_files(self) {
+        if let Some(image_id) = self.image_id {
+            let ids = image_id.split(',').map(str::trim).filter(|id| !id.is_empty()).map(String::from).collect();
+            let _ = delete_unreferenced_images(ids);
+        }
+        let _ = delete_unreferenced_snapshots(&self.content);
+    }
+}
+
+// 智能去重：已有相同内容时原地更新该条目并返回其 ID（保留 ID、置顶、粘贴次数、标签等信息）
 fn check_and_handle_duplicate(
     content: &ProcessedContent,
     conn: &rusqlite::Connection,
     now: i64,
-) -> Result<Option<i64>, rusqlite::Error> {
+) -> Result<Option<(i64, DiscardedPayload)>, rusqlite::Error> {
     let mut stmt = conn.prepare(
-        "SELECT id, content, content_type 
+        "SELECT id, content, content_type, is_pinned 
          FROM clipboard 
-         ORDER BY created_at DESC 
+         WHERE deleted_at IS NULL 
+         ORDER BY COALESCE(last_copied_at, created_at) DESC 
          LIMIT 100"
     )?;
     
@@ -93,11 +135,12 @@ fn check_and_handle_duplicate(
             row.get::<_, i64>(0)?,      // id
             row.get::<_, String>(1)?,   // content
             row.get::<_, String>(2)?,   // content_type
+            row.get::<_, bool>(3)?,     // is_pinned
         ))
     })?;
     
     for item in recent_items {
-        let (db_id, db_content, db_type) = item?;
+        let (db_id, db_content, db_type, is_pinned) = item?;
 
         let is_text_same = if is_text_type(&content.content_type) && is_text_type(&db_type) {
             content.content == db_content
@@ -111,14 +154,67 @@ fn check_and_handle_duplicate(
             continue;
         }
         
-        conn.execute("DELETE FROM clipboard WHERE id = ?", params![db_id])?;
-        return Ok(None);
+        let discarded = bump_duplicate(conn, db_id, is_pinned, content, now)?;
+        return Ok(Some((db_id, discarded)));
     }
     
     Ok(None)
 }
 
 
+// 把重复的条目移到所在区域（置顶/非置顶）的最前，更新来源并累加复制次数
+// 新捕获的格式不少于已保存的格式时，用新捕获的内容、HTML、RTF、图片和格式替换旧的
+fn bump_duplicate(
+    conn: &rusqlite::Connection,
+    id: i64,
+    is_pinned: bool,
+    content: &ProcessedContent,
+    now: i64,
+) -> Result<DiscardedPayload, rusqlite::Error> {
+    let max_order: i64 = conn.query_row(
+        "SELECT COALESCE(MAX(item_order), 0) FROM clipboard WHERE is_pinned = ?1 AND deleted_at IS NULL",
+        params![is_pinned],
+        |row| row.get(0)
+    )?;
+    conn.execute(
+        "UPDATE clipboard SET item_order = ?1, updated_at = ?2, last_copied_at = ?2, copy_count = copy_count + 1,
+             source_app = COALESCE(?3, source_app), source_icon_hash = COALESCE(?4, source_icon_hash)
+         WHERE id = ?5",
+        params![max_order + 1, now, content.source_app, content.source_icon_hash, id],
+    )?;
+
+    let stored_formats: usize = conn.query_row(
+        "SELECT COUNT(*) FROM clipboard_formats WHERE item_id = ?1",
+        params![id],
+        |row| row.get(0)
+    )?;
+    if content.formats.len() < stored_formats {
+        return Ok(DiscardedPayload { content: content.content.clone(), image_id: content.image_id.clone() });
+    }
+
+    let (old_content, old_image_id): (String, Option<String>) = conn.query_row(
+        "SELECT content, image_id FROM clipboard WHERE id = ?1",
+        params![id],
+        |row| Ok((row.get(0)?, row.get(1)?))
+    )?;
+    conn.execute(
+        "UPDATE clipboard SET content = ?1, html_content = ?2, rtf_content = ?3, content_type = ?4, image_id = ?5, char_count = ?6
+         WHERE id = ?7",
+        params![
+            content.content,
+            content.html_content,
+            content.rtf_content,
+            content.content_type,
+            content.image_id,
+            calculate_char_count(&content.content, &content.content_type),
+            id
+        ],
+    )?;
+    conn.execute("DELETE FROM clipboard_formats WHERE item_id = ?1", params![id])?;
+    save_clipboard_formats(conn, id, &content.formats)?;
+    Ok(DiscardedPayload { content: old_content, image_id: old_image_id })
+}
+
 fn is_text_type(content_type: &str) -> bool {
     content_type.starts_with("text") || content_type.contains("rich_text") || content_type.contains("link")
 }
@@ -156,3 +252,100 @@ fn extract_file_paths(json: &Value) -> Vec<String> {
     paths
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::database::connection::create_tables;
+
+    fn captured(content: &str, content_type: &str, source_app: Option<&str>) -> ProcessedContent {
+        ProcessedContent {
+            content: content.to_string(),
+            html_content: None,
+            rtf_content: None,
+            content_type: content_type.to_string(),
+            image_id: None,
+            source_app: source_app.map(String::from),
+            source_icon_hash: None,
+            pending_remote_images: Vec::new(),
+            pending_file_snapshots: Vec::new(),
+            formats: Vec::new(),
+        }
+    }
+
+    fn files(paths: &[&str]) -> String {
+        let files: Vec<Value> = paths.iter().map(|p| serde_json::json!({ "path": p, "name": p, "size": 1 })).collect();
+        format!("files:{}", serde_json::json!({ "files": files, "operation": "copy" }))
+    }
+
+    #[test]
+    fn pinned_duplicate_is_updated_in_place() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO clipboard (id, content, is_pinned, item_order, paste_count, source_app, created_at, updated_at) VALUES
+                (1, 'token', 1, 1, 3, 'Terminal', 10, 10),
+                (2, 'other pinned', 1, 2, 0, NULL, 20, 20),
+                (3, 'latest', 0, 5, 0, NULL, 30, 30);"
+        ).unwrap();
+
+        let id = check_and_handle_duplicate(&captured("token", "text", Some("Chrome")), &conn, 100).unwrap();
+        assert_eq!(id.map(|(id, _)| id), Some(1));
+
+        let row: (bool, i64, i64, String, i64, i64, i64, i64) = conn.query_row(
+            "SELECT is_pinned, item_order, paste_count, source_app, copy_count, last_copied_at, created_at, (SELECT COUNT(*) FROM clipboard) FROM clipboard WHERE id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?, row.get(6)?, row.get(7)?)),
+        ).unwrap();
+        // 仍然置顶，移到置顶区最前，原有信息保留
+        assert_eq!(row, (true, 3, 3, "Chrome".to_string(), 2, 100, 10, 3));
+    }
+
+    #[test]
+    fn file_lists_are_compared_by_path_set() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO clipboard (id, content, content_type, created_at, updated_at) VALUES (1, ?1, 'file', 0, 0)",
+            params![files(&["/a.txt", "/b.txt"])],
+        ).unwrap();
+
+        let reordered = captured(&files(&["/b.txt", "/a.txt"]), "file", None);
+        assert_eq!(check_and_handle_duplicate(&reordered, &conn, 5).unwrap().map(|(id, _)| id), Some(1));
+        let subset = captured(&files(&["/a.txt"]), "file", None);
+        assert!(check_and_handle_duplicate(&subset, &conn, 6).unwrap().is_none());
+
+        let copy_count: i64 = conn.query_row("SELECT copy_count FROM clipboard WHERE id = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(copy_count, 2);
+    }
+
+    #[test]
+    fn rich_copy_of_plain_item_refreshes_payload() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO clipboard (id, content, content_type, created_at, updated_at) VALUES (1, 'hello', 'text', 0, 0)",
+            [],
+        ).unwrap();
+        save_clipboard_formats(&conn, 1, &build_formats("hello", "text", None, None)).unwrap();
+
+        let html = "<b>hello</b>";
+        let mut rich = captured("hello", "rich_text", None);
+        rich.html_content = Some(html.to_string());
+        rich.formats = build_formats("hello", "rich_text", Some(html), None);
+        let (id, discarded) = check_and_handle_duplicate(&rich, &conn, 5).unwrap().unwrap();
+        assert_eq!((id, discarded.content.as_str()), (1, "hello"));
+
+        let (content_type, html_content): (String, Option<String>) = conn.query_row(
+            "SELECT content_type, html_content FROM clipboard WHERE id = 1", [], |row| Ok((row.get(0)?, row.get(1)?)),
+        ).unwrap();
+        assert_eq!((content_type.as_str(), html_content.as_deref()), ("rich_text", Some(html)));
+        let format_count: i64 = conn.query_row("SELECT COUNT(*) FROM clipboard_formats WHERE item_id = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(format_count, 2);
+
+        // 再次以纯文本复制时保留已有的 HTML
+        assert!(check_and_handle_duplicate(&captured("hello", "text", None), &conn, 6).unwrap().is_some());
+        let html_content: Option<String> = conn.query_row("SELECT html_content FROM clipboard WHERE id = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(html_content.as_deref(), Some(html));
+    }
+}
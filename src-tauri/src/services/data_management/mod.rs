@@ -177,6 +177,61 @@ fn backup_full_zip(dir: &Path) -> Result<Option<PathBuf>, String> {
     Ok(Some(target))
 }
 
+// 条目引用的数据目录文件（图片、文件快照），返回相对存储路径
+pub fn referenced_data_files(content: &str, image_id: Option<&str>) -> Vec<String> {
+    let mut paths = Vec::new();
+    let image_ids = image_id.into_iter()
+        .flat_map(|ids| ids.split(','))
+        .chain(content.strip_prefix("image:"));
+    for iid in image_ids.map(str::trim).filter(|s| !s.is_empty()) {
+        paths.push(format!("clipboard_images/{}.png", iid));
+    }
+
+    if let Some(json) = content.strip_prefix("files:") {
+        if let Ok(data) = serde_json::from_str::<serde_json::Value>(json) {
+            let files = data["files"].as_array().into_iter().flatten();
+            for file in files {
+                for key in ["path", "snapshot"] {
+                    let Some(stored) = file[key].as_str() else { continue };
+                    let rel = crate::services::normalize_path_for_hash(stored);
+                    if ["clipboard_images/", "pin_images/", "file_snapshots/"].iter().any(|p| rel.starts_with(p)) {
+                        paths.push(rel);
+                    }
+                }
+            }
+        }
+    }
+    paths
+}
+
+// 导出选中的条目为 zip：items.json 加上条目引用的图片、文件快照
+pub fn export_items_zip<T: Serialize>(items: &[T], data_files: &[String], target_path: &Path) -> Result<(), String> {
+    let data_dir = get_data_directory()?;
+    let json = serde_json::to_string_pretty(items).map_err(|e| format!("序列化导出条目失败: {}", e))?;
+
+    let file = fs::File::create(target_path).map_err(|e| format!("创建导出文件失败: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("items.json", options).map_err(|e| e.to_string())?;
+    std::io::Write::write_all(&mut zip, json.as_bytes()).map_err(|e| format!("写入导出文件失败: {}", e))?;
+
+    let unique: std::collections::BTreeSet<&String> = data_files.iter().collect();
+    for rel in unique {
+        // 相对路径中不允许出现 ..，避免打包数据目录以外的文件
+        if rel.split('/').any(|c| c == "..") { continue; }
+        let path = data_dir.join(rel);
+        if !path.is_file() { continue; }
+        let mut f = fs::File::open(&path).map_err(|e| e.to_string())?;
+        zip.start_file(rel.as_str(), options).map_err(|e| e.to_string())?;
+        std::io::copy(&mut f, &mut zip).map_err(|e| e.to_string())?;
+    }
+
+    zip.finish().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
 // 获取备份列表
 #[derive(Debug, Clone, Serialize)]
 pub struct BackupInfo {
@@ -251,6 +306,10 @@ pub fn reset_all_data() -> Result<String, String> {
         if image_library.exists() { let _ = fs::remove_dir_all(&image_library); }
         let app_icons = dir.join("app_icons");
         if app_icons.exists() { let _ = fs::remove_dir_all(&app_icons); }
+        let favicons = dir.join("favicons");
+        if favicons.exists() { let _ = fs::remove_dir_all(&favicons); }
+        let file_snapshots = dir.join("file_snapshots");
+        if file_snapshots.exists() { let _ = fs::remove_dir_all(&file_snapshots); }
         for name in ["quickclipboard.db", "quickclipboard.db-shm", "quickclipboard.db-wal"] {
             let p = dir.join(name);
             if p.exists() { let _ = fs::remove_file(&p); }
@@ -304,6 +363,8 @@ pub fn import_data_zip(zip_path: PathBuf, mode: &str) -> Result<String, String>
     let imported_images = temp_root.join("clipboard_images");
     let imported_image_library = temp_root.join("image_library");
     let imported_app_icons = temp_root.join("app_icons");
+    let imported_file_snapshots = temp_root.join("file_snapshots");
+    let imported_favicons = temp_root.join("favicons");
     let imported_settings = temp_root.join("settings.json");
 
     match mode {
@@ -352,6 +413,12 @@ pub fn import_data_zip(zip_path: PathBuf, mode: &str) -> Result<String, String>
             let target_app_icons = target_dir.join("app_icons");
             if target_app_icons.exists() { fs::remove_dir_all(&target_app_icons).map_err(|e| e.to_string())?; }
             if imported_app_icons.exists() { copy_dir_all(&imported_app_icons, &target_app_icons)?; }
+            let target_file_snapshots = target_dir.join("file_snapshots");
+            if target_file_snapshots.exists() { fs::remove_dir_all(&target_file_snapshots).map_err(|e| e.to_string())?; }
+            if imported_file_snapshots.exists() { copy_dir_all(&imported_file_snapshots, &target_file_snapshots)?; }
+            let target_favicons = target_dir.join("favicons");
+            if target_favicons.exists() { fs::remove_dir_all(&target_favicons).map_err(|e| e.to_string())?; }
+            if imported_favicons.exists() { copy_dir_all(&imported_favicons, &target_favicons)?; }
 
             let src_db = temp_root.join("quickclipboard.db");
             let dst_db = target_dir.join("quickclipboard.db");
@@ -396,6 +463,18 @@ pub fn import_data_zip(zip_path: PathBuf, mode: &str) -> Result<String, String>
                 merge_dir_overwrite(&imported_app_icons, &target_app_icons)?;
             }
 
+            let target_file_snapshots = current_dir.join("file_snapshots");
+            if imported_file_snapshots.exists() {
+                if !target_file_snapshots.exists() { fs::create_dir_all(&target_file_snapshots).map_err(|e| e.to_string())?; }
+                merge_dir_overwrite(&imported_file_snapshots, &target_file_snapshots)?;
+            }
+
+            let target_favicons = current_dir.join("favicons");
+            if imported_favicons.exists() {
+                if !target_favicons.exists() { fs::create_dir_all(&target_favicons).map_err(|e| e.to_string())?; }
+                merge_dir_overwrite(&imported_favicons, &target_favicons)?;
+            }
+
             if imported_db.exists() {
                 merge_database(&imported_db)?;
             }
@@ -499,31 +578,62 @@ fn merge_database(src_db: &Path) -> Result<(), String> {
     with_connection(|conn| {
         let import_path = src_db.to_str().ok_or(rusqlite::Error::InvalidPath("bad path".into()))?;
         conn.execute("ATTACH DATABASE ?1 AS importdb", [import_path])?;
-        let _ = conn.execute(
-            "INSERT OR IGNORE INTO groups (name, icon, color, order_index, created_at, updated_at)
-             SELECT name, icon, color, order_index, created_at, updated_at FROM importdb.groups",
-            [],
-        );
-
-        let _ = conn.execute(
-            "INSERT OR IGNORE INTO favorites (id, title, content, html_content, content_type, image_id, group_name, item_order, created_at, updated_at)
-             SELECT id, title, content, html_content, content_type, image_id, group_name, item_order, created_at, updated_at FROM importdb.favorites",
-            [],
-        );
-        let _ = conn.execute(
-            "INSERT INTO clipboard (content, html_content, content_type, image_id, created_at, updated_at)
-             SELECT content, html_content, content_type, image_id, created_at, updated_at FROM importdb.clipboard",
-            [],
-        );
+        // 分组按名称路径合并（兼容旧版以名称为主键的分组表），收藏项的分组按映射转换
+        let groups = crate::services::database::groups::merge_attached_groups(conn, "importdb").unwrap_or_default();
+        let _ = crate::services::database::favorites::merge_attached_favorites(conn, "importdb", &groups);
+
+        // 剪贴板项逐条插入以记录新旧 ID 映射，标签按映射合并
+        let clipboard_ids = merge_attached_clipboard(conn).unwrap_or_default();
+        let _ = crate::services::database::tags::merge_attached_tags(conn, "importdb", &clipboard_ids);
 
         let _ = conn.execute("DETACH DATABASE importdb", []);
         reorder_clipboard_by_time(conn);
+        let _ = crate::services::database::formats::migrate_clipboard_formats(conn);
         
         Ok(())
     })?;
     Ok(())
 }
 
+fn merge_attached_clipboard(conn: &rusqlite::Connection) -> Result<std::collections::HashMap<i64, i64>, rusqlite::Error> {
+    use crate::services::database::connection::attached_column_or_null;
+
+    let mut ids = std::collections::HashMap::new();
+    // 回收站中的条目不导入
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, content, html_content, content_type, image_id, created_at, updated_at, {}, {}, COALESCE({}, 1), {}, {} FROM importdb.clipboard WHERE {} IS NULL",
+        attached_column_or_null(conn, "importdb", "clipboard", "title"),
+        attached_column_or_null(conn, "importdb", "clipboard", "note"),
+        attached_column_or_null(conn, "importdb", "clipboard", "copy_count"),
+        attached_column_or_null(conn, "importdb", "clipboard", "last_copied_at"),
+        attached_column_or_null(conn, "importdb", "clipboard", "rtf_content"),
+        attached_column_or_null(conn, "importdb", "clipboard", "deleted_at"),
+    ))?;
+    let mut rows = stmt.query([])?;
+    while let Some(row) = rows.next()? {
+        let old_id: i64 = row.get(0)?;
+        conn.execute(
+            "INSERT INTO clipboard (content, html_content, content_type, image_id, created_at, updated_at, title, note, copy_count, last_copied_at, rtf_content)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            rusqlite::params![
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, i64>(5)?,
+                row.get::<_, i64>(6)?,
+                row.get::<_, Option<String>>(7)?,
+                row.get::<_, Option<String>>(8)?,
+                row.get::<_, i64>(9)?,
+                row.get::<_, Option<i64>>(10)?,
+                row.get::<_, Option<String>>(11)?,
+            ],
+        )?;
+        ids.insert(old_id, conn.last_insert_rowid());
+    }
+    Ok(ids)
+}
+
 fn reorder_clipboard_by_time(conn: &rusqlite::Connection) {
     if let Ok(mut stmt) = conn.prepare(
         "SELECT id FROM clipboard ORDER BY is_pinned DESC, created_at DESC"
@@ -697,6 +807,8 @@ pub fn export_data_zip(target_path: PathBuf) -> Result<PathBuf, String> {
     let images_dir = current_dir.join("clipboard_images");
     let image_library_dir = current_dir.join("image_library");
     let app_icons_dir = current_dir.join("app_icons");
+    let file_snapshots_dir = current_dir.join("file_snapshots");
+    let favicons_dir = current_dir.join("favicons");
     let db_files = [
         "quickclipboard.db",
     ];
@@ -748,6 +860,14 @@ pub fn export_data_zip(target_path: PathBuf) -> Result<PathBuf, String> {
         add_dir_to_zip(&app_icons_dir, &app_icons_dir, "app_icons", &mut zip, options)?;
     }
 
+    if file_snapshots_dir.exists() {
+        add_dir_to_zip(&file_snapshots_dir, &file_snapshots_dir, "file_snapshots", &mut zip, options)?;
+    }
+
+    if favicons_dir.exists() {
+        add_dir_to_zip(&favicons_dir, &favicons_dir, "favicons", &mut zip, options)?;
+    }
+
     if settings_path.exists() {
         let mut f = fs::File::open(&settings_path).map_err(|e| format!("读取settings失败: {}", e))?;
         zip.start_file("settings.json", options).map_err(|e| e.to_string())?;
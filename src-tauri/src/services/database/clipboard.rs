@@ -1,6 +1,12 @@
 use super::models::{ClipboardItem, PaginatedResult, QueryParams};
 use super::connection::{with_connection, MAX_CONTENT_LENGTH};
-use crate::utils::{truncate_string, truncate_around_keyword, truncate_html};
+use super::tags::{load_item_tags, tag_filter_sql, TAG_ITEM_CLIPBOARD};
+use super::revisions::save_revision;
+use super::formats::{set_item_html, set_item_text};
+use super::trash::{id_in_sql, move_to_trash, TRASH_ACTION_CLIPBOARD, TRASH_ACTION_CLEAR_HISTORY};
+use super::snapshots::{collect_snapshot_ids, snapshot_ids_in, unreferenced_snapshots, delete_snapshot_dirs};
+use super::link_metadata::{collect_link_urls, prune_link_metadata, delete_favicon_files};
+use crate::utils::{truncate_string, truncate_around_keyword, truncate_html, add_image_id_to_img_tags};
 use rusqlite::{params, OptionalExtension};
 use std::collections::HashSet;
 use chrono;
@@ -39,7 +45,7 @@ pub fn update_missing_char_counts(items: Vec<(i64, String, String)>) {
 }
 
 // 按逗号拆分图片ID
-fn split_image_ids(s: &str) -> Vec<String> {
+pub(super) fn split_image_ids(s: &str) -> Vec<String> {
     s.split(',')
         .map(|x| x.trim())
         .filter(|x| !x.is_empty())
@@ -48,7 +54,7 @@ fn split_image_ids(s: &str) -> Vec<String> {
 }
 
 // 检查图片ID是否仍被 clipboard 或 favorites 引用
-fn is_image_id_referenced(conn: &rusqlite::Connection, image_id: &str) -> Result<bool, rusqlite::Error> {
+pub(super) fn is_image_id_referenced(conn: &rusqlite::Connection, image_id: &str) -> Result<bool, rusqlite::Error> {
     let exact = image_id;
     let p1 = format!("{},%", image_id);
     let p2 = format!("%,{},%", image_id);
@@ -67,7 +73,7 @@ fn is_image_id_referenced(conn: &rusqlite::Connection, image_id: &str) -> Result
 }
 
 // 删除图片文件
-fn delete_image_files(image_ids: Vec<String>) -> Result<(), String> {
+pub(super) fn delete_image_files(image_ids: Vec<String>) -> Result<(), String> {
     if image_ids.is_empty() { return Ok(()); }
     let data_dir = crate::services::get_data_directory()?;
     let images_dir = data_dir.join("clipboard_images");
@@ -80,63 +86,81 @@ fn delete_image_files(image_ids: Vec<String>) -> Result<(), String> {
     Ok(())
 }
 
+// 构建剪贴板查询的 WHERE 子句和参数
+fn clipboard_filter(params: &QueryParams) -> (String, Vec<Box<dyn rusqlite::ToSql>>) {
+    let mut where_clauses: Vec<String> = vec!["deleted_at IS NULL".to_string()];
+    let mut query_params: Vec<Box<dyn rusqlite::ToSql>> = vec![];
+
+    if let Some(ref search) = params.search {
+        if !search.trim().is_empty() {
+            where_clauses.push("(content LIKE ? OR title LIKE ? OR note LIKE ?)".to_string());
+            let search_pattern = format!("%{}%", search);
+            query_params.push(Box::new(search_pattern.clone()));
+            query_params.push(Box::new(search_pattern.clone()));
+            query_params.push(Box::new(search_pattern));
+        }
+    }
+
+    if let Some(ref content_type) = params.content_type {
+        if content_type != "all" {
+            where_clauses.push("content_type = ?".to_string());
+            query_params.push(Box::new(content_type.clone()));
+        }
+    }
+
+    if let Some(ref source_app) = params.source_app {
+        if !source_app.trim().is_empty() {
+            where_clauses.push("source_app LIKE ?".to_string());
+            query_params.push(Box::new(format!("%{}%", source_app.trim())));
+        }
+    }
+
+    if let Some(after) = params.created_after {
+        where_clauses.push("created_at >= ?".to_string());
+        query_params.push(Box::new(after));
+    }
+
+    if let Some(before) = params.created_before {
+        where_clauses.push("created_at < ?".to_string());
+        query_params.push(Box::new(before));
+    }
+
+    if params.pinned_only {
+        where_clauses.push("is_pinned = 1".to_string());
+    }
+
+    for tag in &params.tags {
+        where_clauses.push(tag_filter_sql(TAG_ITEM_CLIPBOARD, "CAST(id AS TEXT)"));
+        query_params.push(Box::new(tag.trim().to_string()));
+    }
+
+    (format!("WHERE {}", where_clauses.join(" AND ")), query_params)
+}
+
+// 统计符合条件的剪贴板项数量
+pub(super) fn count_clipboard_items(conn: &rusqlite::Connection, params: &QueryParams) -> Result<i64, rusqlite::Error> {
+    let (where_clause, query_params) = clipboard_filter(params);
+    conn.query_row(
+        &format!("SELECT COUNT(*) FROM clipboard {}", where_clause),
+        rusqlite::params_from_iter(query_params),
+        |row| row.get(0)
+    )
+}
+
 // 分页查询剪贴板历史
 pub fn query_clipboard_items(params: QueryParams) -> Result<PaginatedResult<ClipboardItem>, String> {
     let search_keyword = params.search.clone();
-    let has_filter = search_keyword.as_ref().map(|s| !s.trim().is_empty()).unwrap_or(false)
-        || params.content_type.as_ref().map(|t| t != "all").unwrap_or(false);
     
     with_connection(|conn| {
-        let mut where_clauses = vec![];
-        let mut query_params: Vec<Box<dyn rusqlite::ToSql>> = vec![];
-        
-        if let Some(ref search) = search_keyword {
-            if !search.trim().is_empty() {
-                where_clauses.push("content LIKE ?");
-                let search_pattern = format!("%{}%", search);
-                query_params.push(Box::new(search_pattern));
-            }
-        }
-        
-        if let Some(ref content_type) = params.content_type {
-            if content_type != "all" {
-                where_clauses.push("content_type = ?");
-                query_params.push(Box::new(content_type.clone()));
-            }
-        }
-        
-        let where_clause = if where_clauses.is_empty() {
-            String::new()
-        } else {
-            format!("WHERE {}", where_clauses.join(" AND "))
-        };
-        
-        let total_count: i64 = if has_filter {
-            let count_sql = format!("SELECT COUNT(*) FROM clipboard {}", where_clause);
-            let count_params: Vec<Box<dyn rusqlite::ToSql>> = query_params.iter().map(|p| {
-                let val: Box<dyn rusqlite::ToSql> = match p.as_ref().to_sql() {
-                    Ok(rusqlite::types::ToSqlOutput::Borrowed(rusqlite::types::ValueRef::Text(s))) => {
-                        Box::new(String::from_utf8_lossy(s).to_string())
-                    }
-                    _ => Box::new("")
-                };
-                val
-            }).collect();
-            conn.query_row(
-                &count_sql,
-                rusqlite::params_from_iter(count_params.iter().map(|p| p.as_ref())),
-                |row| row.get(0)
-            )?
-        } else {
-            conn.query_row("SELECT COUNT(*) FROM clipboard", [], |row| row.get(0))?
-        };
+        let total_count = count_clipboard_items(conn, &params)?;
+        let (where_clause, mut query_params) = clipboard_filter(&params);
         
         if total_count == 0 {
             return Ok(PaginatedResult::new(0, vec![], params.offset, params.limit));
         }
         
         let query_sql = format!(
-            "SELECT id, content, html_content, content_type, image_id, item_order, is_pinned, paste_count, source_app, source_icon_hash, created_at, updated_at, char_count 
+            "SELECT id, content, html_content, content_type, image_id, item_order, is_pinned, paste_count, source_app, source_icon_hash, created_at, updated_at, char_count, title, note, copy_count, last_copied_at 
              FROM clipboard 
              {} 
              ORDER BY is_pinned DESC, item_order DESC, updated_at DESC 
@@ -199,6 +223,7 @@ pub fn query_clipboard_items(params: QueryParams) -> Result<PaginatedResult<Clip
                     id,
                     content: truncated_content,
                     html_content: truncated_html,
+                    rtf_content: None,
                     content_type: content_type.clone(),
                     image_id: row.get(4)?,
                     item_order: row.get(5)?,
@@ -207,6 +232,11 @@ pub fn query_clipboard_items(params: QueryParams) -> Result<PaginatedResult<Clip
                     source_app: row.get(8)?,
                     source_icon_hash: row.get(9)?,
                     char_count: final_char_count,
+                    title: row.get(13)?,
+                    note: row.get(14)?,
+                    copy_count: row.get(15)?,
+                    last_copied_at: row.get(16)?,
+                    tags: Vec::new(),
                     created_at: row.get(10)?,
                     updated_at: row.get(11)?,
                 }, char_count.is_none() && needs_char_count, id, content, content_type))
@@ -225,6 +255,12 @@ pub fn query_clipboard_items(params: QueryParams) -> Result<PaginatedResult<Clip
         if !items_to_update.is_empty() {
             update_missing_char_counts(items_to_update);
         }
+
+        let ids: Vec<String> = result_items.iter().map(|item| item.id.to_string()).collect();
+        let mut tags = load_item_tags(conn, TAG_ITEM_CLIPBOARD, &ids)?;
+        for item in result_items.iter_mut() {
+            item.tags = tags.remove(&item.id.to_string()).unwrap_or_default();
+        }
         
         Ok(PaginatedResult::new(total_count, result_items, params.offset, params.limit))
     })
@@ -233,51 +269,72 @@ pub fn query_clipboard_items(params: QueryParams) -> Result<PaginatedResult<Clip
 // 获取剪贴板总数
 pub fn get_clipboard_count() -> Result<i64, String> {
     with_connection(|conn| {
-        conn.query_row("SELECT COUNT(*) FROM clipboard", [], |row| row.get(0))
+        conn.query_row("SELECT COUNT(*) FROM clipboard WHERE deleted_at IS NULL", [], |row| row.get(0))
     })
 }
 
 // 根据ID获取剪贴板项（完整内容，不截断）
 pub fn get_clipboard_item_by_id(id: i64) -> Result<Option<ClipboardItem>, String> {
+    with_connection(|conn| load_clipboard_item(conn, id))
+}
+
+// 按 ID 列表获取剪贴板项（完整内容，按列表顺序，跳过不存在的项）
+pub fn get_clipboard_items_by_ids(ids: &[i64]) -> Result<Vec<ClipboardItem>, String> {
     with_connection(|conn| {
-        conn.query_row(
-            "SELECT id, content, html_content, content_type, image_id, item_order, is_pinned, paste_count, source_app, source_icon_hash, created_at, updated_at, char_count 
-             FROM clipboard WHERE id = ?",
-            params![id],
-            |row| {
-                let content: String = row.get(1)?;
-                let content_type: String = row.get(3)?;
-                let char_count: Option<i64> = row.get(12)?;
-                
-                // 计算字符数
-                let final_char_count = if char_count.is_none() && (content_type.contains("text") || content_type.contains("rich_text")) && !content.is_empty() {
-                    Some(content.chars().count() as i64)
-                } else {
-                    char_count
-                };
-                
-                Ok(ClipboardItem {
-                    id: row.get(0)?,
-                    content,
-                    html_content: row.get(2)?,
-                    content_type,
-                    image_id: row.get(4)?,
-                    item_order: row.get(5)?,
-                    is_pinned: row.get::<_, i64>(6)? != 0,
-                    paste_count: row.get(7)?,
-                    source_app: row.get(8)?,
-                    source_icon_hash: row.get(9)?,
-                    char_count: final_char_count,
-                    created_at: row.get(10)?,
-                    updated_at: row.get(11)?,
-                })
-            }
-        )
-        .optional()
-        .map_err(|e| e.into())
+        let mut items = Vec::with_capacity(ids.len());
+        for id in ids {
+            items.extend(load_clipboard_item(conn, *id)?);
+        }
+        Ok(items)
     })
 }
 
+fn load_clipboard_item(conn: &rusqlite::Connection, id: i64) -> Result<Option<ClipboardItem>, rusqlite::Error> {
+    let tags = load_item_tags(conn, TAG_ITEM_CLIPBOARD, &[id.to_string()])?
+        .remove(&id.to_string())
+        .unwrap_or_default();
+    conn.query_row(
+        "SELECT id, content, html_content, content_type, image_id, item_order, is_pinned, paste_count, source_app, source_icon_hash, created_at, updated_at, char_count, rtf_content, title, note, copy_count, last_copied_at 
+         FROM clipboard WHERE id = ? AND deleted_at IS NULL",
+        params![id],
+        |row| {
+            let content: String = row.get(1)?;
+            let content_type: String = row.get(3)?;
+            let char_count: Option<i64> = row.get(12)?;
+            
+            // 计算字符数
+            let final_char_count = if char_count.is_none() && (content_type.contains("text") || content_type.contains("rich_text")) && !content.is_empty() {
+                Some(content.chars().count() as i64)
+            } else {
+                char_count
+            };
+            
+            Ok(ClipboardItem {
+                id: row.get(0)?,
+                content,
+                html_content: row.get(2)?,
+                rtf_content: row.get(13)?,
+                content_type,
+                image_id: row.get(4)?,
+                item_order: row.get(5)?,
+                is_pinned: row.get::<_, i64>(6)? != 0,
+                paste_count: row.get(7)?,
+                source_app: row.get(8)?,
+                source_icon_hash: row.get(9)?,
+                char_count: final_char_count,
+                title: row.get(14)?,
+                note: row.get(15)?,
+                copy_count: row.get(16)?,
+                last_copied_at: row.get(17)?,
+                tags,
+                created_at: row.get(10)?,
+                updated_at: row.get(11)?,
+            })
+        }
+    )
+    .optional()
+}
+
 pub fn increment_paste_count(id: i64) -> Result<(), String> {
     with_connection(|conn| {
         conn.execute(
@@ -294,8 +351,18 @@ pub fn limit_clipboard_history(max_count: u64) -> Result<(), String> {
         return Ok(());
     }
     
-    let images_to_delete: Vec<String> = with_connection(|conn| {
-        let sql_ids = "SELECT image_id FROM clipboard WHERE id NOT IN (SELECT id FROM clipboard ORDER BY is_pinned DESC, item_order DESC, updated_at DESC LIMIT ?1) AND image_id IS NOT NULL AND image_id <> ''";
+    let (images_to_delete, snapshots_to_delete, favicons_to_delete) = with_connection(|conn| {
+        let snapshot_ids = collect_snapshot_ids(
+            conn,
+            "SELECT content FROM clipboard WHERE deleted_at IS NULL AND id NOT IN (SELECT id FROM clipboard WHERE deleted_at IS NULL ORDER BY is_pinned DESC, item_order DESC, updated_at DESC LIMIT ?1) AND content LIKE '%file_snapshots/%'",
+            params![max_count],
+        )?;
+        let link_urls = collect_link_urls(
+            conn,
+            "SELECT content FROM clipboard WHERE deleted_at IS NULL AND id NOT IN (SELECT id FROM clipboard WHERE deleted_at IS NULL ORDER BY is_pinned DESC, item_order DESC, updated_at DESC LIMIT ?1) AND content_type LIKE '%link%'",
+            params![max_count],
+        )?;
+        let sql_ids = "SELECT image_id FROM clipboard WHERE deleted_at IS NULL AND id NOT IN (SELECT id FROM clipboard WHERE deleted_at IS NULL ORDER BY is_pinned DESC, item_order DESC, updated_at DESC LIMIT ?1) AND image_id IS NOT NULL AND image_id <> ''";
         let mut stmt = conn.prepare(sql_ids)?;
         let ids_iter = stmt.query_map(params![max_count], |row| row.get::<_, String>(0))?;
         let mut set: HashSet<String> = HashSet::new();
@@ -309,8 +376,8 @@ pub fn limit_clipboard_history(max_count: u64) -> Result<(), String> {
         drop(stmt);
 
         conn.execute(
-            "DELETE FROM clipboard WHERE id NOT IN (
-                SELECT id FROM clipboard ORDER BY is_pinned DESC, item_order DESC, updated_at DESC LIMIT ?1
+            "DELETE FROM clipboard WHERE deleted_at IS NULL AND id NOT IN (
+                SELECT id FROM clipboard WHERE deleted_at IS NULL ORDER BY is_pinned DESC, item_order DESC, updated_at DESC LIMIT ?1
             )",
             params![max_count],
         )?;
@@ -321,69 +388,28 @@ pub fn limit_clipboard_history(max_count: u64) -> Result<(), String> {
                 to_delete.push(iid);
             }
         }
-        Ok(to_delete)
+        Ok((to_delete, unreferenced_snapshots(conn, snapshot_ids)?, prune_link_metadata(conn, link_urls)?))
     })?;
 
+    delete_snapshot_dirs(snapshots_to_delete)?;
+    delete_favicon_files(favicons_to_delete)?;
     delete_image_files(images_to_delete)
 }
 
-// 删除单个剪贴板项
+// 删除单个剪贴板项（移到回收站，图片在清除回收站时才删除）
 pub fn delete_clipboard_item(id: i64) -> Result<(), String> {
-    let images_to_delete: Vec<String> = with_connection(|conn| {
-        let image_ids_opt: Option<Option<String>> = conn
-            .query_row(
-                "SELECT image_id FROM clipboard WHERE id = ?",
-                params![id],
-                |row| row.get::<_, Option<String>>(0),
-            )
-            .optional()?;
-        let image_ids: Option<String> = image_ids_opt.flatten();
-
-        conn.execute("DELETE FROM clipboard WHERE id = ?1", params![id])?;
-
-        let mut to_delete = Vec::new();
-        if let Some(ids) = image_ids {
-            for iid in split_image_ids(&ids) {
-                if !is_image_id_referenced(conn, &iid)? {
-                    to_delete.push(iid);
-                }
-            }
-        }
-        Ok(to_delete)
-    })?;
-
-    delete_image_files(images_to_delete)
+    with_connection(|conn| {
+        move_to_trash(conn, TRASH_ACTION_CLIPBOARD, "clipboard", "id = ?1", params![id])?;
+        Ok(())
+    })
 }
 
-// 清空所有剪贴板历史
+// 清空所有剪贴板历史（移到回收站，可撤销）
 pub fn clear_clipboard_history() -> Result<(), String> {
-    let images_to_delete: Vec<String> = with_connection(|conn| {
-        let mut stmt = conn.prepare(
-            "SELECT image_id FROM clipboard WHERE image_id IS NOT NULL AND image_id <> ''",
-        )?;
-        let ids_iter = stmt.query_map([], |row| row.get::<_, String>(0))?;
-        let mut set: HashSet<String> = HashSet::new();
-        for r in ids_iter {
-            if let Ok(s) = r {
-                for iid in split_image_ids(&s) {
-                    set.insert(iid);
-                }
-            }
-        }
-        drop(stmt);
-
-        conn.execute("DELETE FROM clipboard", [])?;
-
-        let mut to_delete = Vec::new();
-        for iid in set.into_iter() {
-            if !is_image_id_referenced(conn, &iid)? {
-                to_delete.push(iid);
-            }
-        }
-        Ok(to_delete)
-    })?;
-
-    delete_image_files(images_to_delete)
+    with_connection(|conn| {
+        move_to_trash(conn, TRASH_ACTION_CLEAR_HISTORY, "clipboard", "1 = 1", params![])?;
+        Ok(())
+    })
 }
 
 // 排序逻辑
@@ -413,13 +439,13 @@ pub fn move_clipboard_item_to_top(id: i64) -> Result<(), String> {
     with_connection(|conn| {
         let now = chrono::Local::now().timestamp();
         let max_order: i64 = conn.query_row(
-            "SELECT COALESCE(MAX(item_order), 0) FROM clipboard WHERE is_pinned = 0",
+            "SELECT COALESCE(MAX(item_order), 0) FROM clipboard WHERE is_pinned = 0 AND deleted_at IS NULL",
             [],
             |row| row.get(0)
         ).unwrap_or(0);
         
         conn.execute(
-            "UPDATE clipboard SET item_order = ?1, updated_at = ?2 WHERE id = ?3 AND is_pinned = 0",
+            "UPDATE clipboard SET item_order = ?1, updated_at = ?2 WHERE id = ?3 AND is_pinned = 0 AND deleted_at IS NULL",
             params![max_order + 1, now, id],
         )?;
         Ok(())
@@ -432,11 +458,11 @@ pub fn move_clipboard_item_by_id(from_id: i64, to_id: i64) -> Result<(), String>
 
     with_connection(|conn| {
         let from_pinned: i64 = conn.query_row(
-            "SELECT is_pinned FROM clipboard WHERE id = ?",
+            "SELECT is_pinned FROM clipboard WHERE id = ? AND deleted_at IS NULL",
             params![from_id], |row| row.get(0)
         )?;
         let to_pinned: i64 = conn.query_row(
-            "SELECT is_pinned FROM clipboard WHERE id = ?",
+            "SELECT is_pinned FROM clipboard WHERE id = ? AND deleted_at IS NULL",
             params![to_id], |row| row.get(0)
         )?;
         
@@ -444,7 +470,7 @@ pub fn move_clipboard_item_by_id(from_id: i64, to_id: i64) -> Result<(), String>
             return Ok(());
         }
         
-        let items: Vec<(i64, i64)> = conn.prepare("SELECT id, item_order FROM clipboard ORDER BY is_pinned DESC, item_order DESC, updated_at DESC")?
+        let items: Vec<(i64, i64)> = conn.prepare("SELECT id, item_order FROM clipboard WHERE deleted_at IS NULL ORDER BY is_pinned DESC, item_order DESC, updated_at DESC")?
             .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
             .collect::<Result<Vec<_>, _>>()?;
 
@@ -457,40 +483,165 @@ pub fn move_clipboard_item_by_id(from_id: i64, to_id: i64) -> Result<(), String>
     })
 }
 
-// 更新剪贴板项的内容
+// 更新剪贴板项的内容（修改前的内容保存为历史版本）
 pub fn update_clipboard_item(id: i64, content: String) -> Result<(), String> {
     with_connection(|conn| {
-        let rows = conn.execute(
-            "UPDATE clipboard SET content = ?1, updated_at = ?2 WHERE id = ?3",
-            params![content, chrono::Local::now().timestamp(), id],
+        save_revision(conn, TAG_ITEM_CLIPBOARD, &id.to_string(), None, &content)?;
+        let rows = set_item_text(conn, TAG_ITEM_CLIPBOARD, &id, &content)?;
+        if rows == 0 { return Err(rusqlite::Error::QueryReturnedNoRows); }
+        conn.execute(
+            "UPDATE clipboard SET updated_at = ?1 WHERE id = ?2",
+            params![chrono::Local::now().timestamp(), id],
         )?;
-        if rows == 0 { Err(rusqlite::Error::QueryReturnedNoRows) } else { Ok(()) }
+        Ok(())
     }).map_err(|e| if e.contains("QueryReturnedNoRows") {
         format!("剪贴板项不存在: {}", id)
     } else { e })
 }
 
+// 设置剪贴板项的标题和备注（空白内容视为清除）
+pub fn update_clipboard_item_note(id: i64, title: Option<String>, note: Option<String>) -> Result<(), String> {
+    let normalize = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let rows = with_connection(|conn| conn.execute(
+        "UPDATE clipboard SET title = ?1, note = ?2 WHERE id = ?3 AND deleted_at IS NULL",
+        params![normalize(title), normalize(note), id],
+    ))?;
+    match rows {
+        0 => Err(format!("剪贴板项不存在: {}", id)),
+        _ => Ok(()),
+    }
+}
+
+// 将后台下载的远程图片关联到富文本剪贴板项，返回是否有修改
+pub fn link_html_image(id: i64, src: &str, image_id: &str) -> Result<bool, String> {
+    with_connection(|conn| {
+        let row: Option<(Option<String>, Option<String>)> = conn
+            .query_row(
+                "SELECT html_content, image_id FROM clipboard WHERE id = ?1",
+                params![id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let Some((Some(html), current_ids)) = row else { return Ok(false) };
+        let Some(new_html) = add_image_id_to_img_tags(&html, src, image_id) else { return Ok(false) };
+
+        let mut ids = current_ids.as_deref().map(split_image_ids).unwrap_or_default();
+        if !ids.iter().any(|x| x == image_id) {
+            ids.push(image_id.to_string());
+        }
+
+        conn.execute(
+            "UPDATE clipboard SET image_id = ?1 WHERE id = ?2",
+            params![ids.join(","), id],
+        )?;
+        set_item_html(conn, TAG_ITEM_CLIPBOARD, &id, Some(&new_html))?;
+        Ok(true)
+    })
+}
+
+// 将后台创建的文件快照写回剪贴板项（snapshots 为 (文件路径, 快照路径)），条目不存在时返回 false
+pub fn link_file_snapshots(id: i64, snapshots: &[(String, String)]) -> Result<bool, String> {
+    with_connection(|conn| {
+        let content: Option<String> = conn
+            .query_row("SELECT content FROM clipboard WHERE id = ?1", params![id], |row| row.get(0))
+            .optional()?;
+        let Some(json) = content.as_deref().and_then(|c| c.strip_prefix("files:")) else { return Ok(false) };
+        let Ok(mut data) = serde_json::from_str::<serde_json::Value>(json) else { return Ok(false) };
+
+        let mut linked = false;
+        for file in data["files"].as_array_mut().into_iter().flatten() {
+            let Some(path) = file["path"].as_str() else { continue };
+            if let Some((_, snapshot)) = snapshots.iter().find(|(p, _)| p == path) {
+                file["snapshot"] = serde_json::Value::String(snapshot.clone());
+                linked = true;
+            }
+        }
+        if linked {
+            conn.execute(
+                "UPDATE clipboard SET content = ?1 WHERE id = ?2",
+                params![format!("files:{}", data), id],
+            )?;
+        }
+        Ok(linked)
+    })
+}
+
+// 删除未被任何剪贴板项或收藏项引用的图片文件
+pub fn delete_unreferenced_images(image_ids: Vec<String>) -> Result<(), String> {
+    let unreferenced = with_connection(|conn| {
+        let mut result = Vec::new();
+        for iid in image_ids {
+            if !is_image_id_referenced(conn, &iid)? {
+                result.push(iid);
+            }
+        }
+        Ok(result)
+    })?;
+    delete_image_files(unreferenced)
+}
+
+// 删除内容中引用的、已不被任何剪贴板项或收藏项引用的文件快照
+pub fn delete_unreferenced_snapshots(content: &str) -> Result<(), String> {
+    let snapshot_ids: HashSet<String> = snapshot_ids_in(content).into_iter().collect();
+    if snapshot_ids.is_empty() { return Ok(()); }
+    let unreferenced = with_connection(|conn| unreferenced_snapshots(conn, snapshot_ids))?;
+    delete_snapshot_dirs(unreferenced)
+}
+
 // 切换剪贴板项的置顶状态（置顶时放到置顶区第一位，取消置顶时移到非置顶区第一位）
 pub fn toggle_pin_clipboard_item(id: i64) -> Result<bool, String> {
     with_connection(|conn| {
-        let current_pinned: i64 = conn.query_row(
-            "SELECT is_pinned FROM clipboard WHERE id = ?", params![id], |row| row.get(0)
-        )?;
+        let Some(current_pinned) = conn.query_row(
+            "SELECT is_pinned FROM clipboard WHERE id = ? AND deleted_at IS NULL", params![id], |row| row.get::<_, i64>(0)
+        ).optional()? else {
+            return Ok(None);
+        };
         
+        let pinned = current_pinned == 0;
+        set_pinned(conn, id, pinned, chrono::Local::now().timestamp())?;
+        Ok(Some(pinned))
+    })?
+    .ok_or_else(|| format!("剪贴板项不存在: {}", id))
+}
+
+// 设置置顶状态并移到对应区域的第一位
+fn set_pinned(conn: &rusqlite::Connection, id: i64, pinned: bool, now: i64) -> Result<(), rusqlite::Error> {
+    let max_order: i64 = conn.query_row(
+        "SELECT COALESCE(MAX(item_order), 0) FROM clipboard WHERE is_pinned = ?1 AND deleted_at IS NULL", params![pinned], |row| row.get(0)
+    ).unwrap_or(0);
+    conn.execute("UPDATE clipboard SET is_pinned = ?1, item_order = ?2, updated_at = ?3 WHERE id = ?4", params![pinned, max_order + 1, now, id])?;
+    Ok(())
+}
+
+// 批量设置置顶状态（保持列表中的先后顺序），返回状态有变化的项数
+pub fn set_clipboard_items_pinned(ids: &[i64], pinned: bool) -> Result<usize, String> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
         let now = chrono::Local::now().timestamp();
-        if current_pinned == 0 {
-            let max_pinned_order: i64 = conn.query_row(
-                "SELECT COALESCE(MAX(item_order), 0) FROM clipboard WHERE is_pinned = 1", [], |row| row.get(0)
-            ).unwrap_or(0);
-            conn.execute("UPDATE clipboard SET is_pinned = 1, item_order = ?1, updated_at = ?2 WHERE id = ?3", params![max_pinned_order + 1, now, id])?;
-            Ok(true)
-        } else {
-            let max_order: i64 = conn.query_row(
-                "SELECT COALESCE(MAX(item_order), 0) FROM clipboard WHERE is_pinned = 0", [], |row| row.get(0)
-            ).unwrap_or(0);
-            conn.execute("UPDATE clipboard SET is_pinned = 0, item_order = ?1, updated_at = ?2 WHERE id = ?3", params![max_order + 1, now, id])?;
-            Ok(false)
+        let mut changed = 0;
+        // 后处理的项排在前面，所以倒序处理
+        for id in ids.iter().rev() {
+            let current: Option<bool> = tx.query_row(
+                "SELECT is_pinned FROM clipboard WHERE id = ?1 AND deleted_at IS NULL", params![id], |row| row.get(0)
+            ).optional()?;
+            if current.is_some_and(|p| p != pinned) {
+                set_pinned(&tx, *id, pinned, now)?;
+                changed += 1;
+            }
         }
+        tx.commit()?;
+        Ok(changed)
+    })
+}
+
+// 批量删除剪贴板项（作为一次删除操作移到回收站，可整体撤销），返回删除的项数
+pub fn delete_clipboard_items(ids: &[i64]) -> Result<usize, String> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    with_connection(|conn| {
+        let params: Vec<&dyn rusqlite::ToSql> = ids.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
+        move_to_trash(conn, TRASH_ACTION_CLIPBOARD, "clipboard", &id_in_sql(ids.len()), &params)
     })
 }
 
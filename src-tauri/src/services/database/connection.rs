@@ -20,7 +20,8 @@ pub fn init_database(db_path: &str) -> Result<(), String> {
         "PRAGMA journal_mode = WAL;
          PRAGMA synchronous = NORMAL;
          PRAGMA cache_size = 10000;
-         PRAGMA temp_store = MEMORY;"
+         PRAGMA temp_store = MEMORY;
+         PRAGMA foreign_keys = ON;"
     ).map_err(|e| format!("设置数据库参数失败: {}", e))?;
     
     let mut db_conn = DB_CONNECTION.lock();
@@ -38,7 +39,7 @@ pub fn close_database() {
 }
 
 // 创建数据库表
-fn create_tables(conn: &Connection) -> Result<(), String> {
+pub(crate) fn create_tables(conn: &Connection) -> Result<(), String> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS clipboard (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
@@ -96,7 +97,7 @@ fn create_tables(conn: &Connection) -> Result<(), String> {
             html_content TEXT,
             content_type TEXT NOT NULL DEFAULT 'text',
             image_id TEXT,
-            group_name TEXT NOT NULL DEFAULT '全部',
+            group_id INTEGER REFERENCES groups(id) ON DELETE SET NULL,
             item_order INTEGER NOT NULL DEFAULT 0,
             created_at INTEGER NOT NULL,
             updated_at INTEGER NOT NULL
@@ -106,31 +107,62 @@ fn create_tables(conn: &Connection) -> Result<(), String> {
 
     conn.execute(
         "CREATE TABLE IF NOT EXISTS groups (
-            name TEXT PRIMARY KEY,
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
             icon TEXT NOT NULL DEFAULT 'ti ti-folder',
             color TEXT NOT NULL DEFAULT '#dc2626',
             order_index INTEGER NOT NULL DEFAULT 0,
+            parent_id INTEGER REFERENCES groups(id) ON DELETE CASCADE,
             created_at INTEGER NOT NULL,
             updated_at INTEGER NOT NULL
         )",
         [],
     ).map_err(|e| format!("创建分组表失败: {}", e))?;
 
-    let color_exists = conn
+    // 旧版分组表以名称为主键，先补齐字段再迁移为整数 ID
+    let legacy_groups = !conn
         .prepare("PRAGMA table_info(groups)")
         .and_then(|mut stmt| {
-            let columns = stmt.query_map([], |row| {
-                Ok(row.get::<_, String>(1)?)
-            })?;
-            Ok(columns.into_iter().any(|col| col.map(|c| c == "color").unwrap_or(false)))
+            let columns = stmt.query_map([], |row| Ok(row.get::<_, String>(1)?))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(columns.iter().any(|c| c == "id"))
         })
         .unwrap_or(false);
-    
-    if !color_exists {
-        conn.execute(
-            "ALTER TABLE groups ADD COLUMN color TEXT NOT NULL DEFAULT '#dc2626'",
-            [],
-        ).map_err(|e| format!("添加颜色字段失败: {}", e))?;
+
+    if legacy_groups {
+        let color_exists = conn
+            .prepare("PRAGMA table_info(groups)")
+            .and_then(|mut stmt| {
+                let columns = stmt.query_map([], |row| {
+                    Ok(row.get::<_, String>(1)?)
+                })?;
+                Ok(columns.into_iter().any(|col| col.map(|c| c == "color").unwrap_or(false)))
+            })
+            .unwrap_or(false);
+        
+        if !color_exists {
+            conn.execute(
+                "ALTER TABLE groups ADD COLUMN color TEXT NOT NULL DEFAULT '#dc2626'",
+                [],
+            ).map_err(|e| format!("添加颜色字段失败: {}", e))?;
+        }
+
+        // 父分组字段，已有分组保持为顶层
+        let parent_name_exists = conn
+            .prepare("PRAGMA table_info(groups)")
+            .and_then(|mut stmt| {
+                let columns = stmt.query_map([], |row| Ok(row.get::<_, String>(1)?))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(columns.iter().any(|c| c == "parent_name"))
+            })
+            .unwrap_or(false);
+        
+        if !parent_name_exists {
+            conn.execute("ALTER TABLE groups ADD COLUMN parent_name TEXT", [])
+                .map_err(|e| format!("添加父分组字段失败: {}", e))?;
+        }
+
+        migrate_group_ids(conn).map_err(|e| format!("迁移分组ID失败: {}", e))?;
     }
 
     let fav_paste_count_exists = conn
@@ -205,6 +237,22 @@ fn create_tables(conn: &Connection) -> Result<(), String> {
             .map_err(|e| format!("添加收藏字符数量字段失败: {}", e))?;
     }
 
+    for table in ["clipboard", "favorites"] {
+        let rtf_exists = conn
+            .prepare(&format!("PRAGMA table_info({})", table))
+            .and_then(|mut stmt| {
+                let columns = stmt.query_map([], |row| Ok(row.get::<_, String>(1)?))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(columns.iter().any(|c| c == "rtf_content"))
+            })
+            .unwrap_or(false);
+        
+        if !rtf_exists {
+            conn.execute(&format!("ALTER TABLE {} ADD COLUMN rtf_content TEXT", table), [])
+                .map_err(|e| format!("添加RTF字段失败: {}", e))?;
+        }
+    }
+
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_clipboard_order ON clipboard(is_pinned DESC, item_order DESC, updated_at DESC)",
         [],
@@ -216,11 +264,215 @@ fn create_tables(conn: &Connection) -> Result<(), String> {
     ).map_err(|e| format!("创建内容类型索引失败: {}", e))?;
 
     conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_favorites_group ON favorites(group_name, item_order)",
+        "CREATE INDEX IF NOT EXISTS idx_favorites_group ON favorites(group_id, item_order)",
         [],
     ).map_err(|e| format!("创建收藏索引失败: {}", e))?;
     migrate_favorites_auto_titles(conn);
 
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS link_metadata (
+            url TEXT PRIMARY KEY,
+            title TEXT,
+            description TEXT,
+            favicon_hash TEXT,
+            status TEXT NOT NULL DEFAULT 'ok',
+            fetched_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("创建链接预览表失败: {}", e))?;
+
+    // 格式表首次创建时才需要迁移旧的单格式条目
+    let has_formats_table: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'clipboard_formats')",
+        [],
+        |row| row.get(0),
+    ).map_err(|e| format!("检查剪贴板格式表失败: {}", e))?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS clipboard_formats (
+            item_id INTEGER NOT NULL,
+            mime_type TEXT NOT NULL,
+            data TEXT NOT NULL,
+            PRIMARY KEY (item_id, mime_type)
+        )",
+        [],
+    ).map_err(|e| format!("创建剪贴板格式表失败: {}", e))?;
+
+    // 删除剪贴板项时同步删除其全部格式
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS trg_clipboard_formats_cleanup AFTER DELETE ON clipboard
+         BEGIN
+            DELETE FROM clipboard_formats WHERE item_id = OLD.id;
+         END",
+        [],
+    ).map_err(|e| format!("创建剪贴板格式清理触发器失败: {}", e))?;
+
+    // 从剪贴板历史添加的收藏项保留原条目的全部格式
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS favorite_formats (
+            item_id TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            data TEXT NOT NULL,
+            PRIMARY KEY (item_id, mime_type)
+        )",
+        [],
+    ).map_err(|e| format!("创建收藏格式表失败: {}", e))?;
+
+    conn.execute(
+        "CREATE TRIGGER IF NOT EXISTS trg_favorite_formats_cleanup AFTER DELETE ON favorites
+         BEGIN
+            DELETE FROM favorite_formats WHERE item_id = OLD.id;
+         END",
+        [],
+    ).map_err(|e| format!("创建收藏格式清理触发器失败: {}", e))?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tags (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            created_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("创建标签表失败: {}", e))?;
+
+    // 剪贴板项和收藏项共用的标签关联表（item_id 为条目 ID 的文本形式）
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS item_tags (
+            tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+            item_type TEXT NOT NULL,
+            item_id TEXT NOT NULL,
+            PRIMARY KEY (item_type, item_id, tag_id)
+        )",
+        [],
+    ).map_err(|e| format!("创建标签关联表失败: {}", e))?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_item_tags_tag ON item_tags(tag_id)",
+        [],
+    ).map_err(|e| format!("创建标签索引失败: {}", e))?;
+
+    // 删除剪贴板项或收藏项时同步移除其标签
+    conn.execute_batch(
+        "CREATE TRIGGER IF NOT EXISTS trg_clipboard_tags_cleanup AFTER DELETE ON clipboard
+         BEGIN
+            DELETE FROM item_tags WHERE item_type = 'clipboard' AND item_id = CAST(OLD.id AS TEXT);
+         END;
+         CREATE TRIGGER IF NOT EXISTS trg_favorite_tags_cleanup AFTER DELETE ON favorites
+         BEGIN
+            DELETE FROM item_tags WHERE item_type = 'favorite' AND item_id = OLD.id;
+         END;"
+    ).map_err(|e| format!("创建标签清理触发器失败: {}", e))?;
+
+    // 条目的历史版本，删除条目时一并删除
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS item_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            item_type TEXT NOT NULL,
+            item_id TEXT NOT NULL,
+            title TEXT,
+            content TEXT NOT NULL,
+            html_content TEXT,
+            edited_at INTEGER NOT NULL,
+            created_at INTEGER NOT NULL
+         );
+         CREATE INDEX IF NOT EXISTS idx_item_revisions_item ON item_revisions(item_type, item_id, id);
+         CREATE TRIGGER IF NOT EXISTS trg_clipboard_revisions_cleanup AFTER DELETE ON clipboard
+         BEGIN
+            DELETE FROM item_revisions WHERE item_type = 'clipboard' AND item_id = CAST(OLD.id AS TEXT);
+         END;
+         CREATE TRIGGER IF NOT EXISTS trg_favorite_revisions_cleanup AFTER DELETE ON favorites
+         BEGIN
+            DELETE FROM item_revisions WHERE item_type = 'favorite' AND item_id = OLD.id;
+         END;"
+    ).map_err(|e| format!("创建历史版本表失败: {}", e))?;
+
+    // 回收站：deleted_at 非空的条目已删除，trash_batch 为所属的删除操作（用于撤销）
+    for table in ["clipboard", "favorites"] {
+        let deleted_at_exists = conn
+            .prepare(&format!("PRAGMA table_info({})", table))
+            .and_then(|mut stmt| {
+                let columns = stmt.query_map([], |row| row.get::<_, String>(1))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(columns.iter().any(|c| c == "deleted_at"))
+            })
+            .unwrap_or(false);
+
+        if !deleted_at_exists {
+            conn.execute_batch(&format!(
+                "ALTER TABLE {0} ADD COLUMN deleted_at INTEGER;
+                 ALTER TABLE {0} ADD COLUMN trash_batch INTEGER;",
+                table
+            )).map_err(|e| format!("添加回收站字段失败: {}", e))?;
+        }
+        conn.execute(&format!("CREATE INDEX IF NOT EXISTS idx_{0}_deleted ON {0}(deleted_at)", table), [])
+            .map_err(|e| format!("创建回收站索引失败: {}", e))?;
+    }
+
+    // 删除操作记录，删除分组时 group_snapshot 保存被删分组及其收藏项原来的分组
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS trash_batches (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            action TEXT NOT NULL,
+            group_snapshot TEXT,
+            deleted_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("创建回收站表失败: {}", e))?;
+
+    // 标题和备注：剪贴板项两者都可设置，收藏项已有标题，只添加备注
+    for (table, columns) in [("clipboard", &["title", "note"][..]), ("favorites", &["note"][..])] {
+        let existing: Vec<String> = conn
+            .prepare(&format!("PRAGMA table_info({})", table))
+            .and_then(|mut stmt| {
+                let columns = stmt.query_map([], |row| row.get::<_, String>(1))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(columns)
+            })
+            .unwrap_or_default();
+        for column in columns.iter().filter(|c| !existing.iter().any(|e| e == *c)) {
+            conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} TEXT", table, column), [])
+                .map_err(|e| format!("添加{}字段失败: {}", column, e))?;
+        }
+    }
+
+    // 重复复制时原地更新条目：copy_count 为复制次数，last_copied_at 为最近一次复制的时间
+    let copy_count_exists = conn
+        .prepare("PRAGMA table_info(clipboard)")
+        .and_then(|mut stmt| {
+            let columns = stmt.query_map([], |row| row.get::<_, String>(1))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(columns.iter().any(|c| c == "copy_count"))
+        })
+        .unwrap_or(false);
+
+    if !copy_count_exists {
+        conn.execute_batch(
+            "ALTER TABLE clipboard ADD COLUMN copy_count INTEGER NOT NULL DEFAULT 1;
+             ALTER TABLE clipboard ADD COLUMN last_copied_at INTEGER;
+             UPDATE clipboard SET last_copied_at = updated_at;"
+        ).map_err(|e| format!("添加复制次数字段失败: {}", e))?;
+    }
+
+    // 智能分组：query 为 SmartGroupQuery 的 JSON，order_index 与普通分组共用同一顺序
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS smart_groups (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            icon TEXT NOT NULL DEFAULT 'ti ti-filter',
+            color TEXT NOT NULL DEFAULT '#dc2626',
+            order_index INTEGER NOT NULL DEFAULT 0,
+            query TEXT NOT NULL,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+        )",
+        [],
+    ).map_err(|e| format!("创建智能分组表失败: {}", e))?;
+
+    if !has_formats_table {
+        super::formats::migrate_clipboard_formats(conn)
+            .map_err(|e| format!("迁移剪贴板格式失败: {}", e))?;
+    }
+
     Ok(())
 }
 
@@ -248,20 +500,20 @@ pub fn migrate_clipboard_order(conn: &Connection) {
     }
     
     // 收藏迁移：按分组独立处理
-    if let Ok(groups) = conn.prepare("SELECT DISTINCT group_name FROM favorites")
-        .and_then(|mut s| s.query_map([], |r| r.get::<_, String>(0))
+    if let Ok(groups) = conn.prepare("SELECT DISTINCT group_id FROM favorites")
+        .and_then(|mut s| s.query_map([], |r| r.get::<_, Option<i64>>(0))
             .map(|rows| rows.filter_map(|r| r.ok()).collect::<Vec<_>>()))
     {
         for group in groups {
             let need: bool = conn.query_row(
-                "SELECT (SELECT MAX(item_order) FROM favorites WHERE group_name = ?1) 
-                      < (SELECT COUNT(*) FROM favorites WHERE group_name = ?1)",
+                "SELECT (SELECT MAX(item_order) FROM favorites WHERE group_id IS ?1) 
+                      < (SELECT COUNT(*) FROM favorites WHERE group_id IS ?1)",
                 [&group], |row| row.get(0)
             ).unwrap_or(false);
             
             if need {
                 if let Ok(mut stmt) = conn.prepare(
-                    "SELECT id FROM favorites WHERE group_name = ? ORDER BY item_order ASC, updated_at DESC"
+                    "SELECT id FROM favorites WHERE group_id IS ? ORDER BY item_order ASC, updated_at DESC"
                 ) {
                     let ids: Vec<String> = stmt.query_map([&group], |row| row.get(0))
                         .map(|rows| rows.filter_map(|r| r.ok()).collect())
@@ -277,6 +529,44 @@ pub fn migrate_clipboard_order(conn: &Connection) {
     }
 }
 
+// 分组迁移为整数 ID：收藏项改为通过 group_id 引用分组，'全部'（默认分组）对应空值
+// 收藏项引用了不存在的分组时补建该分组，整个迁移在一个事务中完成
+fn migrate_group_ids(conn: &Connection) -> Result<(), rusqlite::Error> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(
+        "ALTER TABLE groups RENAME TO groups_legacy;
+         CREATE TABLE groups (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            icon TEXT NOT NULL DEFAULT 'ti ti-folder',
+            color TEXT NOT NULL DEFAULT '#dc2626',
+            order_index INTEGER NOT NULL DEFAULT 0,
+            parent_id INTEGER REFERENCES groups(id) ON DELETE CASCADE,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL
+         );
+         INSERT INTO groups_legacy (name, icon, color, order_index, created_at, updated_at)
+            SELECT DISTINCT group_name, 'ti ti-folder', '#dc2626',
+                   (SELECT COALESCE(MAX(order_index), 0) + 1 FROM groups_legacy),
+                   strftime('%s', 'now'), strftime('%s', 'now')
+            FROM favorites
+            WHERE group_name <> '全部' AND group_name NOT IN (SELECT name FROM groups_legacy);
+         INSERT INTO groups (name, icon, color, order_index, created_at, updated_at)
+            SELECT name, icon, color, order_index, created_at, updated_at
+            FROM groups_legacy ORDER BY order_index, name;
+         UPDATE groups SET parent_id = (
+            SELECT p.id FROM groups_legacy l JOIN groups p ON p.name = l.parent_name
+            WHERE l.name = groups.name AND l.parent_name <> l.name
+         );
+         ALTER TABLE favorites ADD COLUMN group_id INTEGER REFERENCES groups(id) ON DELETE SET NULL;
+         UPDATE favorites SET group_id = (SELECT id FROM groups WHERE groups.name = favorites.group_name);
+         DROP INDEX IF EXISTS idx_favorites_group;
+         ALTER TABLE favorites DROP COLUMN group_name;
+         DROP TABLE groups_legacy;"
+    )?;
+    tx.commit()
+}
+
 // 获取数据库连接
 pub fn with_connection<F, R>(f: F) -> Result<R, String>
 where
@@ -288,6 +578,51 @@ where
     f(conn).map_err(|e| format!("数据库操作失败: {}", e))
 }
 
+// 数据库操作中的错误：Invalid 是直接展示给用户的校验错误，Sqlite 是数据库本身的错误
+#[derive(Debug)]
+pub enum DbError {
+    Invalid(String),
+    Sqlite(rusqlite::Error),
+}
+
+impl From<rusqlite::Error> for DbError {
+    fn from(e: rusqlite::Error) -> Self {
+        DbError::Sqlite(e)
+    }
+}
+
+impl std::fmt::Display for DbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DbError::Invalid(msg) => write!(f, "{}", msg),
+            DbError::Sqlite(e) => write!(f, "数据库操作失败: {}", e),
+        }
+    }
+}
+
+// 与 with_connection 相同，但闭包可以返回校验错误（原样返回给调用方，不加数据库错误前缀）
+pub fn with_checked_connection<F, R>(f: F) -> Result<R, String>
+where
+    F: FnOnce(&Connection) -> Result<R, DbError>,
+{
+    let conn_guard = DB_CONNECTION.lock();
+    let conn = conn_guard.as_ref()
+        .ok_or("数据库未初始化")?;
+    f(conn).map_err(|e| e.to_string())
+}
+
+// 导入库（已 ATTACH 为 schema）的表中存在该列时返回列名，否则返回 NULL（兼容旧版本的数据库）
+pub(crate) fn attached_column_or_null(conn: &Connection, schema: &str, table: &str, column: &str) -> String {
+    let exists = conn
+        .prepare(&format!("PRAGMA {}.table_info({})", schema, table))
+        .and_then(|mut stmt| {
+            let columns = stmt.query_map([], |row| row.get::<_, String>(1))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(columns.iter().any(|c| c == column))
+        })
+        .unwrap_or(false);
+    if exists { column.to_string() } else { "NULL".to_string() }
+}
 
 // 清理文件和图片类型收藏项的自动生成标题
 fn migrate_favorites_auto_titles(conn: &Connection) {
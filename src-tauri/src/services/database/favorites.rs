@@ -1,5 +1,10 @@
 use super::models::{FavoriteItem, PaginatedResult, FavoritesQueryParams};
-use super::connection::{with_connection, MAX_CONTENT_LENGTH};
+use super::connection::{attached_column_or_null, with_connection, MAX_CONTENT_LENGTH};
+use super::groups::{group_display_name, GROUP_SUBTREE_SQL, DEFAULT_GROUP_NAME};
+use super::tags::{copy_item_tags, load_item_tags, tag_filter_sql, TAG_ITEM_CLIPBOARD, TAG_ITEM_FAVORITE};
+use super::revisions::save_revision;
+use super::formats::{copy_formats_to_favorite, set_item_text};
+use super::trash::{id_in_sql, move_to_trash, TRASH_ACTION_FAVORITE};
 use crate::utils::{truncate_string, truncate_around_keyword, truncate_html};
 use rusqlite::{params, OptionalExtension};
 use chrono;
@@ -37,75 +42,89 @@ pub fn update_missing_favorite_char_counts(items: Vec<(String, String, String)>)
     });
 }
 
-// 分页查询收藏列表
-pub fn query_favorites(params: FavoritesQueryParams) -> Result<PaginatedResult<FavoriteItem>, String> {
-    let search_keyword = params.search.clone();
-    
-    with_connection(|conn| {
-        let mut where_clauses = vec![];
-        let mut count_params: Vec<Box<dyn rusqlite::ToSql>> = vec![];
-        let mut query_params: Vec<Box<dyn rusqlite::ToSql>> = vec![];
-
-        if let Some(ref group_name) = params.group_name {
-            if group_name != "全部" {
-                where_clauses.push("group_name = ?");
-                count_params.push(Box::new(group_name.clone()));
-                query_params.push(Box::new(group_name.clone()));
-            }
-        }
+// 构建收藏查询的 WHERE 子句和参数
+fn favorites_filter(params: &FavoritesQueryParams) -> (String, Vec<Box<dyn rusqlite::ToSql>>) {
+    let mut where_clauses: Vec<String> = vec!["f.deleted_at IS NULL".to_string()];
+    let mut query_params: Vec<Box<dyn rusqlite::ToSql>> = vec![];
 
-        if let Some(ref search_query) = search_keyword {
-            if !search_query.is_empty() {
-                where_clauses.push("(title LIKE ? OR content LIKE ? OR html_content LIKE ?)");
-                let search_pattern = format!("%{}%", search_query);
-                count_params.push(Box::new(search_pattern.clone()));
-                count_params.push(Box::new(search_pattern.clone()));
-                count_params.push(Box::new(search_pattern.clone()));
-                query_params.push(Box::new(search_pattern.clone()));
-                query_params.push(Box::new(search_pattern.clone()));
-                query_params.push(Box::new(search_pattern));
-            }
+    if let Some(group_id) = params.group_id {
+        where_clauses.push(if params.include_descendants {
+            format!("f.group_id IN ({})", GROUP_SUBTREE_SQL)
+        } else {
+            "f.group_id = ?".to_string()
+        });
+        query_params.push(Box::new(group_id));
+    }
+
+    if let Some(ref search_query) = params.search {
+        if !search_query.is_empty() {
+            where_clauses.push("(f.title LIKE ? OR f.content LIKE ? OR f.html_content LIKE ? OR f.note LIKE ?)".to_string());
+            let search_pattern = format!("%{}%", search_query);
+            query_params.push(Box::new(search_pattern.clone()));
+            query_params.push(Box::new(search_pattern.clone()));
+            query_params.push(Box::new(search_pattern.clone()));
+            query_params.push(Box::new(search_pattern));
         }
+    }
 
-        if let Some(content_type) = params.content_type {
-            if content_type != "all" {
-                let pattern = format!("%{}%", content_type);
-                where_clauses.push("content_type LIKE ?");
-                count_params.push(Box::new(pattern.clone()));
-                query_params.push(Box::new(pattern));
-            }
+    if let Some(ref content_type) = params.content_type {
+        if content_type != "all" {
+            where_clauses.push("f.content_type LIKE ?".to_string());
+            query_params.push(Box::new(format!("%{}%", content_type)));
         }
+    }
 
-        let where_sql = if where_clauses.is_empty() {
-            String::new()
-        } else {
-            format!("WHERE {}", where_clauses.join(" AND "))
-        };
+    if let Some(after) = params.created_after {
+        where_clauses.push("f.created_at >= ?".to_string());
+        query_params.push(Box::new(after));
+    }
 
-        let total_count_sql = format!("SELECT COUNT(*) FROM favorites {}", where_sql);
-        let total_count: i64 = conn.query_row(&total_count_sql, rusqlite::params_from_iter(count_params), |row| row.get(0))?;
+    if let Some(before) = params.created_before {
+        where_clauses.push("f.created_at < ?".to_string());
+        query_params.push(Box::new(before));
+    }
 
-        let is_all_groups = params.group_name.is_none() || 
-                           params.group_name.as_ref().map(|g| g == "全部").unwrap_or(false);
-        
-        let query_sql = if is_all_groups {
-            // 查询全部分组时，按分组顺序排列
-            format!(
-                "SELECT f.id, f.title, f.content, f.html_content, f.content_type, f.image_id, f.group_name, f.item_order, f.paste_count, f.created_at, f.updated_at, f.char_count 
-                 FROM favorites f 
-                 LEFT JOIN groups g ON f.group_name = g.name 
-                 {} 
-                 ORDER BY CASE WHEN f.group_name = '全部' THEN 0 ELSE 1 END, COALESCE(g.order_index, 999999), f.item_order DESC, f.updated_at DESC 
-                 LIMIT ? OFFSET ?",
-                where_sql
-            )
+    for tag in &params.tags {
+        where_clauses.push(tag_filter_sql(TAG_ITEM_FAVORITE, "f.id"));
+        query_params.push(Box::new(tag.trim().to_string()));
+    }
+
+    (format!("WHERE {}", where_clauses.join(" AND ")), query_params)
+}
+
+// 统计符合条件的收藏项数量
+pub(super) fn count_favorites(conn: &rusqlite::Connection, params: &FavoritesQueryParams) -> Result<i64, rusqlite::Error> {
+    let (where_sql, query_params) = favorites_filter(params);
+    conn.query_row(
+        &format!("SELECT COUNT(*) FROM favorites f {}", where_sql),
+        rusqlite::params_from_iter(query_params),
+        |row| row.get(0)
+    )
+}
+
+// 分页查询收藏列表
+pub fn query_favorites(params: FavoritesQueryParams) -> Result<PaginatedResult<FavoriteItem>, String> {
+    let search_keyword = params.search.clone();
+    
+    with_connection(|conn| {
+        let total_count = count_favorites(conn, &params)?;
+        let (where_sql, mut query_params) = favorites_filter(&params);
+
+        // 查询全部分组时，默认分组在前，其余按分组顺序排列
+        let order_sql = if params.group_id.is_none() {
+            "CASE WHEN f.group_id IS NULL THEN 0 ELSE 1 END, COALESCE(g.order_index, 999999), f.item_order DESC, f.updated_at DESC"
         } else {
-            format!(
-                "SELECT id, title, content, html_content, content_type, image_id, group_name, item_order, paste_count, created_at, updated_at, char_count 
-                 FROM favorites {} ORDER BY item_order DESC, updated_at DESC LIMIT ? OFFSET ?",
-                where_sql
-            )
+            "f.item_order DESC, f.updated_at DESC"
         };
+        let query_sql = format!(
+            "SELECT f.id, f.title, f.content, f.html_content, f.content_type, f.image_id, f.group_id, f.item_order, f.paste_count, f.created_at, f.updated_at, f.char_count, g.name, f.note 
+             FROM favorites f 
+             LEFT JOIN groups g ON f.group_id = g.id 
+             {} 
+             ORDER BY {} 
+             LIMIT ? OFFSET ?",
+            where_sql, order_sql
+        );
 
         query_params.push(Box::new(params.limit));
         query_params.push(Box::new(params.offset));
@@ -160,12 +179,16 @@ pub fn query_favorites(params: FavoritesQueryParams) -> Result<PaginatedResult<F
                 title: row.get(1)?,
                 content: truncated_content,
                 html_content: truncated_html,
+                rtf_content: None,
                 content_type: content_type.clone(),
                 image_id: row.get(5)?,
-                group_name: row.get(6)?,
+                group_id: row.get(6)?,
+                group_name: row.get::<_, Option<String>>(12)?.unwrap_or_else(|| DEFAULT_GROUP_NAME.to_string()),
                 item_order: row.get(7)?,
                 paste_count: row.get(8)?,
                 char_count: final_char_count,
+                note: row.get(13)?,
+                tags: Vec::new(),
                 created_at: row.get(9)?,
                 updated_at: row.get(10)?,
             }, char_count.is_none() && needs_char_count, id, content, content_type))
@@ -183,64 +206,28 @@ pub fn query_favorites(params: FavoritesQueryParams) -> Result<PaginatedResult<F
         if !items_to_update.is_empty() {
             update_missing_favorite_char_counts(items_to_update);
         }
+
+        let ids: Vec<String> = result_items.iter().map(|item| item.id.clone()).collect();
+        let mut tags = load_item_tags(conn, TAG_ITEM_FAVORITE, &ids)?;
+        for item in result_items.iter_mut() {
+            item.tags = tags.remove(&item.id).unwrap_or_default();
+        }
         
         Ok(PaginatedResult::new(total_count, result_items, params.offset, params.limit))
     })
 }
 
-// 按逗号拆分图片ID
-fn split_image_ids(s: &str) -> Vec<String> {
-    s.split(',')
-        .map(|x| x.trim())
-        .filter(|x| !x.is_empty())
-        .map(|x| x.to_string())
-        .collect()
-}
-
-// 检查图片ID是否仍被 clipboard 或 favorites 引用
-fn is_image_id_referenced(conn: &rusqlite::Connection, image_id: &str) -> Result<bool, rusqlite::Error> {
-    let exact = image_id;
-    let p1 = format!("{},%", image_id);
-    let p2 = format!("%,{},%", image_id);
-    let p3 = format!("%,{}", image_id);
-
-    let q = |table: &str| -> Result<bool, rusqlite::Error> {
-        let sql = format!(
-            "SELECT EXISTS(SELECT 1 FROM {} WHERE image_id = ?1 OR image_id LIKE ?2 OR image_id LIKE ?3 OR image_id LIKE ?4)",
-            table
-        );
-        let exists: i64 = conn.query_row(&sql, params![exact, p1, p2, p3], |row| row.get(0))?;
-        Ok(exists != 0)
-    };
-
-    Ok(q("clipboard")? || q("favorites")?)
-}
-
-// 删除图片文件
-fn delete_image_files(image_ids: Vec<String>) -> Result<(), String> {
-    if image_ids.is_empty() { return Ok(()); }
-    let data_dir = crate::services::get_data_directory()?;
-    let images_dir = data_dir.join("clipboard_images");
-    for iid in image_ids {
-        let p = images_dir.join(format!("{}.png", iid));
-        if p.exists() {
-            let _ = std::fs::remove_file(&p);
-        }
-    }
-    Ok(())
-}
-
-// 获取收藏总数
-pub fn get_favorites_count(group_name: Option<String>) -> Result<i64, String> {
+// 获取收藏总数（group_id 为空时统计全部，可包含子孙分组）
+pub fn get_favorites_count(group_id: Option<i64>, include_descendants: bool) -> Result<i64, String> {
     with_connection(|conn| {
-        let (sql, params): (String, Vec<Box<dyn rusqlite::ToSql>>) = if let Some(group) = group_name {
-            if group == "全部" {
-                ("SELECT COUNT(*) FROM favorites".to_string(), vec![])
+        let (sql, params): (String, Vec<Box<dyn rusqlite::ToSql>>) = if let Some(group) = group_id {
+            if include_descendants {
+                (format!("SELECT COUNT(*) FROM favorites WHERE deleted_at IS NULL AND group_id IN ({})", GROUP_SUBTREE_SQL), vec![Box::new(group)])
             } else {
-                ("SELECT COUNT(*) FROM favorites WHERE group_name = ?".to_string(), vec![Box::new(group)])
+                ("SELECT COUNT(*) FROM favorites WHERE deleted_at IS NULL AND group_id = ?".to_string(), vec![Box::new(group)])
             }
         } else {
-            ("SELECT COUNT(*) FROM favorites".to_string(), vec![])
+            ("SELECT COUNT(*) FROM favorites WHERE deleted_at IS NULL".to_string(), vec![])
         };
         
         conn.query_row(&sql, rusqlite::params_from_iter(params), |row| row.get(0))
@@ -249,44 +236,63 @@ pub fn get_favorites_count(group_name: Option<String>) -> Result<i64, String> {
 
 // 根据ID获取收藏项（完整内容，不截断）
 pub fn get_favorite_by_id(id: &str) -> Result<Option<FavoriteItem>, String> {
+    with_connection(|conn| load_favorite(conn, id))
+}
+
+// 按 ID 列表获取收藏项（完整内容，按列表顺序，跳过不存在的项）
+pub fn get_favorites_by_ids(ids: &[String]) -> Result<Vec<FavoriteItem>, String> {
     with_connection(|conn| {
-        conn.query_row(
-            "SELECT id, title, content, html_content, content_type, image_id, group_name, item_order, paste_count, created_at, updated_at, char_count 
-             FROM favorites WHERE id = ?",
-            params![id],
-            |row| {
-                let content: String = row.get(2)?;
-                let content_type: String = row.get(4)?;
-                let char_count: Option<i64> = row.get(11)?;
-                
-                // 计算字符数
-                let final_char_count = if char_count.is_none() && (content_type.contains("text") || content_type.contains("rich_text")) && !content.is_empty() {
-                    Some(content.chars().count() as i64)
-                } else {
-                    char_count
-                };
-                
-                Ok(FavoriteItem {
-                    id: row.get(0)?,
-                    title: row.get(1)?,
-                    content,
-                    html_content: row.get(3)?,
-                    content_type,
-                    image_id: row.get(5)?,
-                    group_name: row.get(6)?,
-                    item_order: row.get(7)?,
-                    paste_count: row.get(8)?,
-                    char_count: final_char_count,
-                    created_at: row.get(9)?,
-                    updated_at: row.get(10)?,
-                })
-            }
-        )
-        .optional()
-        .map_err(|e| e.into())
+        let mut items = Vec::with_capacity(ids.len());
+        for id in ids {
+            items.extend(load_favorite(conn, id)?);
+        }
+        Ok(items)
     })
 }
 
+fn load_favorite(conn: &rusqlite::Connection, id: &str) -> Result<Option<FavoriteItem>, rusqlite::Error> {
+    let tags = load_item_tags(conn, TAG_ITEM_FAVORITE, &[id.to_string()])?
+        .remove(id)
+        .unwrap_or_default();
+    conn.query_row(
+        "SELECT f.id, f.title, f.content, f.html_content, f.content_type, f.image_id, f.group_id, f.item_order, f.paste_count, f.created_at, f.updated_at, f.char_count, f.rtf_content, g.name, f.note 
+         FROM favorites f LEFT JOIN groups g ON f.group_id = g.id WHERE f.id = ? AND f.deleted_at IS NULL",
+        params![id],
+        |row| {
+            let content: String = row.get(2)?;
+            let content_type: String = row.get(4)?;
+            let char_count: Option<i64> = row.get(11)?;
+            
+            // 计算字符数
+            let final_char_count = if char_count.is_none() && (content_type.contains("text") || content_type.contains("rich_text")) && !content.is_empty() {
+                Some(content.chars().count() as i64)
+            } else {
+                char_count
+            };
+            
+            Ok(FavoriteItem {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                content,
+                html_content: row.get(3)?,
+                rtf_content: row.get(12)?,
+                content_type,
+                image_id: row.get(5)?,
+                group_id: row.get(6)?,
+                group_name: row.get::<_, Option<String>>(13)?.unwrap_or_else(|| DEFAULT_GROUP_NAME.to_string()),
+                item_order: row.get(7)?,
+                paste_count: row.get(8)?,
+                char_count: final_char_count,
+                note: row.get(14)?,
+                tags,
+                created_at: row.get(9)?,
+                updated_at: row.get(10)?,
+            })
+        }
+    )
+    .optional()
+}
+
 pub fn increment_favorite_paste_count(id: &str) -> Result<(), String> {
     with_connection(|conn| {
         conn.execute(
@@ -320,17 +326,17 @@ fn reorder_favorite_items(conn: &rusqlite::Connection, from_idx: usize, to_idx:
 }
 
 // 移动收藏项
-pub fn move_favorite_by_id(group_name: Option<String>, from_id: String, to_id: String) -> Result<(), String> {
+pub fn move_favorite_by_id(group_id: Option<i64>, from_id: String, to_id: String) -> Result<(), String> {
     if from_id == to_id { return Ok(()); }
 
     with_connection(|conn| {
-        let (sql, params): (String, Vec<Box<dyn rusqlite::ToSql>>) = match &group_name {
-            Some(group) if group != "全部" => (
-                "SELECT id, item_order FROM favorites WHERE group_name = ? ORDER BY item_order DESC, updated_at DESC".to_string(),
-                vec![Box::new(group.clone()) as Box<dyn rusqlite::ToSql>]
+        let (sql, params): (String, Vec<Box<dyn rusqlite::ToSql>>) = match group_id {
+            Some(group) => (
+                "SELECT id, item_order FROM favorites WHERE group_id = ? AND deleted_at IS NULL ORDER BY item_order DESC, updated_at DESC".to_string(),
+                vec![Box::new(group) as Box<dyn rusqlite::ToSql>]
             ),
             _ => (
-                "SELECT id, item_order FROM favorites ORDER BY item_order DESC, updated_at DESC".to_string(),
+                "SELECT id, item_order FROM favorites WHERE deleted_at IS NULL ORDER BY item_order DESC, updated_at DESC".to_string(),
                 vec![]
             ),
         };
@@ -349,230 +355,388 @@ pub fn move_favorite_by_id(group_name: Option<String>, from_id: String, to_id: S
 }
 
 // 从剪贴板历史添加到收藏
-pub fn add_clipboard_to_favorites(clipboard_id: i64, group_name: Option<String>) -> Result<FavoriteItem, String> {
+pub fn add_clipboard_to_favorites(clipboard_id: i64, group_id: Option<i64>) -> Result<FavoriteItem, String> {
+    with_connection(|conn| {
+        let group_name = group_display_name(conn, group_id)?;
+        copy_clipboard_to_favorites(conn, clipboard_id, group_id, group_name)
+    })
+}
+
+// 批量添加到收藏（保持列表中的先后顺序），返回新建的收藏项
+pub fn add_clipboard_items_to_favorites(clipboard_ids: &[i64], group_id: Option<i64>) -> Result<Vec<FavoriteItem>, String> {
+    with_connection(|conn| {
+        let group_name = group_display_name(conn, group_id)?;
+        let tx = conn.unchecked_transaction()?;
+        // 后添加的项排在前面，所以倒序添加
+        let mut items = clipboard_ids
+            .iter()
+            .rev()
+            .map(|id| copy_clipboard_to_favorites(&tx, *id, group_id, group_name.clone()))
+            .collect::<Result<Vec<_>, _>>()?;
+        tx.commit()?;
+        items.reverse();
+        Ok(items)
+    })
+}
+
+fn copy_clipboard_to_favorites(conn: &rusqlite::Connection, clipboard_id: i64, group_id: Option<i64>, group_name: String) -> Result<FavoriteItem, rusqlite::Error> {
     use uuid::Uuid;
+
+    let (content, html_content, content_type, image_id, char_count, rtf_content, title, note) = conn.query_row(
+        "SELECT content, html_content, content_type, image_id, char_count, rtf_content, title, note FROM clipboard WHERE id = ?",
+        params![clipboard_id],
+        |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<i64>>(4)?,
+                row.get::<_, Option<String>>(5)?,
+                row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+                row.get::<_, Option<String>>(7)?,
+            ))
+        }
+    )?;
+
+    let final_char_count = if char_count.is_none() && (content_type.contains("text") || content_type.contains("rich_text")) && !content.is_empty() {
+        Some(content.chars().count() as i64)
+    } else {
+        char_count
+    };
     
-    let group_name = group_name.unwrap_or_else(|| "全部".to_string());
+    let id = Uuid::new_v4().to_string();
+    let now = chrono::Local::now().timestamp();
     
-    with_connection(|conn| {
-        let (content, html_content, content_type, image_id, char_count) = conn.query_row(
-            "SELECT content, html_content, content_type, image_id, char_count FROM clipboard WHERE id = ?",
-            params![clipboard_id],
-            |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, Option<String>>(1)?,
-                    row.get::<_, String>(2)?,
-                    row.get::<_, Option<String>>(3)?,
-                    row.get::<_, Option<i64>>(4)?,
-                ))
-            }
-        )?;
-        
-        let title = String::new();
-
-        let final_char_count = if char_count.is_none() && (content_type.contains("text") || content_type.contains("rich_text")) && !content.is_empty() {
-            Some(content.chars().count() as i64)
-        } else {
-            char_count
-        };
-        
-        let id = Uuid::new_v4().to_string();
-        let now = chrono::Local::now().timestamp();
-        
-        let max_order: i64 = conn.query_row(
-            "SELECT COALESCE(MAX(item_order), 0) FROM favorites WHERE group_name = ?",
-            params![&group_name],
-            |row| row.get(0)
-        ).unwrap_or(0);
-        let new_order = max_order + 1;
-        
-        conn.execute(
-            "INSERT INTO favorites (id, title, content, html_content, content_type, image_id, group_name, item_order, char_count, created_at, updated_at) 
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-            params![
-                &id,
-                &title,
-                &content,
-                &html_content,
-                &content_type,
-                &image_id,
-                &group_name,
-                new_order,
-                final_char_count,
-                now,
-                now,
-            ],
-        )?;
-        
-        Ok(FavoriteItem {
-            id,
-            title,
-            content,
-            html_content,
-            content_type,
-            image_id,
-            group_name,
-            item_order: new_order,
-            paste_count: 0,
-            char_count: final_char_count,
-            created_at: now,
-            updated_at: now,
-        })
+    let max_order: i64 = conn.query_row(
+        "SELECT COALESCE(MAX(item_order), 0) FROM favorites WHERE group_id IS ? AND deleted_at IS NULL",
+        params![group_id],
+        |row| row.get(0)
+    ).unwrap_or(0);
+    let new_order = max_order + 1;
+    
+    conn.execute(
+        "INSERT INTO favorites (id, title, content, html_content, content_type, image_id, group_id, item_order, char_count, created_at, updated_at, rtf_content, note) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![
+            &id,
+            &title,
+            &content,
+            &html_content,
+            &content_type,
+            &image_id,
+            group_id,
+            new_order,
+            final_char_count,
+            now,
+            now,
+            &rtf_content,
+            &note,
+        ],
+    )?;
+
+    copy_item_tags(conn, TAG_ITEM_CLIPBOARD, &clipboard_id.to_string(), TAG_ITEM_FAVORITE, &id)?;
+    copy_formats_to_favorite(conn, clipboard_id, &id)?;
+    let tags = load_item_tags(conn, TAG_ITEM_FAVORITE, std::slice::from_ref(&id))?
+        .remove(&id)
+        .unwrap_or_default();
+    
+    Ok(FavoriteItem {
+        id,
+        title,
+        content,
+        html_content,
+        rtf_content,
+        content_type,
+        image_id,
+        group_id,
+        group_name,
+        item_order: new_order,
+        paste_count: 0,
+        char_count: final_char_count,
+        note,
+        tags,
+        created_at: now,
+        updated_at: now,
     })
 }
 
 // 移动收藏项到指定分组
-pub fn move_favorite_to_group(id: String, group_name: String) -> Result<(), String> {
+pub fn move_favorite_to_group(id: String, group_id: Option<i64>) -> Result<(), String> {
     with_connection(|conn| {
-        let existing_item = conn.query_row(
-            "SELECT id, group_name FROM favorites WHERE id = ?",
-            params![&id],
-            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
-        )?;
-        
-        let old_group_name = existing_item.1;
-        
-        if old_group_name == group_name {
-            return Ok(());
-        }
-        
         let now = chrono::Local::now().timestamp();
-        
-        let max_order: i64 = conn.query_row(
-            "SELECT COALESCE(MAX(item_order), 0) FROM favorites WHERE group_name = ?",
-            params![&group_name], |row| row.get(0)
-        ).unwrap_or(0);
-        let new_order = max_order + 1;
-        
-        conn.execute(
-            "UPDATE favorites SET group_name = ?1, item_order = ?2, updated_at = ?3 WHERE id = ?4",
-            params![&group_name, new_order, now, &id],
-        )?;
-        
-        let mut stmt = conn.prepare(
-            "SELECT id FROM favorites WHERE group_name = ? ORDER BY item_order DESC, updated_at DESC"
-        )?;
-        let item_ids: Vec<String> = stmt
-            .query_map(params![&old_group_name], |row| row.get(0))?
-            .collect::<Result<Vec<String>, _>>()?;
-        drop(stmt);
-        
-        for (index, item_id) in item_ids.iter().enumerate() {
-            conn.execute(
-                "UPDATE favorites SET item_order = ?1, updated_at = ?2 WHERE id = ?3",
-                params![index as i64, now, item_id],
-            )?;
+        if let Some(old_group_id) = move_favorite_row(conn, &id, group_id, now)? {
+            renumber_group_items(conn, old_group_id, now)?;
         }
-        
         Ok(())
     })
 }
 
-// 删除收藏项
-pub fn delete_favorite(id: String) -> Result<(), String> {
-    let images_to_delete: Vec<String> = with_connection(|conn| {
-        let image_ids_opt: Option<Option<String>> = conn
-            .query_row(
-                "SELECT image_id FROM favorites WHERE id = ?",
-                params![&id],
-                |row| row.get::<_, Option<String>>(0),
-            )
-            .optional()?;
-        let image_ids: Option<String> = image_ids_opt.flatten();
-
-        conn.execute("DELETE FROM favorites WHERE id = ?1", params![id])?;
-
-        let mut to_delete = Vec::new();
-        if let Some(ids) = image_ids {
-            for iid in split_image_ids(&ids) {
-                if !is_image_id_referenced(conn, &iid)? {
-                    to_delete.push(iid);
-                }
+// 批量移动收藏项到指定分组（保持列表中的先后顺序），返回实际移动的项数
+pub fn move_favorites_to_group(ids: &[String], group_id: Option<i64>) -> Result<usize, String> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let now = chrono::Local::now().timestamp();
+        let mut old_groups: Vec<Option<i64>> = Vec::new();
+        // 后移入的项排在前面，所以倒序处理
+        for id in ids.iter().rev() {
+            if let Some(old_group_id) = move_favorite_row(&tx, id, group_id, now)? {
+                old_groups.push(old_group_id);
             }
         }
-        Ok(to_delete)
-    })?;
+        let moved = old_groups.len();
+        old_groups.sort();
+        old_groups.dedup();
+        for old_group_id in old_groups {
+            renumber_group_items(&tx, old_group_id, now)?;
+        }
+        tx.commit()?;
+        Ok(moved)
+    })
+}
+
+// 把收藏项移到目标分组末尾（排在最前），返回原分组；已在目标分组时返回 None
+fn move_favorite_row(conn: &rusqlite::Connection, id: &str, group_id: Option<i64>, now: i64) -> Result<Option<Option<i64>>, rusqlite::Error> {
+    let old_group_id: Option<i64> = conn.query_row(
+        "SELECT group_id FROM favorites WHERE id = ?",
+        params![id],
+        |row| row.get(0)
+    )?;
+    
+    if old_group_id == group_id {
+        return Ok(None);
+    }
+    
+    let max_order: i64 = conn.query_row(
+        "SELECT COALESCE(MAX(item_order), 0) FROM favorites WHERE group_id IS ? AND deleted_at IS NULL",
+        params![group_id], |row| row.get(0)
+    ).unwrap_or(0);
+    
+    conn.execute(
+        "UPDATE favorites SET group_id = ?1, item_order = ?2, updated_at = ?3 WHERE id = ?4",
+        params![group_id, max_order + 1, now, id],
+    )?;
+    Ok(Some(old_group_id))
+}
+
+// 重新编排分组内收藏项的顺序（移出项目后调用）
+fn renumber_group_items(conn: &rusqlite::Connection, group_id: Option<i64>, now: i64) -> Result<(), rusqlite::Error> {
+    let item_ids: Vec<String> = conn.prepare(
+        "SELECT id FROM favorites WHERE group_id IS ? AND deleted_at IS NULL ORDER BY item_order DESC, updated_at DESC"
+    )?.query_map(params![group_id], |row| row.get(0))?
+      .collect::<Result<Vec<String>, _>>()?;
+    
+    for (index, item_id) in item_ids.iter().enumerate() {
+        conn.execute(
+            "UPDATE favorites SET item_order = ?1, updated_at = ?2 WHERE id = ?3",
+            params![index as i64, now, item_id],
+        )?;
+    }
+    Ok(())
+}
 
-    delete_image_files(images_to_delete)
+// 删除收藏项（移到回收站，图片在清除回收站时才删除）
+pub fn delete_favorite(id: String) -> Result<(), String> {
+    with_connection(|conn| {
+        move_to_trash(conn, TRASH_ACTION_FAVORITE, "favorites", "id = ?1", params![id])?;
+        Ok(())
+    })
+}
+
+// 设置收藏项的备注（空白内容视为清除）
+pub fn update_favorite_note(id: &str, note: Option<String>) -> Result<(), String> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let rows = with_connection(|conn| conn.execute(
+        "UPDATE favorites SET note = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+        params![note, id],
+    ))?;
+    match rows {
+        0 => Err(format!("收藏项不存在: {}", id)),
+        _ => Ok(()),
+    }
+}
+
+// 批量删除收藏项（作为一次删除操作移到回收站，可整体撤销），返回删除的项数
+pub fn delete_favorites(ids: &[String]) -> Result<usize, String> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    with_connection(|conn| {
+        let params: Vec<&dyn rusqlite::ToSql> = ids.iter().map(|id| id as &dyn rusqlite::ToSql).collect();
+        move_to_trash(conn, TRASH_ACTION_FAVORITE, "favorites", &id_in_sql(ids.len()), &params)
+    })
 }
 
 // 添加收藏项
-pub fn add_favorite(title: String, content: String, group_name: Option<String>) -> Result<FavoriteItem, String> {
+pub fn add_favorite(title: String, content: String, group_id: Option<i64>) -> Result<FavoriteItem, String> {
     use uuid::Uuid;
     
-    let group_name = group_name.unwrap_or_else(|| "全部".to_string());
     let (id, now) = (Uuid::new_v4().to_string(), chrono::Local::now().timestamp());
 
     let char_count = Some(content.chars().count() as i64);
     
     with_connection(|conn| {
+        let group_name = group_display_name(conn, group_id)?;
         let max_order: i64 = conn.query_row(
-            "SELECT COALESCE(MAX(item_order), 0) FROM favorites WHERE group_name = ?",
-            params![&group_name], |row| row.get(0)
+            "SELECT COALESCE(MAX(item_order), 0) FROM favorites WHERE group_id IS ? AND deleted_at IS NULL",
+            params![group_id], |row| row.get(0)
         ).unwrap_or(0);
         let new_order = max_order + 1;
         
         conn.execute(
-            "INSERT INTO favorites (id, title, content, html_content, content_type, image_id, group_name, item_order, char_count, created_at, updated_at) 
+            "INSERT INTO favorites (id, title, content, html_content, content_type, image_id, group_id, item_order, char_count, created_at, updated_at) 
              VALUES (?1, ?2, ?3, NULL, 'text', NULL, ?4, ?5, ?6, ?7, ?8)",
-            params![&id, &title, &content, &group_name, new_order, char_count, now, now],
+            params![&id, &title, &content, group_id, new_order, char_count, now, now],
         )?;
         
         Ok(FavoriteItem {
-            id: id.clone(), title, content, html_content: None,
-            content_type: "text".to_string(), image_id: None, group_name,
-            item_order: new_order, paste_count: 0, char_count, created_at: now, updated_at: now,
+            id: id.clone(), title, content, html_content: None, rtf_content: None,
+            content_type: "text".to_string(), image_id: None, group_id, group_name,
+            item_order: new_order, paste_count: 0, char_count, note: None, tags: Vec::new(), created_at: now, updated_at: now,
         })
     })
 }
 
-// 更新收藏项
-pub fn update_favorite(id: String, title: String, content: String, group_name: Option<String>) -> Result<FavoriteItem, String> {
-    let group_name = group_name.unwrap_or_else(|| "全部".to_string());
-    
+// 更新收藏项（修改前的内容保存为历史版本）
+pub fn update_favorite(id: String, title: String, content: String, group_id: Option<i64>) -> Result<FavoriteItem, String> {
     with_connection(|conn| {
-        let (old_group_name, content_type) = conn.query_row(
-            "SELECT group_name, content_type FROM favorites WHERE id = ?", params![&id],
-            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        let (old_group_id, content_type) = conn.query_row(
+            "SELECT group_id, content_type FROM favorites WHERE id = ?", params![&id],
+            |row| Ok((row.get::<_, Option<i64>>(0)?, row.get::<_, String>(1)?))
         ).optional()?.ok_or(rusqlite::Error::QueryReturnedNoRows)?;
+        save_revision(conn, TAG_ITEM_FAVORITE, &id, Some(&title), &content)?;
         
         let now = chrono::Local::now().timestamp();
 
         let char_count = calculate_char_count(&content, &content_type);
         
-        if old_group_name != group_name {
+        if old_group_id != group_id {
             let max_order: i64 = conn.query_row(
-                "SELECT COALESCE(MAX(item_order), 0) FROM favorites WHERE group_name = ?",
-                params![&group_name], |row| row.get(0)
+                "SELECT COALESCE(MAX(item_order), 0) FROM favorites WHERE group_id IS ? AND deleted_at IS NULL",
+                params![group_id], |row| row.get(0)
             ).unwrap_or(0);
             let new_order = max_order + 1;
             
             conn.execute(
-                "UPDATE favorites SET title = ?1, content = ?2, group_name = ?3, item_order = ?4, char_count = ?5, updated_at = ?6 WHERE id = ?7",
-                params![&title, &content, &group_name, new_order, char_count, now, &id],
+                "UPDATE favorites SET title = ?1, group_id = ?2, item_order = ?3, char_count = ?4, updated_at = ?5 WHERE id = ?6",
+                params![&title, group_id, new_order, char_count, now, &id],
             )?;
             
-            let item_ids: Vec<String> = conn.prepare(
-                "SELECT id FROM favorites WHERE group_name = ? ORDER BY item_order DESC, updated_at DESC"
-            )?.query_map(params![&old_group_name], |row| row.get(0))?
-              .collect::<Result<Vec<String>, _>>()?;
-            
-            for (index, item_id) in item_ids.iter().enumerate() {
-                conn.execute(
-                    "UPDATE favorites SET item_order = ?1, updated_at = ?2 WHERE id = ?3",
-                    params![index as i64, now, item_id],
-                )?;
-            }
+            renumber_group_items(conn, old_group_id, now)?;
         } else {
             conn.execute(
-                "UPDATE favorites SET title = ?1, content = ?2, char_count = ?3, updated_at = ?4 WHERE id = ?5",
-                params![&title, &content, char_count, now, &id],
+                "UPDATE favorites SET title = ?1, char_count = ?2, updated_at = ?3 WHERE id = ?4",
+                params![&title, char_count, now, &id],
             )?;
         }
+        set_item_text(conn, TAG_ITEM_FAVORITE, &id, &content)?;
         Ok(())
     })?;
     
     get_favorite_by_id(&id)?.ok_or_else(|| format!("更新后无法获取收藏项: {}", id))
 }
 
+
+// 合并导入库（已 ATTACH 为 schema）中的收藏项，ID 已存在的跳过；分组按导入时的映射转换，找不到的归入默认分组
+pub(crate) fn merge_attached_favorites(conn: &rusqlite::Connection, schema: &str, groups: &std::collections::HashMap<String, i64>) -> Result<(), rusqlite::Error> {
+    let note_column = attached_column_or_null(conn, schema, "favorites", "note");
+    let rtf_column = attached_column_or_null(conn, schema, "favorites", "rtf_content");
+    // 回收站中的收藏项不导入
+    let deleted_column = attached_column_or_null(conn, schema, "favorites", "deleted_at");
+    let rows = ["CAST(group_id AS TEXT)", "group_name"]
+        .iter()
+        .find_map(|group_column| {
+            let sql = format!(
+                "SELECT id, title, content, html_content, content_type, image_id, {}, item_order, created_at, updated_at, {}, {} FROM {}.favorites WHERE {} IS NULL",
+                group_column, note_column, rtf_column, schema, deleted_column
+            );
+            let mut stmt = conn.prepare(&sql).ok()?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, String>(2)?,
+                        row.get::<_, Option<String>>(3)?,
+                        row.get::<_, String>(4)?,
+                        row.get::<_, Option<String>>(5)?,
+                        row.get::<_, Option<String>>(6)?,
+                        row.get::<_, i64>(7)?,
+                        row.get::<_, i64>(8)?,
+                        row.get::<_, i64>(9)?,
+                        row.get::<_, Option<String>>(10)?,
+                        row.get::<_, Option<String>>(11)?,
+                    ))
+                })
+                .and_then(|rows| rows.collect::<Result<Vec<_>, _>>());
+            Some(rows)
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    for (id, title, content, html_content, content_type, image_id, group_key, item_order, created_at, updated_at, note, rtf_content) in rows {
+        let group_id = group_key.and_then(|key| groups.get(&key).copied());
+        conn.execute(
+            "INSERT OR IGNORE INTO favorites (id, title, content, html_content, content_type, image_id, group_id, item_order, created_at, updated_at, note, rtf_content)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![id, title, content, html_content, content_type, image_id, group_id, item_order, created_at, updated_at, note, rtf_content],
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::clipboard::count_clipboard_items;
+    use super::super::connection::create_tables;
+    use super::super::models::QueryParams;
+
+    #[test]
+    fn clipboard_title_and_note_carry_over_to_favorites() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO clipboard (id, content, title, note, created_at, updated_at)
+             VALUES (1, 'https://staging/reset', 'Staging reset', 'expires friday', 0, 0)",
+            [],
+        ).unwrap();
+
+        let search = |text: &str| Some(text.to_string());
+        let history = QueryParams { search: search("FRIDAY"), ..Default::default() };
+        assert_eq!(count_clipboard_items(&conn, &history).unwrap(), 1);
+
+        let favorite = copy_clipboard_to_favorites(&conn, 1, None, DEFAULT_GROUP_NAME.to_string()).unwrap();
+        assert_eq!((favorite.title.as_str(), favorite.note.as_deref()), ("Staging reset", Some("expires friday")));
+        let favorites = FavoritesQueryParams { search: search("friday"), ..Default::default() };
+        assert_eq!(count_favorites(&conn, &favorites).unwrap(), 1);
+    }
+
+    #[test]
+    fn favorite_keeps_clipboard_formats() {
+        use super::super::formats::{build_formats, save_clipboard_formats};
+
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO clipboard (id, content, html_content, content_type, created_at, updated_at)
+             VALUES (1, 'hi', '<b>hi</b>', 'rich_text', 0, 0)",
+            [],
+        ).unwrap();
+        save_clipboard_formats(&conn, 1, &build_formats("hi", "rich_text", Some("<b>hi</b>"), None)).unwrap();
+
+        let favorite = copy_clipboard_to_favorites(&conn, 1, None, DEFAULT_GROUP_NAME.to_string()).unwrap();
+        let formats = || -> Vec<(String, String)> {
+            conn.prepare("SELECT mime_type, data FROM favorite_formats WHERE item_id = ?1 ORDER BY rowid").unwrap()
+                .query_map(params![favorite.id], |row| Ok((row.get(0)?, row.get(1)?))).unwrap()
+                .collect::<Result<_, _>>().unwrap()
+        };
+        assert_eq!(formats(), vec![
+            ("text/plain".to_string(), "hi".to_string()),
+            ("text/html".to_string(), "<b>hi</b>".to_string()),
+        ]);
+
+        conn.execute("DELETE FROM favorites WHERE id = ?1", params![favorite.id]).unwrap();
+        assert!(formats().is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+use super::models::ClipboardFormat;
+use super::connection::with_connection;
+use super::tags::TAG_ITEM_CLIPBOARD;
+use rusqlite::{params, ToSql};
+
+pub const MIME_TEXT: &str = "text/plain";
+pub const MIME_HTML: &str = "text/html";
+pub const MIME_RTF: &str = "text/rtf";
+pub const MIME_IMAGE: &str = "image/png";
+pub const MIME_FILES: &str = "text/uri-list";
+
+// 根据剪贴板项的字段生成格式列表（新条目和旧数据迁移共用）
+pub fn build_formats(
+    content: &str,
+    content_type: &str,
+    html_content: Option<&str>,
+    rtf_content: Option<&str>,
+) -> Vec<ClipboardFormat> {
+    let mut formats = Vec::new();
+    let format = |mime: &str, data: String| ClipboardFormat { mime_type: mime.to_string(), data };
+
+    if let Some(json_str) = content.strip_prefix("files:") {
+        let paths: Vec<String> = serde_json::from_str::<serde_json::Value>(json_str)
+            .ok()
+            .and_then(|json| json["files"].as_array().cloned())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|f| f["path"].as_str().map(String::from))
+            .collect();
+
+        if content_type.starts_with("image") {
+            if let Some(path) = paths.first() {
+                formats.push(format(MIME_IMAGE, path.clone()));
+            }
+        }
+        if !paths.is_empty() {
+            formats.push(format(MIME_FILES, paths.join("\n")));
+        }
+        return formats;
+    }
+
+    // 旧格式图片（image:{id}）
+    if let Some(image_id) = content.strip_prefix("image:") {
+        formats.push(format(MIME_IMAGE, format!("clipboard_images/{}.png", image_id)));
+        return formats;
+    }
+
+    formats.push(format(MIME_TEXT, content.to_string()));
+    if let Some(html) = html_content {
+        formats.push(format(MIME_HTML, html.to_string()));
+    }
+    if let Some(rtf) = rtf_content {
+        formats.push(format(MIME_RTF, rtf.to_string()));
+    }
+    formats
+}
+
+// 保存剪贴板项的全部格式（覆盖同类型）
+pub fn save_clipboard_formats(
+    conn: &rusqlite::Connection,
+    item_id: i64,
+    formats: &[ClipboardFormat],
+) -> Result<(), rusqlite::Error> {
+    let mut stmt = conn.prepare(
+        "INSERT OR REPLACE INTO clipboard_formats (item_id, mime_type, data) VALUES (?1, ?2, ?3)"
+    )?;
+    for format in formats {
+        stmt.execute(params![item_id, format.mime_type, format.data])?;
+    }
+    Ok(())
+}
+
+// 收藏项复制剪贴板项的全部格式（从历史添加到收藏时调用）
+pub(super) fn copy_formats_to_favorite(
+    conn: &rusqlite::Connection,
+    clipboard_id: i64,
+    favorite_id: &str,
+) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "INSERT OR REPLACE INTO favorite_formats (item_id, mime_type, data)
+         SELECT ?2, mime_type, data FROM clipboard_formats WHERE item_id = ?1 ORDER BY rowid",
+        params![clipboard_id, favorite_id],
+    )?;
+    Ok(())
+}
+
+// 条目表和对应的格式表（kind 为 TAG_ITEM_CLIPBOARD 或 TAG_ITEM_FAVORITE）
+fn item_tables(kind: &str) -> (&'static str, &'static str) {
+    if kind == TAG_ITEM_CLIPBOARD {
+        ("clipboard", "clipboard_formats")
+    } else {
+        ("favorites", "favorite_formats")
+    }
+}
+
+// 条目的文本和 HTML 同时保存在条目列和格式表中，修改后的内容只能经过下面两个函数写入
+
+// 设置条目的文本内容，返回更新的条目数
+pub(super) fn set_item_text(
+    conn: &rusqlite::Connection,
+    kind: &str,
+    item_id: &dyn ToSql,
+    content: &str,
+) -> Result<usize, rusqlite::Error> {
+    let (items, formats) = item_tables(kind);
+    let rows = conn.execute(
+        &format!("UPDATE {} SET content = ?1 WHERE id = ?2", items),
+        params![content, item_id],
+    )?;
+    conn.execute(
+        &format!("UPDATE {} SET data = ?1 WHERE item_id = ?2 AND mime_type = ?3", formats),
+        params![content, item_id, MIME_TEXT],
+    )?;
+    Ok(rows)
+}
+
+// 设置或清除条目的 HTML（只为已有格式记录的条目补充 HTML 格式）
+pub(super) fn set_item_html(
+    conn: &rusqlite::Connection,
+    kind: &str,
+    item_id: &dyn ToSql,
+    html: Option<&str>,
+) -> Result<(), rusqlite::Error> {
+    let (items, formats) = item_tables(kind);
+    conn.execute(
+        &format!("UPDATE {} SET html_content = ?1 WHERE id = ?2", items),
+        params![html, item_id],
+    )?;
+    let Some(html) = html else {
+        conn.execute(
+            &format!("DELETE FROM {} WHERE item_id = ?1 AND mime_type = ?2", formats),
+            params![item_id, MIME_HTML],
+        )?;
+        return Ok(());
+    };
+    let updated = conn.execute(
+        &format!("UPDATE {} SET data = ?1 WHERE item_id = ?2 AND mime_type = ?3", formats),
+        params![html, item_id, MIME_HTML],
+    )?;
+    if updated == 0 {
+        conn.execute(
+            &format!(
+                "INSERT INTO {0} (item_id, mime_type, data) SELECT ?1, ?2, ?3
+                 WHERE EXISTS (SELECT 1 FROM {0} WHERE item_id = ?1)",
+                formats
+            ),
+            params![item_id, MIME_HTML, html],
+        )?;
+    }
+    Ok(())
+}
+
+fn load_formats(table: &str, item_id: &dyn ToSql) -> Result<Vec<ClipboardFormat>, String> {
+    with_connection(|conn| {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT mime_type, data FROM {} WHERE item_id = ? ORDER BY rowid", table
+        ))?;
+        let formats = stmt.query_map(params![item_id], |row| {
+            Ok(ClipboardFormat {
+                mime_type: row.get(0)?,
+                data: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        Ok(formats)
+    })
+}
+
+// 获取剪贴板项的全部格式
+pub fn get_clipboard_formats(item_id: i64) -> Result<Vec<ClipboardFormat>, String> {
+    load_formats("clipboard_formats", &item_id)
+}
+
+// 获取收藏项的全部格式（仅从剪贴板历史添加的收藏项有）
+pub fn get_favorite_formats(item_id: &str) -> Result<Vec<ClipboardFormat>, String> {
+    load_formats("favorite_formats", &item_id)
+}
+
+// 获取剪贴板项可用的格式类型
+pub fn get_clipboard_format_types(item_id: i64) -> Result<Vec<String>, String> {
+    Ok(get_clipboard_formats(item_id)?.into_iter().map(|f| f.mime_type).collect())
+}
+
+// 获取收藏项可用的格式类型
+pub fn get_favorite_format_types(item_id: &str) -> Result<Vec<String>, String> {
+    Ok(get_favorite_formats(item_id)?.into_iter().map(|f| f.mime_type).collect())
+}
+
+type LegacyRow = (i64, String, String, Option<String>, Option<String>);
+
+// 将旧的单格式条目迁移到格式表（建表时和合并导入旧数据后调用）
+pub fn migrate_clipboard_formats(conn: &rusqlite::Connection) -> Result<(), rusqlite::Error> {
+    let rows: Vec<LegacyRow> = {
+        let mut stmt = conn.prepare(
+            "SELECT id, content, content_type, html_content, rtf_content FROM clipboard
+             WHERE id NOT IN (SELECT DISTINCT item_id FROM clipboard_formats)"
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    for (id, content, content_type, html, rtf) in rows {
+        let formats = build_formats(&content, &content_type, html.as_deref(), rtf.as_deref());
+        save_clipboard_formats(&tx, id, &formats)?;
+    }
+    tx.commit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::connection::create_tables;
+    use rusqlite::Connection;
+
+    fn format_count(conn: &Connection) -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM clipboard_formats", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn html_edits_keep_column_and_formats_in_sync() {
+        use super::{build_formats, save_clipboard_formats, set_item_html, MIME_HTML};
+        use super::super::tags::TAG_ITEM_CLIPBOARD;
+
+        let conn = Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute("INSERT INTO clipboard (id, content, content_type, created_at, updated_at) VALUES (1, 'hi', 'text', 0, 0)", []).unwrap();
+        save_clipboard_formats(&conn, 1, &build_formats("hi", "text", None, None)).unwrap();
+
+        let html_of = |conn: &Connection| -> (Option<String>, Option<String>) {
+            let column = conn.query_row("SELECT html_content FROM clipboard WHERE id = 1", [], |row| row.get(0)).unwrap();
+            let format = conn.query_row(
+                "SELECT data FROM clipboard_formats WHERE item_id = 1 AND mime_type = ?1", [MIME_HTML], |row| row.get(0)
+            ).ok();
+            (column, format)
+        };
+
+        set_item_html(&conn, TAG_ITEM_CLIPBOARD, &1, Some("<b>hi</b>")).unwrap();
+        assert_eq!(html_of(&conn), (Some("<b>hi</b>".into()), Some("<b>hi</b>".into())));
+        set_item_html(&conn, TAG_ITEM_CLIPBOARD, &1, Some("<i>hi</i>")).unwrap();
+        assert_eq!(html_of(&conn), (Some("<i>hi</i>".into()), Some("<i>hi</i>".into())));
+        set_item_html(&conn, TAG_ITEM_CLIPBOARD, &1, None).unwrap();
+        assert_eq!(html_of(&conn), (None, None));
+    }
+
+    #[test]
+    fn migrates_only_when_formats_table_is_created() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute_batch(
+            "DROP TABLE clipboard_formats;
+             INSERT INTO clipboard (id, content, content_type, created_at, updated_at) VALUES (1, 'old', 'text', 0, 0);"
+        ).unwrap();
+
+        // 旧库（没有格式表）升级时迁移
+        create_tables(&conn).unwrap();
+        assert_eq!(format_count(&conn), 1);
+
+        // 之后的启动不再扫描
+        conn.execute("INSERT INTO clipboard (id, content, content_type, created_at, updated_at) VALUES (2, 'new', 'text', 0, 0)", []).unwrap();
+        create_tables(&conn).unwrap();
+        assert_eq!(format_count(&conn), 1);
+    }
+}
@@ -1,160 +1,534 @@
 use super::models::GroupInfo;
-use super::connection::with_connection;
-use rusqlite::params;
+use super::connection::{with_checked_connection, with_connection, DbError};
+use super::trash::record_group_deletion;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
 use chrono;
 
-// 获取所有分组
+// 默认分组的显示名称，对应 group_id 为空的收藏项（数据库中不保存该名称）
+pub const DEFAULT_GROUP_NAME: &str = "全部";
+
+// 分组及其全部子孙分组的 ID（参数为分组 ID，UNION 去重可避免环路死循环）
+pub(super) const GROUP_SUBTREE_SQL: &str =
+    "WITH RECURSIVE subtree(id) AS (
+        SELECT ? UNION SELECT g.id FROM groups g JOIN subtree s ON g.parent_id = s.id
+     ) SELECT id FROM subtree";
+
+// 获取所有分组（按父子关系组成树，顶层在前）
+// 智能分组的 ID 来自独立的 smart_groups 表，与普通分组可能重复，由 get_all_smart_groups 单独返回
 pub fn get_all_groups() -> Result<Vec<GroupInfo>, String> {
     with_connection(|conn| {
-        let mut groups = Vec::new();
-        
-        let mut stmt = conn.prepare("SELECT name, icon, color, order_index FROM groups ORDER BY order_index, name")?;
-        let group_rows: Vec<(String, String, String, i32)> = stmt
+        let counts: HashMap<i64, i32> = conn
+            .prepare("SELECT group_id, COUNT(*) FROM favorites WHERE group_id IS NOT NULL AND deleted_at IS NULL GROUP BY group_id")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<_, _>>()?;
+
+        let mut stmt = conn.prepare("SELECT id, name, icon, color, order_index, parent_id FROM groups ORDER BY order_index, name")?;
+        let groups: Vec<GroupInfo> = stmt
             .query_map([], |row| {
-                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+                let id: i64 = row.get(0)?;
+                Ok(GroupInfo {
+                    id,
+                    name: row.get(1)?,
+                    icon: row.get(2)?,
+                    color: row.get(3)?,
+                    order: row.get(4)?,
+                    item_count: counts.get(&id).copied().unwrap_or(0),
+                    parent_id: row.get(5)?,
+                    total_count: 0,
+                    children: Vec::new(),
+                    smart_query: None,
+                })
             })?
             .collect::<Result<Vec<_>, _>>()?;
-        
-        drop(stmt);
-        
-        for (name, icon, color, order) in group_rows {
-            let count: i32 = conn.query_row(
-                "SELECT COUNT(*) FROM favorites WHERE group_name = ?1",
-                params![&name],
-                |row| row.get(0)
-            )?;
-            
-            groups.push(GroupInfo {
-                name,
-                icon,
-                color,
-                order,
-                item_count: count,
-            });
-        }
-        
-        Ok(groups)
+
+        Ok(build_group_tree(groups))
     })
 }
 
-// 添加分组
-pub fn add_group(name: String, icon: String, color: String) -> Result<GroupInfo, String> {
-    with_connection(|conn| {
-        let exists: i64 = conn.query_row(
-            "SELECT COUNT(*) FROM groups WHERE name = ?1",
-            params![&name],
-            |row| row.get(0)
-        )?;
-        
-        if exists > 0 {
-            return Err(rusqlite::Error::InvalidParameterName(
-                format!("分组 '{}' 已存在", name)
-            ));
+// 父分组不存在的视为顶层分组
+fn build_group_tree(groups: Vec<GroupInfo>) -> Vec<GroupInfo> {
+    let ids: HashSet<i64> = groups.iter().map(|g| g.id).collect();
+    let mut children: HashMap<i64, Vec<GroupInfo>> = HashMap::new();
+    let mut roots = Vec::new();
+
+    for group in groups {
+        match group.parent_id.filter(|p| ids.contains(p)) {
+            Some(parent) => children.entry(parent).or_default().push(group),
+            None => roots.push(group),
         }
-        
-        let max_order: Option<i32> = conn.query_row(
-            "SELECT MAX(order_index) FROM groups",
-            [],
-            |row| row.get(0)
-        ).ok().flatten();
-        
-        let new_order = max_order.unwrap_or(0) + 1;
-        let now = chrono::Local::now().timestamp();
-        
-        conn.execute(
-            "INSERT INTO groups (name, icon, color, order_index, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![&name, &icon, &color, new_order, now, now],
-        )?;
-        
-        Ok(GroupInfo {
-            name,
-            icon,
-            color,
-            order: new_order,
-            item_count: 0,
-        })
-    })
+    }
+
+    roots.into_iter().map(|g| attach_children(g, &mut children)).collect()
 }
 
-// 更新分组
-pub fn update_group(old_name: String, new_name: String, new_icon: String, new_color: String) -> Result<GroupInfo, String> {
-    with_connection(|conn| {
-        if old_name != new_name {
-            let exists: i64 = conn.query_row(
-                "SELECT COUNT(*) FROM groups WHERE name = ?1",
-                params![&new_name],
-                |row| row.get(0)
-            )?;
-            
-            if exists > 0 {
-                return Err(rusqlite::Error::InvalidParameterName(
-                    format!("分组 '{}' 已存在", new_name)
-                ));
-            }
-        }
-        
-        let now = chrono::Local::now().timestamp();
-        let tx = conn.unchecked_transaction()?;
-        
-        tx.execute(
-            "UPDATE groups SET name = ?1, icon = ?2, color = ?3, updated_at = ?4 WHERE name = ?5",
-            params![&new_name, &new_icon, &new_color, now, &old_name],
-        )?;
-        
-        if old_name != new_name {
-            tx.execute(
-                "UPDATE favorites SET group_name = ?1 WHERE group_name = ?2",
-                params![&new_name, &old_name],
-            )?;
-        }
-        
-        tx.commit()?;
-        
-        let count: i32 = conn.query_row(
-            "SELECT COUNT(*) FROM favorites WHERE group_name = ?1",
-            params![&new_name],
-            |row| row.get(0)
-        )?;
-        
-        let (order, color): (i32, String) = conn.query_row(
-            "SELECT order_index, color FROM groups WHERE name = ?1",
-            params![&new_name],
-            |row| Ok((row.get(0)?, row.get(1)?))
-        )?;
-        
-        Ok(GroupInfo {
-            name: new_name,
-            icon: new_icon,
-            color,
-            order,
+fn attach_children(mut group: GroupInfo, children: &mut HashMap<i64, Vec<GroupInfo>>) -> GroupInfo {
+    group.children = children
+        .remove(&group.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| attach_children(c, children))
+        .collect();
+    group.total_count = group.item_count + group.children.iter().map(|c| c.total_count).sum::<i32>();
+    group
+}
+
+// 按 ID 或名称确定分组：ID 优先；名称为'全部'或未提供时为空（默认分组）
+// 名称可以是唯一的分组名，也可以是 "父分组/子分组" 形式的路径
+pub fn resolve_group(group_id: Option<i64>, group_name: Option<&str>) -> Result<Option<i64>, String> {
+    if group_id.is_some() {
+        return Ok(group_id);
+    }
+    match group_name.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(name) if name == DEFAULT_GROUP_NAME => Ok(None),
+        Some(name) => with_checked_connection(|conn| find_group_by_name(conn, name)).map(Some),
+    }
+}
+
+fn find_group_by_name(conn: &Connection, name: &str) -> Result<i64, DbError> {
+    let ids: Vec<i64> = conn
+        .prepare("SELECT id FROM groups WHERE name = ?1")?
+        .query_map(params![name], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    match ids.as_slice() {
+        [id] => Ok(*id),
+        [] if name.contains('/') => find_group_by_path(conn, name),
+        [] => Err(DbError::Invalid(format!("分组 '{}' 不存在", name))),
+        _ => Err(DbError::Invalid(
+            format!("存在多个名为 '{}' 的分组，请使用分组路径或ID", name)
+        )),
+    }
+}
+
+fn find_group_by_path(conn: &Connection, path: &str) -> Result<i64, DbError> {
+    let mut parent: Option<i64> = None;
+    for segment in path.split('/').map(str::trim).filter(|s| !s.is_empty()) {
+        parent = Some(find_child_group(conn, parent, segment)?.ok_or_else(|| {
+            DbError::Invalid(format!("分组 '{}' 不存在", path))
+        })?);
+    }
+    parent.ok_or_else(|| DbError::Invalid(format!("分组 '{}' 不存在", path)))
+}
+
+pub(super) fn find_child_group(conn: &Connection, parent_id: Option<i64>, name: &str) -> Result<Option<i64>, rusqlite::Error> {
+    conn.query_row(
+        "SELECT id FROM groups WHERE parent_id IS ?1 AND name = ?2",
+        params![parent_id, name],
+        |row| row.get(0)
+    ).optional()
+}
+
+// 分组的显示名称（默认分组为'全部'）
+pub(super) fn group_display_name(conn: &Connection, group_id: Option<i64>) -> Result<String, rusqlite::Error> {
+    let name = match group_id {
+        Some(id) => conn.query_row("SELECT name FROM groups WHERE id = ?1", params![id], |row| row.get(0)).optional()?,
+        None => None,
+    };
+    Ok(name.unwrap_or_else(|| DEFAULT_GROUP_NAME.to_string()))
+}
+
+// 分组及其全部子孙分组的 ID
+fn group_subtree(conn: &Connection, id: i64) -> Result<Vec<i64>, rusqlite::Error> {
+    conn.prepare(GROUP_SUBTREE_SQL)?
+        .query_map(params![id], |row| row.get(0))?
+        .collect()
+}
+
+fn group_parent(conn: &Connection, id: i64) -> Result<Option<i64>, DbError> {
+    conn.query_row(
+        "SELECT parent_id FROM groups WHERE id = ?1",
+        params![id],
+        |row| row.get(0)
+    ).optional()?.ok_or_else(|| DbError::Invalid(format!("分组 {} 不存在", id)))
+}
+
+// 同一父分组下名称不能重复
+fn ensure_name_available(conn: &Connection, parent_id: Option<i64>, name: &str, except: Option<i64>) -> Result<(), DbError> {
+    match find_child_group(conn, parent_id, name)? {
+        Some(id) if Some(id) != except => Err(DbError::Invalid(
+            format!("分组 '{}' 已存在", name)
+        )),
+        _ => Ok(()),
+    }
+}
+
+// 普通分组和智能分组共用同一顺序
+pub(super) fn next_group_order(conn: &Connection) -> Result<i32, rusqlite::Error> {
+    let max_order: Option<i32> = conn.query_row(
+        "SELECT MAX(order_index) FROM (SELECT order_index FROM groups UNION ALL SELECT order_index FROM smart_groups)",
+        [],
+        |row| row.get(0)
+    )?;
+    Ok(max_order.unwrap_or(0) + 1)
+}
+
+fn load_group(conn: &Connection, id: i64) -> Result<GroupInfo, rusqlite::Error> {
+    let count: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM favorites WHERE group_id = ?1 AND deleted_at IS NULL",
+        params![id],
+        |row| row.get(0)
+    )?;
+
+    conn.query_row(
+        "SELECT name, icon, color, order_index, parent_id FROM groups WHERE id = ?1",
+        params![id],
+        |row| Ok(GroupInfo {
+            id,
+            name: row.get(0)?,
+            icon: row.get(1)?,
+            color: row.get(2)?,
+            order: row.get(3)?,
             item_count: count,
+            parent_id: row.get(4)?,
+            total_count: count,
+            children: Vec::new(),
+            smart_query: None,
         })
-    })
+    )
 }
 
-// 删除分组
-pub fn delete_group(name: String) -> Result<(), String> {
-    with_connection(|conn| {
-        if name == "全部" {
-            return Err(rusqlite::Error::InvalidParameterName(
-                "不能删除'全部'分组".to_string()
+// 添加分组（parent_id 为空时添加到顶层）
+pub fn add_group(name: String, icon: String, color: String, parent_id: Option<i64>) -> Result<GroupInfo, String> {
+    with_checked_connection(|conn| insert_group(conn, &name, &icon, &color, parent_id))
+}
+
+fn insert_group(conn: &Connection, name: &str, icon: &str, color: &str, parent_id: Option<i64>) -> Result<GroupInfo, DbError> {
+    if let Some(parent) = parent_id {
+        group_parent(conn, parent)?;
+    }
+    ensure_name_available(conn, parent_id, name, None)?;
+
+    let now = chrono::Local::now().timestamp();
+    conn.execute(
+        "INSERT INTO groups (name, icon, color, order_index, parent_id, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![name, icon, color, next_group_order(conn)?, parent_id, now, now],
+    )?;
+
+    Ok(load_group(conn, conn.last_insert_rowid())?)
+}
+
+// 更新分组名称、图标和颜色（收藏项按 ID 引用，重命名无需改动收藏项）
+pub fn update_group(id: i64, new_name: String, new_icon: String, new_color: String) -> Result<GroupInfo, String> {
+    with_checked_connection(|conn| update_group_row(conn, id, &new_name, &new_icon, &new_color))
+}
+
+fn update_group_row(conn: &Connection, id: i64, name: &str, icon: &str, color: &str) -> Result<GroupInfo, DbError> {
+    let parent_id = group_parent(conn, id)?;
+    ensure_name_available(conn, parent_id, name, Some(id))?;
+
+    let now = chrono::Local::now().timestamp();
+    conn.execute(
+        "UPDATE groups SET name = ?1, icon = ?2, color = ?3, updated_at = ?4 WHERE id = ?5",
+        params![name, icon, color, now, id],
+    )?;
+
+    Ok(load_group(conn, id)?)
+}
+
+// 移动分组到新的父分组下（为空时移到顶层），排在同级最后
+pub fn move_group(id: i64, new_parent_id: Option<i64>) -> Result<(), String> {
+    with_checked_connection(|conn| reparent_group(conn, id, new_parent_id))
+}
+
+fn reparent_group(conn: &Connection, id: i64, new_parent_id: Option<i64>) -> Result<(), DbError> {
+    group_parent(conn, id)?;
+
+    if let Some(parent) = new_parent_id {
+        group_parent(conn, parent)?;
+        if group_subtree(conn, id)?.contains(&parent) {
+            return Err(DbError::Invalid(
+                "不能将分组移动到自身或其子分组下".to_string()
             ));
         }
-        
-        let tx = conn.unchecked_transaction()?;
-        
-        tx.execute(
-            "UPDATE favorites SET group_name = '全部' WHERE group_name = ?1",
-            params![&name],
-        )?;
-        
+    }
+
+    let name: String = conn.query_row("SELECT name FROM groups WHERE id = ?1", params![id], |row| row.get(0))?;
+    ensure_name_available(conn, new_parent_id, &name, Some(id))?;
+
+    let now = chrono::Local::now().timestamp();
+    conn.execute(
+        "UPDATE groups SET parent_id = ?1, order_index = ?2, updated_at = ?3 WHERE id = ?4",
+        params![new_parent_id, next_group_order(conn)?, now, id],
+    )?;
+    Ok(())
+}
+
+// 删除分组及其全部子分组，其中的收藏项移到被删分组的父分组（顶层则移到默认分组），可撤销
+pub fn delete_group(id: i64) -> Result<(), String> {
+    with_checked_connection(|conn| delete_group_tree(conn, id))
+}
+
+fn delete_group_tree(conn: &Connection, id: i64) -> Result<(), DbError> {
+    let parent_id = group_parent(conn, id)?;
+    let subtree = group_subtree(conn, id)?;
+
+    let tx = conn.unchecked_transaction()?;
+    record_group_deletion(&tx, &subtree, parent_id)?;
+
+    for group in &subtree {
         tx.execute(
-            "DELETE FROM groups WHERE name = ?1",
-            params![&name],
+            "UPDATE favorites SET group_id = ?1 WHERE group_id = ?2",
+            params![parent_id, group],
         )?;
-        
+    }
+
+    // 子分组会由外键级联删除，这里从最深层开始逐个删除，未开启外键约束时同样有效
+    for group in subtree.iter().rev() {
+        tx.execute("DELETE FROM groups WHERE id = ?1", params![group])?;
+    }
+
+    Ok(tx.commit()?)
+}
+
+// 合并分组：源分组的收藏项和子分组并入目标分组，同名子分组递归合并，最后删除源分组
+pub fn merge_groups(source_id: i64, target_id: i64) -> Result<GroupInfo, String> {
+    with_checked_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        merge_group_into(&tx, source_id, target_id)?;
         tx.commit()?;
-        Ok(())
+        Ok(load_group(conn, target_id)?)
     })
 }
 
+fn merge_group_into(conn: &Connection, source_id: i64, target_id: i64) -> Result<(), DbError> {
+    group_parent(conn, source_id)?;
+    group_parent(conn, target_id)?;
+    if group_subtree(conn, source_id)?.contains(&target_id) {
+        return Err(DbError::Invalid(
+            "不能将分组合并到自身或其子分组".to_string()
+        ));
+    }
+
+    // 源分组的收藏项排在目标分组原有项之前
+    let max_order: i64 = conn.query_row(
+        "SELECT COALESCE(MAX(item_order), 0) FROM favorites WHERE group_id = ?1 AND deleted_at IS NULL",
+        params![target_id],
+        |row| row.get(0)
+    )?;
+    conn.execute(
+        "UPDATE favorites SET group_id = ?1, item_order = item_order + ?2 WHERE group_id = ?3",
+        params![target_id, max_order, source_id],
+    )?;
+
+    let children: Vec<(i64, String)> = conn
+        .prepare("SELECT id, name FROM groups WHERE parent_id = ?1 ORDER BY order_index")?
+        .query_map(params![source_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+
+    for (child_id, name) in children {
+        match find_child_group(conn, Some(target_id), &name)? {
+            Some(existing) => merge_group_into(conn, child_id, existing)?,
+            None => {
+                conn.execute(
+                    "UPDATE groups SET parent_id = ?1 WHERE id = ?2",
+                    params![target_id, child_id],
+                )?;
+            }
+        }
+    }
+
+    conn.execute("DELETE FROM groups WHERE id = ?1", params![source_id])?;
+    Ok(())
+}
+
+// 合并导入库（已 ATTACH 为 schema）中的分组：按父分组和名称匹配本地分组，没有则新建
+// 返回导入库中的分组键（新版为 ID，旧版为名称）到本地分组 ID 的映射
+pub(crate) fn merge_attached_groups(conn: &Connection, schema: &str) -> Result<HashMap<String, i64>, rusqlite::Error> {
+    // 依次尝试新版（ID）、带父分组名的旧版和平铺的旧版表结构
+    let key_columns = [
+        "CAST(id AS TEXT), name, CAST(parent_id AS TEXT)",
+        "name, name, parent_name",
+        "name, name, NULL",
+    ];
+    let rows = key_columns
+        .iter()
+        .find_map(|cols| {
+            let sql = format!(
+                "SELECT {}, icon, color, created_at, updated_at FROM {}.groups ORDER BY order_index",
+                cols, schema
+            );
+            let mut stmt = conn.prepare(&sql).ok()?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok(ImportedGroup {
+                        key: row.get(0)?,
+                        name: row.get(1)?,
+                        parent_key: row.get(2)?,
+                        icon: row.get(3)?,
+                        color: row.get(4)?,
+                        created_at: row.get(5)?,
+                        updated_at: row.get(6)?,
+                    })
+                })
+                .and_then(|rows| rows.collect::<Result<Vec<_>, _>>());
+            Some(rows)
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let keys: HashSet<String> = rows.iter().map(|g| g.key.clone()).collect();
+    let mut mapping: HashMap<String, i64> = HashMap::new();
+    let mut pending = rows;
+
+    // 先处理父分组已确定的分组；剩余无法确定的（存在环路）作为顶层导入
+    while !pending.is_empty() {
+        let (ready, waiting): (Vec<_>, Vec<_>) = pending.into_iter().partition(|g| {
+            g.parent_key.as_ref().is_none_or(|p| !keys.contains(p) || mapping.contains_key(p))
+        });
+        let stalled = ready.is_empty();
+        let (batch, rest) = if stalled { (waiting, Vec::new()) } else { (ready, waiting) };
+        pending = rest;
+
+        for group in batch {
+            let parent_id = if stalled {
+                None
+            } else {
+                group.parent_key.as_ref().and_then(|p| mapping.get(p).copied())
+            };
+            let id = match find_child_group(conn, parent_id, &group.name)? {
+                Some(id) => id,
+                None => {
+                    conn.execute(
+                        "INSERT INTO groups (name, icon, color, order_index, parent_id, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                        params![&group.name, &group.icon, &group.color, next_group_order(conn)?, parent_id, group.created_at, group.updated_at],
+                    )?;
+                    conn.last_insert_rowid()
+                }
+            };
+            mapping.insert(group.key, id);
+        }
+    }
+
+    Ok(mapping)
+}
+
+struct ImportedGroup {
+    key: String,
+    name: String,
+    parent_key: Option<String>,
+    icon: String,
+    color: String,
+    created_at: i64,
+    updated_at: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::connection::create_tables;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+        conn
+    }
+
+    fn add_favorite(conn: &Connection, id: &str, group_id: Option<i64>) {
+        conn.execute(
+            "INSERT INTO favorites (id, title, content, group_id, item_order, created_at, updated_at) VALUES (?1, '', ?1, ?2, 1, 0, 0)",
+            params![id, group_id],
+        ).unwrap();
+    }
+
+    fn favorite_group(conn: &Connection, id: &str) -> Option<i64> {
+        conn.query_row("SELECT group_id FROM favorites WHERE id = ?1", params![id], |row| row.get(0)).unwrap()
+    }
+
+    fn group_count(conn: &Connection) -> i64 {
+        conn.query_row("SELECT COUNT(*) FROM groups", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn rename_keeps_favorites_and_allows_same_name_elsewhere() {
+        let conn = setup();
+        let work = insert_group(&conn, "Work", "", "", None).unwrap().id;
+        let templates = insert_group(&conn, "Templates", "", "", Some(work)).unwrap().id;
+        add_favorite(&conn, "a", Some(templates));
+
+        let renamed = update_group_row(&conn, templates, "Snippets", "ti ti-code", "#000").unwrap();
+        assert_eq!((renamed.name.as_str(), renamed.item_count, renamed.parent_id), ("Snippets", 1, Some(work)));
+        assert_eq!(favorite_group(&conn, "a"), Some(templates));
+
+        // 不同父分组下可以同名，同一父分组下不行
+        assert!(insert_group(&conn, "Snippets", "", "", None).is_ok());
+        assert!(insert_group(&conn, "Snippets", "", "", Some(work)).is_err());
+        assert_eq!(find_group_by_path(&conn, "Work/Snippets").unwrap(), templates);
+        // 校验错误原样返回，不带数据库错误前缀
+        let err = find_group_by_name(&conn, "Snippets").unwrap_err().to_string();
+        assert_eq!(err, "存在多个名为 'Snippets' 的分组，请使用分组路径或ID");
+    }
+
+    #[test]
+    fn delete_moves_favorites_to_parent_and_removes_subtree() {
+        let conn = setup();
+        let work = insert_group(&conn, "Work", "", "", None).unwrap().id;
+        let customers = insert_group(&conn, "Customers", "", "", Some(work)).unwrap().id;
+        let templates = insert_group(&conn, "Templates", "", "", Some(customers)).unwrap().id;
+        add_favorite(&conn, "a", Some(templates));
+        add_favorite(&conn, "b", Some(customers));
+
+        assert!(reparent_group(&conn, work, Some(templates)).is_err());
+
+        delete_group_tree(&conn, customers).unwrap();
+        assert_eq!(group_count(&conn), 1);
+        assert_eq!(favorite_group(&conn, "a"), Some(work));
+        assert_eq!(favorite_group(&conn, "b"), Some(work));
+
+        delete_group_tree(&conn, work).unwrap();
+        assert_eq!(group_count(&conn), 0);
+        assert_eq!(favorite_group(&conn, "a"), None);
+    }
+
+    #[test]
+    fn merge_moves_favorites_and_merges_same_name_children() {
+        let conn = setup();
+        let old = insert_group(&conn, "Old", "", "", None).unwrap().id;
+        let old_sql = insert_group(&conn, "SQL", "", "", Some(old)).unwrap().id;
+        let old_bash = insert_group(&conn, "Bash", "", "", Some(old)).unwrap().id;
+        let new = insert_group(&conn, "New", "", "", None).unwrap().id;
+        let new_sql = insert_group(&conn, "SQL", "", "", Some(new)).unwrap().id;
+        add_favorite(&conn, "a", Some(old));
+        add_favorite(&conn, "b", Some(old_sql));
+        add_favorite(&conn, "c", Some(new_sql));
+
+        assert!(merge_group_into(&conn, new, new_sql).is_err());
+        merge_group_into(&conn, old, new).unwrap();
+
+        assert_eq!(favorite_group(&conn, "a"), Some(new));
+        assert_eq!(favorite_group(&conn, "b"), Some(new_sql));
+        assert_eq!(group_parent(&conn, old_bash).unwrap(), Some(new));
+        assert!(group_parent(&conn, old).is_err() && group_parent(&conn, old_sql).is_err());
+        assert_eq!(group_count(&conn), 3);
+    }
+
+    #[test]
+    fn migrates_legacy_name_keyed_groups() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE groups (name TEXT PRIMARY KEY, icon TEXT NOT NULL DEFAULT 'ti ti-folder',
+                order_index INTEGER NOT NULL DEFAULT 0, created_at INTEGER NOT NULL, updated_at INTEGER NOT NULL);
+             CREATE TABLE favorites (id TEXT PRIMARY KEY, title TEXT NOT NULL, content TEXT NOT NULL, html_content TEXT,
+                content_type TEXT NOT NULL DEFAULT 'text', image_id TEXT, group_name TEXT NOT NULL DEFAULT '全部',
+                item_order INTEGER NOT NULL DEFAULT 0, created_at INTEGER NOT NULL, updated_at INTEGER NOT NULL);
+             CREATE INDEX idx_favorites_group ON favorites(group_name, item_order);
+             INSERT INTO groups (name, order_index, created_at, updated_at) VALUES ('Work', 1, 0, 0), ('Home', 2, 0, 0);
+             INSERT INTO favorites (id, title, content, group_name, created_at, updated_at) VALUES
+                ('a', '', 'a', 'Work', 0, 0), ('b', '', 'b', '全部', 0, 0), ('c', '', 'c', 'Lost', 0, 0);",
+        ).unwrap();
+
+        create_tables(&conn).unwrap();
+
+        let work = find_group_by_name(&conn, "Work").unwrap();
+        assert_eq!(favorite_group(&conn, "a"), Some(work));
+        assert_eq!(favorite_group(&conn, "b"), None);
+        assert_eq!(favorite_group(&conn, "c"), Some(find_group_by_name(&conn, "Lost").unwrap()));
+        assert_eq!(group_count(&conn), 3);
+
+        // 再次初始化不会重复迁移
+        create_tables(&conn).unwrap();
+        assert_eq!(group_count(&conn), 3);
+    }
+}
@@ -0,0 +1,155 @@
+use super::models::LinkMetadata;
+use super::connection::with_connection;
+use crate::services::link_preview::extract_first_url;
+use rusqlite::{params, OptionalExtension};
+use std::collections::HashSet;
+
+// 获取链接预览元数据
+pub fn get_link_metadata(url: &str) -> Result<Option<LinkMetadata>, String> {
+    with_connection(|conn| {
+        conn.query_row(
+            "SELECT url, title, description, favicon_hash, status, fetched_at FROM link_metadata WHERE url = ?",
+            params![url],
+            |row| {
+                Ok(LinkMetadata {
+                    url: row.get(0)?,
+                    title: row.get(1)?,
+                    description: row.get(2)?,
+                    favicon_hash: row.get(3)?,
+                    status: row.get(4)?,
+                    fetched_at: row.get(5)?,
+                })
+            }
+        )
+        .optional()
+    })
+}
+
+// 保存链接预览元数据（同一 URL 覆盖）
+pub fn save_link_metadata(metadata: &LinkMetadata) -> Result<(), String> {
+    with_connection(|conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO link_metadata (url, title, description, favicon_hash, status, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                metadata.url,
+                metadata.title,
+                metadata.description,
+                metadata.favicon_hash,
+                metadata.status,
+                metadata.fetched_at,
+            ],
+        )?;
+        Ok(())
+    })
+}
+
+// 收集满足条件的条目中的链接（在删除条目前调用）
+pub(super) fn collect_link_urls(
+    conn: &rusqlite::Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<HashSet<String>, rusqlite::Error> {
+    let mut stmt = conn.prepare(sql)?;
+    let contents = stmt.query_map(params, |row| row.get::<_, String>(0))?;
+    Ok(contents
+        .flatten()
+        .filter_map(|content| extract_first_url(&content))
+        .collect())
+}
+
+// 删除不再被任何条目引用的链接预览元数据，返回不再被使用的网站图标哈希
+pub(super) fn prune_link_metadata(
+    conn: &rusqlite::Connection,
+    urls: HashSet<String>,
+) -> Result<Vec<String>, rusqlite::Error> {
+    let mut favicon_hashes = HashSet::new();
+    for url in urls {
+        // www. 开头的链接在提取时补了协议，按去掉协议后的地址匹配
+        let needle = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .unwrap_or(&url);
+        let referenced: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM clipboard WHERE instr(content, ?1) > 0)
+                 OR EXISTS(SELECT 1 FROM favorites WHERE instr(content, ?1) > 0)",
+            params![needle],
+            |row| row.get(0),
+        )?;
+        if referenced {
+            continue;
+        }
+
+        let favicon: Option<Option<String>> = conn.query_row(
+            "SELECT favicon_hash FROM link_metadata WHERE url = ?1",
+            params![url],
+            |row| row.get(0),
+        ).optional()?;
+        conn.execute("DELETE FROM link_metadata WHERE url = ?1", params![url])?;
+        if let Some(Some(hash)) = favicon {
+            favicon_hashes.insert(hash);
+        }
+    }
+
+    // 同一图标可能被多个链接共用
+    let mut unused = Vec::new();
+    for hash in favicon_hashes {
+        let in_use: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM link_metadata WHERE favicon_hash = ?1)",
+            params![hash],
+            |row| row.get(0),
+        )?;
+        if !in_use {
+            unused.push(hash);
+        }
+    }
+    Ok(unused)
+}
+
+// 删除网站图标文件
+pub(super) fn delete_favicon_files(favicon_hashes: Vec<String>) -> Result<(), String> {
+    if favicon_hashes.is_empty() { return Ok(()); }
+    let favicons_dir = crate::services::get_data_directory()?.join("favicons");
+    for hash in favicon_hashes {
+        let p = favicons_dir.join(format!("{}.png", hash));
+        if p.exists() {
+            let _ = std::fs::remove_file(&p);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::connection::create_tables;
+
+    fn insert_metadata(conn: &rusqlite::Connection, url: &str, favicon: &str) {
+        conn.execute(
+            "INSERT INTO link_metadata (url, favicon_hash, fetched_at) VALUES (?1, ?2, 0)",
+            params![url, favicon],
+        ).unwrap();
+    }
+
+    #[test]
+    fn prunes_only_unreferenced_links_and_favicons() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO clipboard (content, created_at, updated_at) VALUES ('see www.kept.com/a', 0, 0)",
+            [],
+        ).unwrap();
+        insert_metadata(&conn, "https://www.kept.com/a", "aaaa");
+        insert_metadata(&conn, "https://gone.com/x", "aaaa");
+        insert_metadata(&conn, "https://gone.com/y", "bbbb");
+
+        let urls: HashSet<String> = ["https://www.kept.com/a", "https://gone.com/x", "https://gone.com/y"]
+            .iter().map(|s| s.to_string()).collect();
+        assert_eq!(prune_link_metadata(&conn, urls).unwrap(), vec!["bbbb"]);
+
+        let remaining: Vec<String> = conn.prepare("SELECT url FROM link_metadata").unwrap()
+            .query_map([], |row| row.get(0)).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(remaining, vec!["https://www.kept.com/a"]);
+    }
+}
@@ -3,10 +3,23 @@ pub mod connection;
 pub mod clipboard;
 pub mod favorites;
 pub mod groups;
+pub mod smart_groups;
+pub mod tags;
+pub mod revisions;
+pub mod trash;
+pub mod link_metadata;
+pub mod formats;
+mod snapshots;
 
 pub use models::*;
 pub use connection::init_database;
 pub use clipboard::*;
 pub use favorites::*;
 pub use groups::*;
+pub use smart_groups::*;
+pub use tags::*;
+pub use revisions::*;
+pub use trash::*;
+pub use link_metadata::*;
+pub use formats::{get_clipboard_formats, get_clipboard_format_types, get_favorite_formats, get_favorite_format_types};
 
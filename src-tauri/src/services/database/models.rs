@@ -7,6 +7,8 @@ pub struct ClipboardItem {
     pub content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub html_content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtf_content: Option<String>,
     pub content_type: String,  
     #[serde(skip_serializing_if = "Option::is_none")]
     pub image_id: Option<String>,
@@ -19,6 +21,18 @@ pub struct ClipboardItem {
     pub source_icon_hash: Option<String>, 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub char_count: Option<i64>,
+    // 用户设置的标题和备注
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    // 复制次数（重复复制时累加）和最近一次复制的时间
+    #[serde(default)]
+    pub copy_count: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_copied_at: Option<i64>,
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub created_at: i64,  
     pub updated_at: i64, 
 }
@@ -31,26 +45,202 @@ pub struct FavoriteItem {
     pub content: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub html_content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rtf_content: Option<String>,
     pub content_type: String,  
     #[serde(skip_serializing_if = "Option::is_none")]
     pub image_id: Option<String>,
+    // 所属分组 ID（默认分组为空）
+    #[serde(default)]
+    pub group_id: Option<i64>,
+    // 所属分组的显示名称
     pub group_name: String,
     pub item_order: i64,
     pub paste_count: i64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub char_count: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub created_at: i64,  
     pub updated_at: i64, 
 }
 
+impl FavoriteItem {
+    // 转换为剪贴板项格式（供粘贴流程使用）
+    pub fn into_clipboard_item(self) -> ClipboardItem {
+        ClipboardItem {
+            id: 0,
+            content: self.content,
+            html_content: self.html_content,
+            rtf_content: self.rtf_content,
+            content_type: self.content_type,
+            image_id: self.image_id,
+            item_order: self.item_order,
+            is_pinned: false,
+            paste_count: 0,
+            source_app: None,
+            source_icon_hash: None,
+            char_count: self.char_count,
+            title: Some(self.title).filter(|t| !t.is_empty()),
+            note: self.note,
+            copy_count: 0,
+            last_copied_at: None,
+            tags: self.tags,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+}
+
 // 分组信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GroupInfo {
+    pub id: i64,
     pub name: String,
     pub icon: String,
     pub color: String,
     pub order: i32,
     pub item_count: i32,
+    // 父分组 ID（顶层分组为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i64>,
+    // 包含全部子孙分组的项目数
+    #[serde(default)]
+    pub total_count: i32,
+    #[serde(default)]
+    pub children: Vec<GroupInfo>,
+    // 智能分组的查询条件（普通分组为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart_query: Option<SmartGroupQuery>,
+}
+
+// 回收站中的条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashItem {
+    // clipboard / favorite
+    pub item_type: String,
+    pub item_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub content: String,
+    pub content_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_id: Option<String>,
+    pub deleted_at: i64,
+}
+
+// 一次删除操作（撤销删除的单位）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashBatch {
+    pub id: i64,
+    // clipboard / clear_history / favorite / group
+    pub action: String,
+    // 恢复的条目数（删除分组时为恢复的分组数）
+    pub item_count: i64,
+    pub deleted_at: i64,
+}
+
+// 条目的历史版本（修改前的内容）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemRevision {
+    pub id: i64,
+    pub item_type: String,
+    pub item_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub html_content: Option<String>,
+    // 该版本最后编辑的时间
+    pub edited_at: i64,
+    // 被新内容替换（保存为历史版本）的时间
+    pub created_at: i64,
+}
+
+// 两个版本的对比结果（to_revision_id 为空表示当前内容）
+#[derive(Debug, Clone, Serialize)]
+pub struct RevisionDiff {
+    pub from_revision_id: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_revision_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_title: Option<String>,
+    pub lines: Vec<crate::utils::diff::DiffLine>,
+}
+
+// 智能分组的查询范围
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmartGroupScope {
+    // 剪贴板历史
+    #[default]
+    History,
+    // 收藏
+    Favorites,
+}
+
+// 智能分组（保存的搜索）：查询时按条件实时筛选
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SmartGroupQuery {
+    pub scope: SmartGroupScope,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    // 来源应用（仅剪贴板历史）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_app: Option<String>,
+    // 最近 N 天（按查询时的时间计算，可与绝对范围同时使用）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub within_days: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<i64>,
+    // 收藏分组（仅收藏）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<i64>,
+    pub include_descendants: bool,
+    // 只包含置顶项（仅剪贴板历史）
+    pub pinned_only: bool,
+    pub tags: Vec<String>,
+}
+
+// 标签及其使用次数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagInfo {
+    pub id: i64,
+    pub name: String,
+    pub clipboard_count: i64,
+    pub favorite_count: i64,
+}
+
+// 链接预览元数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkMetadata {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favicon_hash: Option<String>,
+    // ok / failed
+    pub status: String,
+    pub fetched_at: i64,
+}
+
+// 剪贴板项的一种数据格式（同一次复制可包含多种格式）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipboardFormat {
+    pub mime_type: String,
+    // 文本类格式为原始内容，图片和文件为存储路径（多个路径按行分隔）
+    pub data: String,
 }
 
 // 分页查询结果
@@ -95,6 +285,20 @@ pub struct QueryParams {
     // 内容类型过滤（可选）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_type: Option<String>,
+    // 来源应用过滤（模糊匹配，可选）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_app: Option<String>,
+    // 创建时间范围（秒级时间戳，含起点不含终点）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<i64>,
+    // 只查询置顶项
+    #[serde(default)]
+    pub pinned_only: bool,
+    // 标签过滤（需包含全部标签）
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl Default for QueryParams {
@@ -104,6 +308,11 @@ impl Default for QueryParams {
             limit: 50,
             search: None,
             content_type: None,
+            source_app: None,
+            created_after: None,
+            created_before: None,
+            pinned_only: false,
+            tags: Vec::new(),
         }
     }
 }
@@ -115,15 +324,26 @@ pub struct FavoritesQueryParams {
     pub offset: i64,
     // 每页数量
     pub limit: i64,
-    // 分组名称（可选）
+    // 分组 ID（为空时查询全部分组）
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub group_name: Option<String>,
+    pub group_id: Option<i64>,
     // 搜索关键词（可选）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search: Option<String>,
     // 内容类型过滤（可选）
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content_type: Option<String>,
+    // 是否包含子孙分组的收藏项
+    #[serde(default)]
+    pub include_descendants: bool,
+    // 创建时间范围（秒级时间戳，含起点不含终点）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_after: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_before: Option<i64>,
+    // 标签过滤（需包含全部标签）
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl Default for FavoritesQueryParams {
@@ -131,9 +351,13 @@ impl Default for FavoritesQueryParams {
         Self {
             offset: 0,
             limit: 50,
-            group_name: None,
+            group_id: None,
             search: None,
             content_type: None,
+            include_descendants: false,
+            created_after: None,
+            created_before: None,
+            tags: Vec::new(),
         }
     }
 }
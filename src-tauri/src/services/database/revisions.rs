@@ -0,0 +1,198 @@
+use super::models::{ItemRevision, RevisionDiff};
+use super::connection::{with_checked_connection, with_connection, DbError};
+use super::tags::{item_kind, TAG_ITEM_CLIPBOARD};
+use super::formats::{set_item_html, set_item_text};
+use crate::utils::diff::diff_lines;
+use rusqlite::{params, Connection, OptionalExtension};
+use chrono;
+
+// 每个条目最多保留的历史版本数
+pub const MAX_REVISIONS_PER_ITEM: i64 = 20;
+
+// 条目当前的 (标题, 内容, HTML, 更新时间)，剪贴板项没有标题
+type ItemSnapshot = (Option<String>, String, Option<String>, i64);
+
+fn current_snapshot(conn: &Connection, item_type: &str, item_id: &str) -> Result<Option<ItemSnapshot>, rusqlite::Error> {
+    let sql = if item_type == TAG_ITEM_CLIPBOARD {
+        "SELECT NULL, content, html_content, updated_at FROM clipboard WHERE id = ?1"
+    } else {
+        "SELECT title, content, html_content, updated_at FROM favorites WHERE id = ?1"
+    };
+    conn.query_row(sql, params![item_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+        .optional()
+}
+
+// 修改条目前调用：把当前内容保存为历史版本（标题和内容都没有变化时不保存），超出上限时删除最旧的版本
+// title 为空表示本次不修改标题
+pub(super) fn save_revision(conn: &Connection, item_type: &str, item_id: &str, title: Option<&str>, content: &str) -> Result<(), rusqlite::Error> {
+    let Some((old_title, old_content, old_html, edited_at)) = current_snapshot(conn, item_type, item_id)? else {
+        return Ok(());
+    };
+    let title_changed = title.is_some_and(|t| Some(t) != old_title.as_deref());
+    if !title_changed && old_content == content {
+        return Ok(());
+    }
+
+    conn.execute(
+        "INSERT INTO item_revisions (item_type, item_id, title, content, html_content, edited_at, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![item_type, item_id, old_title, old_content, old_html, edited_at, chrono::Local::now().timestamp()],
+    )?;
+    conn.execute(
+        "DELETE FROM item_revisions WHERE item_type = ?1 AND item_id = ?2 AND id NOT IN (
+            SELECT id FROM item_revisions WHERE item_type = ?1 AND item_id = ?2 ORDER BY id DESC LIMIT ?3
+         )",
+        params![item_type, item_id, MAX_REVISIONS_PER_ITEM],
+    )?;
+    Ok(())
+}
+
+fn row_to_revision(row: &rusqlite::Row) -> Result<ItemRevision, rusqlite::Error> {
+    Ok(ItemRevision {
+        id: row.get(0)?,
+        item_type: row.get(1)?,
+        item_id: row.get(2)?,
+        title: row.get(3)?,
+        content: row.get(4)?,
+        html_content: row.get(5)?,
+        edited_at: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+fn load_revision(conn: &Connection, id: i64) -> Result<ItemRevision, DbError> {
+    conn.query_row(
+        "SELECT id, item_type, item_id, title, content, html_content, edited_at, created_at FROM item_revisions WHERE id = ?1",
+        params![id],
+        row_to_revision
+    ).optional()?
+        .ok_or_else(|| DbError::Invalid(format!("历史版本 {} 不存在", id)))
+}
+
+// 获取条目的历史版本（最新的在前）
+pub fn get_item_revisions(item_type: &str, item_id: &str) -> Result<Vec<ItemRevision>, String> {
+    let kind = item_kind(item_type)?;
+    with_connection(|conn| {
+        conn.prepare(
+            "SELECT id, item_type, item_id, title, content, html_content, edited_at, created_at
+             FROM item_revisions WHERE item_type = ?1 AND item_id = ?2 ORDER BY id DESC"
+        )?
+        .query_map(params![kind, item_id], row_to_revision)?
+        .collect()
+    })
+}
+
+// 对比两个历史版本，to_id 为空时与条目当前内容对比
+pub fn diff_item_revisions(from_id: i64, to_id: Option<i64>) -> Result<RevisionDiff, String> {
+    with_checked_connection(|conn| {
+        let from = load_revision(conn, from_id)?;
+        let (new_title, new_content) = match to_id {
+            Some(to_id) => {
+                let to = load_revision(conn, to_id)?;
+                if (to.item_type.as_str(), to.item_id.as_str()) != (from.item_type.as_str(), from.item_id.as_str()) {
+                    return Err(DbError::Invalid("只能对比同一条目的历史版本".to_string()));
+                }
+                (to.title, to.content)
+            }
+            None => {
+                let (title, content, _, _) = current_snapshot(conn, &from.item_type, &from.item_id)?
+                    .ok_or_else(|| DbError::Invalid(format!("条目 {} 不存在", from.item_id)))?;
+                (title, content)
+            }
+        };
+
+        Ok(RevisionDiff {
+            from_revision_id: from_id,
+            to_revision_id: to_id,
+            lines: diff_lines(&from.content, &new_content),
+            old_title: from.title,
+            new_title,
+        })
+    })
+}
+
+// 恢复到指定历史版本（恢复前的内容也会保存为历史版本）
+pub fn restore_item_revision(revision_id: i64) -> Result<(), String> {
+    with_checked_connection(|conn| {
+        let revision = load_revision(conn, revision_id)?;
+        let tx = conn.unchecked_transaction()?;
+        restore_revision(&tx, &revision)?;
+        Ok(tx.commit()?)
+    })
+}
+
+fn restore_revision(conn: &Connection, revision: &ItemRevision) -> Result<(), DbError> {
+    let (kind, id) = (revision.item_type.as_str(), revision.item_id.as_str());
+    if current_snapshot(conn, kind, id)?.is_none() {
+        return Err(DbError::Invalid(format!("条目 {} 不存在", id)));
+    }
+    save_revision(conn, kind, id, revision.title.as_deref(), &revision.content)?;
+
+    set_item_text(conn, kind, &id, &revision.content)?;
+    set_item_html(conn, kind, &id, revision.html_content.as_deref())?;
+
+    let now = chrono::Local::now().timestamp();
+    if kind == TAG_ITEM_CLIPBOARD {
+        conn.execute(
+            "UPDATE clipboard SET char_count = NULL, updated_at = ?1 WHERE id = ?2",
+            params![now, id],
+        )?;
+    } else {
+        conn.execute(
+            "UPDATE favorites SET title = COALESCE(?1, title), char_count = NULL, updated_at = ?2 WHERE id = ?3",
+            params![&revision.title, now, id],
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::connection::create_tables;
+    use super::super::tags::TAG_ITEM_FAVORITE;
+
+    fn edit(conn: &Connection, id: &str, title: &str, content: &str) {
+        save_revision(conn, TAG_ITEM_FAVORITE, id, Some(title), content).unwrap();
+        conn.execute(
+            "UPDATE favorites SET title = ?1, content = ?2 WHERE id = ?3",
+            params![title, content, id],
+        ).unwrap();
+    }
+
+    fn revision_ids(conn: &Connection, id: &str) -> Vec<i64> {
+        conn.prepare("SELECT id FROM item_revisions WHERE item_id = ?1 ORDER BY id DESC").unwrap()
+            .query_map(params![id], |row| row.get(0)).unwrap()
+            .collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn revisions_are_capped_restored_and_cleaned_up() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO favorites (id, title, content, item_order, created_at, updated_at) VALUES ('f', 't0', 'v0', 1, 0, 0)",
+            [],
+        ).unwrap();
+
+        // 内容未变化时不保存版本
+        edit(&conn, "f", "t0", "v0");
+        assert!(revision_ids(&conn, "f").is_empty());
+
+        for i in 1..=MAX_REVISIONS_PER_ITEM + 5 {
+            edit(&conn, "f", &format!("t{}", i), &format!("v{}", i));
+        }
+        let ids = revision_ids(&conn, "f");
+        assert_eq!(ids.len() as i64, MAX_REVISIONS_PER_ITEM);
+
+        let oldest = load_revision(&conn, *ids.last().unwrap()).unwrap();
+        assert_eq!(oldest.content, "v5");
+        restore_revision(&conn, &oldest).unwrap();
+        let (title, content, _, _) = current_snapshot(&conn, TAG_ITEM_FAVORITE, "f").unwrap().unwrap();
+        assert_eq!((title.as_deref(), content.as_str()), (Some("t5"), "v5"));
+        assert_eq!(load_revision(&conn, revision_ids(&conn, "f")[0]).unwrap().content, format!("v{}", MAX_REVISIONS_PER_ITEM + 5));
+
+        conn.execute("DELETE FROM favorites WHERE id = 'f'", []).unwrap();
+        assert!(revision_ids(&conn, "f").is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+use super::models::{
+    ClipboardItem, FavoriteItem, FavoritesQueryParams, GroupInfo, PaginatedResult, QueryParams,
+    SmartGroupQuery, SmartGroupScope,
+};
+use super::connection::{with_checked_connection, with_connection, DbError};
+use super::clipboard::{count_clipboard_items, query_clipboard_items};
+use super::favorites::{count_favorites, query_favorites};
+use super::groups::next_group_order;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use chrono;
+
+// 智能分组的查询结果（按查询范围区分）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "scope", content = "result", rename_all = "lowercase")]
+pub enum SmartGroupItems {
+    History(PaginatedResult<ClipboardItem>),
+    Favorites(PaginatedResult<FavoriteItem>),
+}
+
+// 起始时间取"最近 N 天"和绝对起点中较晚的一个
+fn effective_created_after(query: &SmartGroupQuery, now: i64) -> Option<i64> {
+    let relative = query.within_days.filter(|d| *d > 0).map(|d| now - d * 86400);
+    relative.max(query.created_after)
+}
+
+fn history_params(query: &SmartGroupQuery, offset: i64, limit: i64, now: i64) -> QueryParams {
+    QueryParams {
+        offset,
+        limit,
+        search: query.search.clone(),
+        content_type: query.content_type.clone(),
+        source_app: query.source_app.clone(),
+        created_after: effective_created_after(query, now),
+        created_before: query.created_before,
+        pinned_only: query.pinned_only,
+        tags: query.tags.clone(),
+    }
+}
+
+fn favorites_params(query: &SmartGroupQuery, offset: i64, limit: i64, now: i64) -> FavoritesQueryParams {
+    FavoritesQueryParams {
+        offset,
+        limit,
+        group_id: query.group_id,
+        search: query.search.clone(),
+        content_type: query.content_type.clone(),
+        include_descendants: query.include_descendants,
+        created_after: effective_created_after(query, now),
+        created_before: query.created_before,
+        tags: query.tags.clone(),
+    }
+}
+
+fn smart_group_count(conn: &Connection, query: &SmartGroupQuery) -> Result<i64, rusqlite::Error> {
+    let now = chrono::Local::now().timestamp();
+    match query.scope {
+        SmartGroupScope::History => count_clipboard_items(conn, &history_params(query, 0, 0, now)),
+        SmartGroupScope::Favorites => count_favorites(conn, &favorites_params(query, 0, 0, now)),
+    }
+}
+
+fn row_to_smart_group(row: &rusqlite::Row) -> Result<GroupInfo, rusqlite::Error> {
+    let query: String = row.get(5)?;
+    Ok(GroupInfo {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        icon: row.get(2)?,
+        color: row.get(3)?,
+        order: row.get(4)?,
+        item_count: 0,
+        parent_id: None,
+        total_count: 0,
+        children: Vec::new(),
+        smart_query: Some(serde_json::from_str(&query).unwrap_or_default()),
+    })
+}
+
+// 计算智能分组当前匹配的项目数
+fn with_live_count(conn: &Connection, mut group: GroupInfo) -> Result<GroupInfo, rusqlite::Error> {
+    if let Some(ref query) = group.smart_query {
+        let count = smart_group_count(conn, query)? as i32;
+        group.item_count = count;
+        group.total_count = count;
+    }
+    Ok(group)
+}
+
+// 所有智能分组（按 order_index 排序，项目数实时计算）
+pub(super) fn load_smart_groups(conn: &Connection) -> Result<Vec<GroupInfo>, rusqlite::Error> {
+    let groups: Vec<GroupInfo> = conn
+        .prepare("SELECT id, name, icon, color, order_index, query FROM smart_groups ORDER BY order_index, name")?
+        .query_map([], row_to_smart_group)?
+        .collect::<Result<_, _>>()?;
+    groups.into_iter().map(|g| with_live_count(conn, g)).collect()
+}
+
+fn load_smart_group(conn: &Connection, id: i64) -> Result<GroupInfo, DbError> {
+    let group = conn.query_row(
+        "SELECT id, name, icon, color, order_index, query FROM smart_groups WHERE id = ?1",
+        params![id],
+        row_to_smart_group
+    ).optional()?
+        .ok_or_else(|| DbError::Invalid(format!("智能分组 {} 不存在", id)))?;
+    Ok(with_live_count(conn, group)?)
+}
+
+fn validate_smart_group(conn: &Connection, id: Option<i64>, name: &str, query: &SmartGroupQuery) -> Result<(), DbError> {
+    let invalid = |msg: String| Err(DbError::Invalid(msg));
+    if name.trim().is_empty() {
+        return invalid("智能分组名称不能为空".to_string());
+    }
+    let duplicate: Option<i64> = conn.query_row(
+        "SELECT id FROM smart_groups WHERE name = ?1 AND id IS NOT ?2",
+        params![name.trim(), id],
+        |row| row.get(0)
+    ).optional()?;
+    if duplicate.is_some() {
+        return invalid(format!("智能分组 '{}' 已存在", name.trim()));
+    }
+    if query.within_days.is_some_and(|d| d <= 0) {
+        return invalid("最近天数必须大于 0".to_string());
+    }
+
+    match query.scope {
+        SmartGroupScope::History if query.group_id.is_some() => invalid("收藏分组条件只能用于收藏".to_string()),
+        SmartGroupScope::Favorites if query.source_app.is_some() || query.pinned_only => {
+            invalid("来源应用和置顶条件只能用于剪贴板历史".to_string())
+        }
+        SmartGroupScope::Favorites => match query.group_id {
+            Some(group_id) => {
+                let exists: bool = conn.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM groups WHERE id = ?1)",
+                    params![group_id],
+                    |row| row.get(0)
+                )?;
+                if exists { Ok(()) } else { invalid(format!("分组 {} 不存在", group_id)) }
+            }
+            None => Ok(()),
+        },
+        SmartGroupScope::History => Ok(()),
+    }
+}
+
+fn query_json(query: &SmartGroupQuery) -> Result<String, rusqlite::Error> {
+    serde_json::to_string(query)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+// 获取所有智能分组（与普通分组分开返回，ID 来自独立的 smart_groups 表）
+pub fn get_all_smart_groups() -> Result<Vec<GroupInfo>, String> {
+    with_connection(load_smart_groups)
+}
+
+// 添加智能分组（排在所有分组之后）
+pub fn add_smart_group(name: String, icon: String, color: String, query: SmartGroupQuery) -> Result<GroupInfo, String> {
+    with_checked_connection(|conn| insert_smart_group(conn, &name, &icon, &color, &query))
+}
+
+fn insert_smart_group(conn: &Connection, name: &str, icon: &str, color: &str, query: &SmartGroupQuery) -> Result<GroupInfo, DbError> {
+    validate_smart_group(conn, None, name, query)?;
+
+    let now = chrono::Local::now().timestamp();
+    conn.execute(
+        "INSERT INTO smart_groups (name, icon, color, order_index, query, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+        params![name.trim(), icon, color, next_group_order(conn)?, query_json(query)?, now],
+    )?;
+    load_smart_group(conn, conn.last_insert_rowid())
+}
+
+// 更新智能分组的名称、图标、颜色和查询条件
+pub fn update_smart_group(id: i64, name: String, icon: String, color: String, query: SmartGroupQuery) -> Result<GroupInfo, String> {
+    with_checked_connection(|conn| {
+        load_smart_group(conn, id)?;
+        validate_smart_group(conn, Some(id), &name, &query)?;
+        conn.execute(
+            "UPDATE smart_groups SET name = ?1, icon = ?2, color = ?3, query = ?4, updated_at = ?5 WHERE id = ?6",
+            params![name.trim(), icon, color, query_json(&query)?, chrono::Local::now().timestamp(), id],
+        )?;
+        load_smart_group(conn, id)
+    })
+}
+
+// 删除智能分组（不影响其匹配的项目）
+pub fn delete_smart_group(id: i64) -> Result<(), String> {
+    let deleted = with_connection(|conn| conn.execute("DELETE FROM smart_groups WHERE id = ?1", params![id]))?;
+    match deleted {
+        0 => Err(format!("智能分组 {} 不存在", id)),
+        _ => Ok(()),
+    }
+}
+
+// 按智能分组的条件分页查询
+pub fn query_smart_group(id: i64, offset: i64, limit: i64) -> Result<SmartGroupItems, String> {
+    let query = with_checked_connection(|conn| load_smart_group(conn, id))?
+        .smart_query
+        .unwrap_or_default();
+    let now = chrono::Local::now().timestamp();
+
+    match query.scope {
+        SmartGroupScope::History => query_clipboard_items(history_params(&query, offset, limit, now)).map(SmartGroupItems::History),
+        SmartGroupScope::Favorites => query_favorites(favorites_params(&query, offset, limit, now)).map(SmartGroupItems::Favorites),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::connection::create_tables;
+
+    #[test]
+    fn smart_group_counts_are_live() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        let now = chrono::Local::now().timestamp();
+        let old = now - 30 * 86400;
+        conn.execute_batch(&format!(
+            "INSERT INTO clipboard (content, content_type, source_app, created_at, updated_at) VALUES
+                ('https://a.com', 'link', 'Slack', {now}, {now}),
+                ('https://b.com', 'link', 'Slack', {old}, {old}),
+                ('https://c.com', 'link', 'Chrome', {now}, {now});"
+        )).unwrap();
+
+        let query = SmartGroupQuery {
+            content_type: Some("link".to_string()),
+            source_app: Some("slack".to_string()),
+            within_days: Some(7),
+            ..Default::default()
+        };
+        let group = insert_smart_group(&conn, "Slack links", "ti ti-link", "#000", &query).unwrap();
+        assert_eq!(group.item_count, 1);
+
+        conn.execute(
+            "INSERT INTO clipboard (content, content_type, source_app, created_at, updated_at) VALUES ('https://d.com', 'link', 'Slack', ?1, ?1)",
+            params![now],
+        ).unwrap();
+        let groups = load_smart_groups(&conn).unwrap();
+        assert_eq!((groups.len(), groups[0].item_count), (1, 2));
+
+        // 收藏范围不能使用来源应用条件，名称不能重复
+        let favorites = SmartGroupQuery { scope: SmartGroupScope::Favorites, ..query.clone() };
+        assert!(insert_smart_group(&conn, "Slack favorites", "", "", &favorites).is_err());
+        assert!(insert_smart_group(&conn, "Slack links", "", "", &query).is_err());
+    }
+}
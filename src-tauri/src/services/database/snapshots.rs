@@ -0,0 +1,106 @@
+use rusqlite::params;
+use std::collections::HashSet;
+
+const SNAPSHOT_PREFIX: &str = "file_snapshots/";
+
+// 提取内容中引用的文件快照ID
+pub(super) fn snapshot_ids_in(content: &str) -> Vec<String> {
+    content
+        .match_indices(SNAPSHOT_PREFIX)
+        .filter_map(|(idx, _)| {
+            let rest = &content[idx + SNAPSHOT_PREFIX.len()..];
+            let id: String = rest.chars().take_while(|c| c.is_ascii_hexdigit()).collect();
+            (id.len() == 16).then_some(id)
+        })
+        .collect()
+}
+
+// 收集满足条件的条目中引用的文件快照ID（在删除条目前调用）
+pub(super) fn collect_snapshot_ids(
+    conn: &rusqlite::Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<HashSet<String>, rusqlite::Error> {
+    let mut stmt = conn.prepare(sql)?;
+    let contents = stmt.query_map(params, |row| row.get::<_, String>(0))?;
+    let mut set = HashSet::new();
+    for content in contents.flatten() {
+        set.extend(snapshot_ids_in(&content));
+    }
+    Ok(set)
+}
+
+// 过滤出不再被引用的文件快照（只扫描一次文件类条目，所有快照都确认被引用后提前结束）
+pub(super) fn unreferenced_snapshots(
+    conn: &rusqlite::Connection,
+    mut snapshot_ids: HashSet<String>,
+) -> Result<Vec<String>, rusqlite::Error> {
+    if snapshot_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let pattern = format!("%{}%", SNAPSHOT_PREFIX);
+    let mut stmt = conn.prepare(
+        "SELECT content FROM clipboard WHERE content LIKE 'files:%' AND content LIKE ?1
+         UNION ALL
+         SELECT content FROM favorites WHERE content LIKE 'files:%' AND content LIKE ?1",
+    )?;
+    let mut rows = stmt.query(params![pattern])?;
+    while let Some(row) = rows.next()? {
+        let content: String = row.get(0)?;
+        for sid in snapshot_ids_in(&content) {
+            snapshot_ids.remove(&sid);
+        }
+        if snapshot_ids.is_empty() {
+            break;
+        }
+    }
+    Ok(snapshot_ids.into_iter().collect())
+}
+
+// 删除文件快照目录
+pub(super) fn delete_snapshot_dirs(snapshot_ids: Vec<String>) -> Result<(), String> {
+    if snapshot_ids.is_empty() { return Ok(()); }
+    let snapshots_dir = crate::services::get_data_directory()?.join("file_snapshots");
+    for sid in snapshot_ids {
+        let p = snapshots_dir.join(&sid);
+        if p.exists() {
+            let _ = std::fs::remove_dir_all(&p);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::connection::create_tables;
+
+    fn files_content(snapshot: &str) -> String {
+        format!(r#"files:{{"files":[{{"path":"/gone/a.txt","snapshot":"file_snapshots/{}/a.txt"}}],"operation":"copy"}}"#, snapshot)
+    }
+
+    #[test]
+    fn extracts_snapshot_ids() {
+        let content = files_content("0123456789abcdef") + " file_snapshots/short/x file_snapshots/fedcba9876543210/b";
+        assert_eq!(snapshot_ids_in(&content), vec!["0123456789abcdef", "fedcba9876543210"]);
+        assert!(snapshot_ids_in("plain text").is_empty());
+    }
+
+    #[test]
+    fn keeps_snapshots_still_referenced() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO clipboard (content, content_type, created_at, updated_at) VALUES (?1, 'file', 0, 0)",
+            params![files_content("0123456789abcdef")],
+        ).unwrap();
+        conn.execute(
+            "INSERT INTO favorites (id, title, content, content_type, created_at, updated_at) VALUES ('f1', '', ?1, 'file', 0, 0)",
+            params![files_content("1111111111111111")],
+        ).unwrap();
+
+        let candidates: HashSet<String> = ["0123456789abcdef", "1111111111111111", "2222222222222222"]
+            .iter().map(|s| s.to_string()).collect();
+        assert_eq!(unreferenced_snapshots(&conn, candidates).unwrap(), vec!["2222222222222222"]);
+    }
+}
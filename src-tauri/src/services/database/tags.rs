@@ -0,0 +1,273 @@
+use super::models::TagInfo;
+use super::connection::{with_checked_connection, with_connection, DbError};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use chrono;
+
+// 条目类型（item_tags、item_revisions 的 item_type）
+pub const TAG_ITEM_CLIPBOARD: &str = "clipboard";
+pub const TAG_ITEM_FAVORITE: &str = "favorite";
+
+pub(super) fn item_kind(item_type: &str) -> Result<&'static str, String> {
+    match item_type {
+        TAG_ITEM_CLIPBOARD => Ok(TAG_ITEM_CLIPBOARD),
+        TAG_ITEM_FAVORITE | "favorites" => Ok(TAG_ITEM_FAVORITE),
+        _ => Err(format!("不支持的条目类型: {}", item_type)),
+    }
+}
+
+// 去掉首尾空白和前导 #，空标签忽略
+fn normalize_tag(name: &str) -> Option<String> {
+    let name = name.trim().trim_start_matches('#').trim();
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+// 按标签过滤的条件（每个标签一个参数），id_expr 为条目 ID 的文本表达式
+pub(super) fn tag_filter_sql(item_type: &str, id_expr: &str) -> String {
+    format!(
+        "{} IN (SELECT it.item_id FROM item_tags it JOIN tags t ON t.id = it.tag_id WHERE it.item_type = '{}' AND t.name = ?)",
+        id_expr, item_type
+    )
+}
+
+// 批量读取条目的标签（按名称排序）
+pub(super) fn load_item_tags(conn: &Connection, item_type: &str, ids: &[String]) -> Result<HashMap<String, Vec<String>>, rusqlite::Error> {
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    if ids.is_empty() {
+        return Ok(tags);
+    }
+
+    let placeholders = vec!["?"; ids.len()].join(",");
+    let sql = format!(
+        "SELECT it.item_id, t.name FROM item_tags it JOIN tags t ON t.id = it.tag_id
+         WHERE it.item_type = ? AND it.item_id IN ({}) ORDER BY t.name COLLATE NOCASE",
+        placeholders
+    );
+    let mut query_params: Vec<&dyn rusqlite::ToSql> = vec![&item_type];
+    query_params.extend(ids.iter().map(|id| id as &dyn rusqlite::ToSql));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(rusqlite::params_from_iter(query_params), |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for row in rows {
+        let (item_id, name) = row?;
+        tags.entry(item_id).or_default().push(name);
+    }
+    Ok(tags)
+}
+
+fn item_tag_names(conn: &Connection, item_type: &str, item_id: &str) -> Result<Vec<String>, rusqlite::Error> {
+    Ok(load_item_tags(conn, item_type, &[item_id.to_string()])?
+        .remove(item_id)
+        .unwrap_or_default())
+}
+
+// 查找标签，不存在时创建（名称不区分大小写）
+fn ensure_tag(conn: &Connection, name: &str) -> Result<i64, rusqlite::Error> {
+    let existing: Option<i64> = conn.query_row(
+        "SELECT id FROM tags WHERE name = ?1",
+        params![name],
+        |row| row.get(0)
+    ).optional()?;
+
+    match existing {
+        Some(id) => Ok(id),
+        None => {
+            conn.execute(
+                "INSERT INTO tags (name, created_at) VALUES (?1, ?2)",
+                params![name, chrono::Local::now().timestamp()],
+            )?;
+            Ok(conn.last_insert_rowid())
+        }
+    }
+}
+
+// 复制条目的标签（如收藏剪贴板项时）
+pub(super) fn copy_item_tags(conn: &Connection, from_type: &str, from_id: &str, to_type: &str, to_id: &str) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "INSERT OR IGNORE INTO item_tags (tag_id, item_type, item_id)
+         SELECT tag_id, ?3, ?4 FROM item_tags WHERE item_type = ?1 AND item_id = ?2",
+        params![from_type, from_id, to_type, to_id],
+    )?;
+    Ok(())
+}
+
+// 获取所有标签及其使用次数（不统计回收站中的条目）
+pub fn get_all_tags() -> Result<Vec<TagInfo>, String> {
+    with_connection(|conn| {
+        conn.prepare(
+            "SELECT t.id, t.name,
+                (SELECT COUNT(*) FROM item_tags it JOIN clipboard c ON c.id = CAST(it.item_id AS INTEGER)
+                 WHERE it.tag_id = t.id AND it.item_type = ?1 AND c.deleted_at IS NULL),
+                (SELECT COUNT(*) FROM item_tags it JOIN favorites f ON f.id = it.item_id
+                 WHERE it.tag_id = t.id AND it.item_type = ?2 AND f.deleted_at IS NULL)
+             FROM tags t ORDER BY t.name COLLATE NOCASE"
+        )?
+        .query_map(params![TAG_ITEM_CLIPBOARD, TAG_ITEM_FAVORITE], |row| {
+            Ok(TagInfo {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                clipboard_count: row.get(2)?,
+                favorite_count: row.get(3)?,
+            })
+        })?
+        .collect()
+    })
+}
+
+// 为条目添加标签（不存在的标签自动创建），返回条目当前的全部标签
+pub fn add_item_tags(item_type: &str, item_id: &str, tags: &[String]) -> Result<Vec<String>, String> {
+    let kind = item_kind(item_type)?;
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        for name in tags.iter().filter_map(|t| normalize_tag(t)) {
+            let tag_id = ensure_tag(&tx, &name)?;
+            tx.execute(
+                "INSERT OR IGNORE INTO item_tags (tag_id, item_type, item_id) VALUES (?1, ?2, ?3)",
+                params![tag_id, kind, item_id],
+            )?;
+        }
+        tx.commit()?;
+        item_tag_names(conn, kind, item_id)
+    })
+}
+
+// 移除条目的标签，返回条目当前的全部标签
+pub fn remove_item_tags(item_type: &str, item_id: &str, tags: &[String]) -> Result<Vec<String>, String> {
+    let kind = item_kind(item_type)?;
+    with_connection(|conn| {
+        for name in tags.iter().filter_map(|t| normalize_tag(t)) {
+            conn.execute(
+                "DELETE FROM item_tags WHERE item_type = ?1 AND item_id = ?2
+                 AND tag_id = (SELECT id FROM tags WHERE name = ?3)",
+                params![kind, item_id, name],
+            )?;
+        }
+        item_tag_names(conn, kind, item_id)
+    })
+}
+
+// 重命名标签，新名称已存在时合并到已有标签
+pub fn rename_tag(old_name: &str, new_name: &str) -> Result<(), String> {
+    let new_name = normalize_tag(new_name).ok_or("标签名称不能为空")?;
+    with_checked_connection(|conn| {
+        let old_id: i64 = conn.query_row(
+            "SELECT id FROM tags WHERE name = ?1",
+            params![old_name.trim()],
+            |row| row.get(0)
+        ).optional()?
+            .ok_or_else(|| DbError::Invalid(format!("标签 '{}' 不存在", old_name)))?;
+        let existing: Option<i64> = conn.query_row(
+            "SELECT id FROM tags WHERE name = ?1 AND id <> ?2",
+            params![&new_name, old_id],
+            |row| row.get(0)
+        ).optional()?;
+
+        let tx = conn.unchecked_transaction()?;
+        match existing {
+            Some(target_id) => {
+                tx.execute(
+                    "INSERT OR IGNORE INTO item_tags (tag_id, item_type, item_id)
+                     SELECT ?1, item_type, item_id FROM item_tags WHERE tag_id = ?2",
+                    params![target_id, old_id],
+                )?;
+                tx.execute("DELETE FROM item_tags WHERE tag_id = ?1", params![old_id])?;
+                tx.execute("DELETE FROM tags WHERE id = ?1", params![old_id])?;
+            }
+            None => {
+                tx.execute("UPDATE tags SET name = ?1 WHERE id = ?2", params![&new_name, old_id])?;
+            }
+        }
+        Ok(tx.commit()?)
+    })
+}
+
+// 删除标签（同时移除所有条目上的该标签）
+pub fn delete_tag(name: &str) -> Result<(), String> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM item_tags WHERE tag_id IN (SELECT id FROM tags WHERE name = ?1)",
+            params![name.trim()],
+        )?;
+        tx.execute("DELETE FROM tags WHERE name = ?1", params![name.trim()])?;
+        tx.commit()
+    })
+}
+
+// 合并导入库（已 ATTACH 为 schema）中的标签：标签按名称合并，剪贴板项按导入时的 ID 映射转换
+pub(crate) fn merge_attached_tags(conn: &Connection, schema: &str, clipboard_ids: &HashMap<i64, i64>) -> Result<(), rusqlite::Error> {
+    let sql = format!(
+        "SELECT t.name, it.item_type, it.item_id FROM {0}.item_tags it JOIN {0}.tags t ON t.id = it.tag_id",
+        schema
+    );
+    // 旧版本数据库没有标签表
+    let Ok(mut stmt) = conn.prepare(&sql) else {
+        return Ok(());
+    };
+    let rows: Vec<(String, String, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+        .collect::<Result<_, _>>()?;
+
+    for (name, item_type, item_id) in rows {
+        let item_id = match item_type.as_str() {
+            TAG_ITEM_CLIPBOARD => match item_id.parse::<i64>().ok().and_then(|id| clipboard_ids.get(&id)) {
+                Some(id) => id.to_string(),
+                None => continue,
+            },
+            TAG_ITEM_FAVORITE => {
+                let exists: bool = conn.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM favorites WHERE id = ?1)",
+                    params![&item_id],
+                    |row| row.get(0)
+                )?;
+                if !exists {
+                    continue;
+                }
+                item_id
+            }
+            _ => continue,
+        };
+        let tag_id = ensure_tag(conn, &name)?;
+        conn.execute(
+            "INSERT OR IGNORE INTO item_tags (tag_id, item_type, item_id) VALUES (?1, ?2, ?3)",
+            params![tag_id, &item_type, &item_id],
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::connection::create_tables;
+
+    #[test]
+    fn tags_are_case_insensitive_copied_and_cleaned_up() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO clipboard (id, content, created_at, updated_at) VALUES (7, 'x', 0, 0)",
+            [],
+        ).unwrap();
+
+        let work = ensure_tag(&conn, "Work").unwrap();
+        assert_eq!(ensure_tag(&conn, "work").unwrap(), work);
+        conn.execute(
+            "INSERT INTO item_tags (tag_id, item_type, item_id) VALUES (?1, 'clipboard', '7')",
+            params![work],
+        ).unwrap();
+
+        let filter = format!("SELECT COUNT(*) FROM clipboard WHERE {}", tag_filter_sql(TAG_ITEM_CLIPBOARD, "CAST(id AS TEXT)"));
+        let matched: i64 = conn.query_row(&filter, params!["WORK"], |row| row.get(0)).unwrap();
+        assert_eq!(matched, 1);
+
+        copy_item_tags(&conn, TAG_ITEM_CLIPBOARD, "7", TAG_ITEM_FAVORITE, "fav").unwrap();
+        assert_eq!(item_tag_names(&conn, TAG_ITEM_FAVORITE, "fav").unwrap(), vec!["Work"]);
+
+        conn.execute("DELETE FROM clipboard WHERE id = 7", []).unwrap();
+        assert!(item_tag_names(&conn, TAG_ITEM_CLIPBOARD, "7").unwrap().is_empty());
+        assert_eq!(item_tag_names(&conn, TAG_ITEM_FAVORITE, "fav").unwrap(), vec!["Work"]);
+    }
+}
@@ -0,0 +1,412 @@
+use super::models::{PaginatedResult, TrashBatch, TrashItem};
+use super::connection::{with_connection, MAX_CONTENT_LENGTH};
+use super::clipboard::{delete_image_files, is_image_id_referenced, split_image_ids};
+use super::groups::find_child_group;
+use super::link_metadata::{collect_link_urls, prune_link_metadata, delete_favicon_files};
+use super::snapshots::{collect_snapshot_ids, unreferenced_snapshots, delete_snapshot_dirs};
+use super::tags::{item_kind, TAG_ITEM_CLIPBOARD};
+use crate::utils::truncate_string;
+use rusqlite::{params, Connection, OptionalExtension, ToSql};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use chrono;
+
+// 删除操作类型（trash_batches.action）
+pub const TRASH_ACTION_CLIPBOARD: &str = "clipboard";
+pub const TRASH_ACTION_CLEAR_HISTORY: &str = "clear_history";
+pub const TRASH_ACTION_FAVORITE: &str = "favorite";
+pub const TRASH_ACTION_GROUP: &str = "group";
+
+// 删除分组时保存的分组树，以及其中收藏项原来所在的分组
+#[derive(Debug, Serialize, Deserialize)]
+struct GroupSnapshot {
+    parent_id: Option<i64>,
+    groups: Vec<DeletedGroup>,
+    favorites: Vec<(String, i64)>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DeletedGroup {
+    id: i64,
+    name: String,
+    icon: String,
+    color: String,
+    order_index: i32,
+    parent_id: Option<i64>,
+    created_at: i64,
+    updated_at: i64,
+}
+
+fn item_table(item_type: &str) -> Result<&'static str, String> {
+    Ok(if item_kind(item_type)? == TAG_ITEM_CLIPBOARD { "clipboard" } else { "favorites" })
+}
+
+fn begin_batch(conn: &Connection, action: &str, group_snapshot: Option<&str>) -> Result<(i64, i64), rusqlite::Error> {
+    let now = chrono::Local::now().timestamp();
+    conn.execute(
+        "INSERT INTO trash_batches (action, group_snapshot, deleted_at) VALUES (?1, ?2, ?3)",
+        params![action, group_snapshot, now],
+    )?;
+    Ok((conn.last_insert_rowid(), now))
+}
+
+// 生成 "id IN (?1, ?2, ...)" 条件（参数需编号，move_to_trash 会在其后追加参数）
+pub(super) fn id_in_sql(count: usize) -> String {
+    let placeholders: Vec<String> = (1..=count).map(|i| format!("?{}", i)).collect();
+    format!("id IN ({})", placeholders.join(", "))
+}
+
+// 把符合条件的未删除条目移到回收站（作为一次删除操作），返回移动的数量
+pub(super) fn move_to_trash(conn: &Connection, action: &str, table: &str, where_sql: &str, params: &[&dyn ToSql]) -> Result<usize, rusqlite::Error> {
+    let count: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM {} WHERE deleted_at IS NULL AND ({})", table, where_sql),
+        params,
+        |row| row.get(0)
+    )?;
+    if count == 0 {
+        return Ok(0);
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let (batch, now) = begin_batch(&tx, action, None)?;
+    // 条件中的参数编号从 ?1 开始，删除时间和操作 ID 排在其后
+    let mut update_params: Vec<&dyn ToSql> = params.to_vec();
+    update_params.push(&now);
+    update_params.push(&batch);
+    let moved = tx.execute(
+        &format!(
+            "UPDATE {} SET deleted_at = ?{}, trash_batch = ?{} WHERE deleted_at IS NULL AND ({})",
+            table, params.len() + 1, params.len() + 2, where_sql
+        ),
+        update_params.as_slice(),
+    )?;
+    tx.commit()?;
+    Ok(moved)
+}
+
+// 删除分组前调用：记录分组树和收藏项原来的分组，用于撤销
+pub(super) fn record_group_deletion(conn: &Connection, subtree: &[i64], parent_id: Option<i64>) -> Result<(), rusqlite::Error> {
+    let mut groups = Vec::new();
+    let mut favorites = Vec::new();
+    for id in subtree {
+        groups.push(conn.query_row(
+            "SELECT id, name, icon, color, order_index, parent_id, created_at, updated_at FROM groups WHERE id = ?1",
+            params![id],
+            |row| Ok(DeletedGroup {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                icon: row.get(2)?,
+                color: row.get(3)?,
+                order_index: row.get(4)?,
+                parent_id: row.get(5)?,
+                created_at: row.get(6)?,
+                updated_at: row.get(7)?,
+            })
+        )?);
+        let mut stmt = conn.prepare("SELECT id FROM favorites WHERE group_id = ?1")?;
+        for favorite in stmt.query_map(params![id], |row| row.get::<_, String>(0))? {
+            favorites.push((favorite?, *id));
+        }
+    }
+
+    let snapshot = serde_json::to_string(&GroupSnapshot { parent_id, groups, favorites })
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+    begin_batch(conn, TRASH_ACTION_GROUP, Some(&snapshot))?;
+    Ok(())
+}
+
+// 恢复被删除的分组树（父分组在前），同一位置已有同名分组时并入该分组；收藏项仍在删除时移入的分组中的才移回
+fn restore_group_snapshot(conn: &Connection, snapshot: &GroupSnapshot) -> Result<i64, rusqlite::Error> {
+    let group_exists = |id: i64| -> Result<bool, rusqlite::Error> {
+        conn.query_row("SELECT EXISTS(SELECT 1 FROM groups WHERE id = ?1)", params![id], |row| row.get(0))
+    };
+
+    let mut ids: HashMap<i64, i64> = HashMap::new();
+    for group in &snapshot.groups {
+        let parent_id = match group.parent_id {
+            Some(parent) => match ids.get(&parent) {
+                Some(mapped) => Some(*mapped),
+                None if group_exists(parent)? => Some(parent),
+                None => None,
+            },
+            None => None,
+        };
+
+        let id = match find_child_group(conn, parent_id, &group.name)? {
+            Some(existing) => existing,
+            None => {
+                let free_id = (!group_exists(group.id)?).then_some(group.id);
+                conn.execute(
+                    "INSERT INTO groups (id, name, icon, color, order_index, parent_id, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![free_id, &group.name, &group.icon, &group.color, group.order_index, parent_id, group.created_at, group.updated_at],
+                )?;
+                conn.last_insert_rowid()
+            }
+        };
+        ids.insert(group.id, id);
+    }
+
+    for (favorite, group) in &snapshot.favorites {
+        if let Some(group) = ids.get(group) {
+            conn.execute(
+                "UPDATE favorites SET group_id = ?1 WHERE id = ?2 AND group_id IS ?3",
+                params![group, favorite, snapshot.parent_id],
+            )?;
+        }
+    }
+    Ok(snapshot.groups.len() as i64)
+}
+
+// 删除已没有条目的删除操作记录；cutoff 不为空时同时删除早于该时间的分组删除记录
+fn prune_batches(conn: &Connection, cutoff: Option<i64>) -> Result<(), rusqlite::Error> {
+    conn.execute(
+        "DELETE FROM trash_batches WHERE group_snapshot IS NULL
+            AND NOT EXISTS (SELECT 1 FROM clipboard WHERE trash_batch = trash_batches.id AND deleted_at IS NOT NULL)
+            AND NOT EXISTS (SELECT 1 FROM favorites WHERE trash_batch = trash_batches.id AND deleted_at IS NOT NULL)",
+        [],
+    )?;
+    if let Some(cutoff) = cutoff {
+        conn.execute(
+            "DELETE FROM trash_batches WHERE group_snapshot IS NOT NULL AND deleted_at < ?1",
+            params![cutoff],
+        )?;
+    }
+    Ok(())
+}
+
+// 分页获取回收站中的条目（最近删除的在前）
+pub fn get_trash_items(offset: i64, limit: i64) -> Result<PaginatedResult<TrashItem>, String> {
+    with_connection(|conn| {
+        let total_count: i64 = conn.query_row(
+            "SELECT (SELECT COUNT(*) FROM clipboard WHERE deleted_at IS NOT NULL)
+                  + (SELECT COUNT(*) FROM favorites WHERE deleted_at IS NOT NULL)",
+            [],
+            |row| row.get(0)
+        )?;
+
+        let items = conn.prepare(
+            "SELECT 'clipboard', CAST(id AS TEXT), title, content, content_type, image_id, deleted_at FROM clipboard WHERE deleted_at IS NOT NULL
+             UNION ALL
+             SELECT 'favorite', id, title, content, content_type, image_id, deleted_at FROM favorites WHERE deleted_at IS NOT NULL
+             ORDER BY 7 DESC
+             LIMIT ?1 OFFSET ?2"
+        )?
+        .query_map(params![limit, offset], |row| {
+            Ok(TrashItem {
+                item_type: row.get(0)?,
+                item_id: row.get(1)?,
+                title: row.get(2)?,
+                content: truncate_string(row.get(3)?, MAX_CONTENT_LENGTH),
+                content_type: row.get(4)?,
+                image_id: row.get(5)?,
+                deleted_at: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(PaginatedResult::new(total_count, items, offset, limit))
+    })
+}
+
+// 从回收站恢复条目
+pub fn restore_trash_item(item_type: &str, item_id: &str) -> Result<(), String> {
+    let table = item_table(item_type)?;
+    let restored = with_connection(|conn| {
+        let restored = conn.execute(
+            &format!("UPDATE {} SET deleted_at = NULL, trash_batch = NULL WHERE id = ?1 AND deleted_at IS NOT NULL", table),
+            params![item_id],
+        )?;
+        if restored > 0 {
+            prune_batches(conn, None)?;
+        }
+        Ok(restored)
+    })?;
+    match restored {
+        0 => Err(format!("回收站中没有条目 {}", item_id)),
+        _ => Ok(()),
+    }
+}
+
+// 撤销最近一次删除，没有可撤销的操作时返回空
+pub fn undo_last_delete() -> Result<Option<TrashBatch>, String> {
+    with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let batch = undo_latest_batch(&tx)?;
+        tx.commit()?;
+        Ok(batch)
+    })
+}
+
+fn undo_latest_batch(conn: &Connection) -> Result<Option<TrashBatch>, rusqlite::Error> {
+    loop {
+        let latest: Option<(i64, String, Option<String>, i64)> = conn.query_row(
+            "SELECT id, action, group_snapshot, deleted_at FROM trash_batches ORDER BY id DESC LIMIT 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        ).optional()?;
+        let Some((id, action, group_snapshot, deleted_at)) = latest else {
+            return Ok(None);
+        };
+
+        let item_count = match group_snapshot {
+            Some(json) => match serde_json::from_str::<GroupSnapshot>(&json) {
+                Ok(snapshot) => restore_group_snapshot(conn, &snapshot)?,
+                Err(_) => 0,
+            },
+            None => {
+                let mut restored = 0;
+                for table in ["clipboard", "favorites"] {
+                    restored += conn.execute(
+                        &format!("UPDATE {} SET deleted_at = NULL, trash_batch = NULL WHERE trash_batch = ?1 AND deleted_at IS NOT NULL", table),
+                        params![id],
+                    )? as i64;
+                }
+                restored
+            }
+        };
+        conn.execute("DELETE FROM trash_batches WHERE id = ?1", params![id])?;
+
+        // 该次删除的条目已被单独恢复或清除时，继续撤销更早的一次
+        if item_count > 0 {
+            return Ok(Some(TrashBatch { id, action, item_count, deleted_at }));
+        }
+    }
+}
+
+// 彻底删除回收站中符合条件的条目，并删除不再被引用的图片、文件快照和链接预览
+fn purge_where(tables: &[&str], where_sql: &str, params: &[&dyn ToSql], cutoff: Option<i64>) -> Result<usize, String> {
+    let (purged, images_to_delete, snapshots_to_delete, favicons_to_delete) = with_connection(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let mut image_ids: HashSet<String> = HashSet::new();
+        let mut snapshot_ids: HashSet<String> = HashSet::new();
+        let mut link_urls: HashSet<String> = HashSet::new();
+        let mut purged = 0;
+
+        for table in tables {
+            let condition = format!("deleted_at IS NOT NULL AND ({})", where_sql);
+            let content_sql = format!("SELECT content FROM {} WHERE {}", table, condition);
+            snapshot_ids.extend(collect_snapshot_ids(&tx, &content_sql, params)?);
+            link_urls.extend(collect_link_urls(&tx, &content_sql, params)?);
+            let mut stmt = tx.prepare(&format!(
+                "SELECT image_id FROM {} WHERE {} AND image_id IS NOT NULL AND image_id <> ''",
+                table, condition
+            ))?;
+            for ids in stmt.query_map(params, |row| row.get::<_, String>(0))? {
+                image_ids.extend(split_image_ids(&ids?));
+            }
+            drop(stmt);
+
+            purged += tx.execute(&format!("DELETE FROM {} WHERE {}", table, condition), params)?;
+        }
+        prune_batches(&tx, cutoff)?;
+
+        let mut to_delete = Vec::new();
+        for iid in image_ids {
+            if !is_image_id_referenced(&tx, &iid)? {
+                to_delete.push(iid);
+            }
+        }
+        let snapshots = unreferenced_snapshots(&tx, snapshot_ids)?;
+        let favicons = prune_link_metadata(&tx, link_urls)?;
+        tx.commit()?;
+        Ok((purged, to_delete, snapshots, favicons))
+    })?;
+
+    delete_snapshot_dirs(snapshots_to_delete)?;
+    delete_favicon_files(favicons_to_delete)?;
+    delete_image_files(images_to_delete)?;
+    Ok(purged)
+}
+
+// 彻底删除回收站中的单个条目
+pub fn purge_trash_item(item_type: &str, item_id: &str) -> Result<(), String> {
+    let table = item_table(item_type)?;
+    match purge_where(&[table], "id = ?1", params![item_id], None)? {
+        0 => Err(format!("回收站中没有条目 {}", item_id)),
+        _ => Ok(()),
+    }
+}
+
+// 清空回收站（同时清除分组删除记录，之后无法再撤销）
+pub fn empty_trash() -> Result<usize, String> {
+    purge_where(&["clipboard", "favorites"], "1 = 1", params![], Some(i64::MAX))
+}
+
+// 清除超过保留天数的回收站条目，retention_days 为 0 时不自动清除
+pub fn purge_expired_trash(retention_days: u32) -> Result<usize, String> {
+    if retention_days == 0 {
+        return Ok(0);
+    }
+    let cutoff = chrono::Local::now().timestamp() - retention_days as i64 * 86400;
+    purge_where(&["clipboard", "favorites"], "deleted_at < ?1", params![cutoff], Some(cutoff))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::connection::create_tables;
+
+    fn live_ids(conn: &Connection) -> Vec<i64> {
+        conn.prepare("SELECT id FROM clipboard WHERE deleted_at IS NULL ORDER BY id").unwrap()
+            .query_map([], |row| row.get(0)).unwrap()
+            .collect::<Result<_, _>>().unwrap()
+    }
+
+    #[test]
+    fn undo_restores_the_latest_delete_operation() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO clipboard (id, content, created_at, updated_at) VALUES (1, 'a', 0, 0), (2, 'b', 0, 0), (3, 'c', 0, 0);"
+        ).unwrap();
+
+        assert_eq!(move_to_trash(&conn, TRASH_ACTION_CLIPBOARD, "clipboard", "id = ?1", params![1]).unwrap(), 1);
+        assert_eq!(move_to_trash(&conn, TRASH_ACTION_CLEAR_HISTORY, "clipboard", "1 = 1", params![]).unwrap(), 2);
+        assert_eq!(move_to_trash(&conn, TRASH_ACTION_CLEAR_HISTORY, "clipboard", "1 = 1", params![]).unwrap(), 0);
+        assert!(live_ids(&conn).is_empty());
+
+        let undone = undo_latest_batch(&conn).unwrap().unwrap();
+        assert_eq!((undone.action.as_str(), undone.item_count), (TRASH_ACTION_CLEAR_HISTORY, 2));
+        assert_eq!(live_ids(&conn), vec![2, 3]);
+
+        // 单独恢复过的删除操作会被跳过
+        conn.execute("UPDATE clipboard SET deleted_at = NULL, trash_batch = NULL WHERE id = 1", []).unwrap();
+        assert!(undo_latest_batch(&conn).unwrap().is_none());
+    }
+
+    #[test]
+    fn selected_items_are_trashed_as_one_operation() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO clipboard (id, content, created_at, updated_at) VALUES (1, 'a', 0, 0), (2, 'b', 0, 0), (3, 'c', 0, 0);"
+        ).unwrap();
+
+        assert_eq!(move_to_trash(&conn, TRASH_ACTION_CLIPBOARD, "clipboard", &id_in_sql(2), params![1, 3]).unwrap(), 2);
+        assert_eq!(live_ids(&conn), vec![2]);
+        assert_eq!(undo_latest_batch(&conn).unwrap().unwrap().item_count, 2);
+        assert_eq!(live_ids(&conn), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn deleted_group_tree_is_restored() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_tables(&conn).unwrap();
+        conn.execute_batch(
+            "PRAGMA foreign_keys = ON;
+             INSERT INTO groups (id, name, order_index, parent_id, created_at, updated_at) VALUES (1, 'Work', 1, NULL, 0, 0), (2, 'SQL', 2, 1, 0, 0);
+             INSERT INTO favorites (id, title, content, group_id, item_order, created_at, updated_at) VALUES ('f', '', 'x', 2, 1, 0, 0);"
+        ).unwrap();
+
+        record_group_deletion(&conn, &[1, 2], None).unwrap();
+        conn.execute_batch("UPDATE favorites SET group_id = NULL; DELETE FROM groups;").unwrap();
+
+        let json: String = conn.query_row("SELECT group_snapshot FROM trash_batches", [], |row| row.get(0)).unwrap();
+        let restored = restore_group_snapshot(&conn, &serde_json::from_str(&json).unwrap()).unwrap();
+        assert_eq!(restored, 2);
+        let group: Option<i64> = conn.query_row("SELECT group_id FROM favorites WHERE id = 'f'", [], |row| row.get(0)).unwrap();
+        assert_eq!(group, Some(2));
+        let parent: Option<i64> = conn.query_row("SELECT parent_id FROM groups WHERE id = 2", [], |row| row.get(0)).unwrap();
+        assert_eq!(parent, Some(1));
+    }
+}
@@ -0,0 +1,212 @@
+// 网页与图标下载（带大小和超时限制）
+use super::parser::{parse_page_metadata, PageMetadata};
+use crate::utils::http::read_limited_body;
+use reqwest::blocking::Client;
+use reqwest::Url;
+use std::time::Duration;
+
+// 下载限制
+#[derive(Debug, Clone)]
+pub struct FetchLimits {
+    pub timeout: Duration,
+    pub max_page_bytes: u64,
+    pub max_favicon_bytes: u64,
+}
+
+// 下载结果
+#[derive(Debug, Clone, Default)]
+pub struct FetchedPreview {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub favicon: Option<Vec<u8>>,
+}
+
+fn build_client(limits: &FetchLimits) -> Result<Client, String> {
+    Client::builder()
+        .timeout(limits.timeout)
+        .connect_timeout(limits.timeout)
+        .redirect(reqwest::redirect::Policy::limited(5))
+        .user_agent(concat!("QuickClipboard/", env!("CARGO_PKG_VERSION"), " (link preview)"))
+        .build()
+        .map_err(|e| format!("创建HTTP客户端失败: {}", e))
+}
+
+// 获取网页元数据和图标
+pub fn fetch_preview(url: &str, limits: &FetchLimits) -> Result<FetchedPreview, String> {
+    let page_url = Url::parse(url).map_err(|e| format!("无效的链接 [{}]: {}", url, e))?;
+    if page_url.scheme() != "http" && page_url.scheme() != "https" {
+        return Err(format!("不支持的链接协议: {}", page_url.scheme()));
+    }
+
+    let client = build_client(limits)?;
+    let response = client.get(page_url.clone())
+        .header(reqwest::header::ACCEPT, "text/html,application/xhtml+xml")
+        .send()
+        .map_err(|e| format!("请求网页失败: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("请求网页失败: HTTP {}", response.status()));
+    }
+
+    let is_html = response.headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_lowercase().contains("html"))
+        .unwrap_or(true);
+    if !is_html {
+        return Err("链接不是网页".to_string());
+    }
+
+    // 以重定向后的地址作为相对路径的基准
+    let final_url = response.url().clone();
+    let body = read_limited_body(response, limits.max_page_bytes)?;
+    let html = String::from_utf8_lossy(&body);
+    let PageMetadata { title, description, favicon_href } = parse_page_metadata(&html);
+
+    let favicon_url = match favicon_href {
+        Some(href) if !href.starts_with("data:") => final_url.join(&href).ok(),
+        Some(_) => None,
+        None => final_url.join("/favicon.ico").ok(),
+    };
+
+    let favicon = favicon_url.and_then(|icon_url| {
+        fetch_favicon(&client, icon_url, limits.max_favicon_bytes)
+            .map_err(|e| eprintln!("获取网站图标失败: {}", e))
+            .ok()
+    });
+
+    Ok(FetchedPreview { title, description, favicon })
+}
+
+fn fetch_favicon(client: &Client, url: Url, max_bytes: u64) -> Result<Vec<u8>, String> {
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(format!("不支持的图标协议: {}", url.scheme()));
+    }
+
+    let response = client.get(url)
+        .send()
+        .map_err(|e| format!("请求图标失败: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("请求图标失败: HTTP {}", response.status()));
+    }
+
+    read_limited_body(response, max_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    // 本地 HTTP 替身：按路径返回固定响应
+    fn serve(routes: Vec<(&'static str, &'static str, Vec<u8>, u64)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut stream = stream;
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+
+                match routes.iter().find(|(p, _, _, _)| *p == path) {
+                    Some((_, content_type, body, delay_ms)) => {
+                        std::thread::sleep(Duration::from_millis(*delay_ms));
+                        let header = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            content_type,
+                            body.len()
+                        );
+                        let _ = stream.write_all(header.as_bytes());
+                        let _ = stream.write_all(body);
+                    }
+                    None => {
+                        let _ = stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+                    }
+                }
+            }
+        });
+
+        format!("http://{}", addr)
+    }
+
+    fn limits() -> FetchLimits {
+        FetchLimits {
+            timeout: Duration::from_secs(2),
+            max_page_bytes: 64 * 1024,
+            max_favicon_bytes: 1024,
+        }
+    }
+
+    #[test]
+    fn test_fetch_preview_with_declared_icon() {
+        let html = br#"<html><head><title>Stand-in</title>
+            <meta property="og:description" content="Local page">
+            <link rel="icon" href="icons/site.png"></head></html>"#;
+        let base = serve(vec![
+            ("/page/index.html", "text/html; charset=utf-8", html.to_vec(), 0),
+            ("/page/icons/site.png", "image/png", vec![1, 2, 3], 0),
+        ]);
+
+        let preview = fetch_preview(&format!("{}/page/index.html", base), &limits()).unwrap();
+        assert_eq!(preview.title.as_deref(), Some("Stand-in"));
+        assert_eq!(preview.description.as_deref(), Some("Local page"));
+        assert_eq!(preview.favicon, Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_fetch_preview_falls_back_to_favicon_ico() {
+        let base = serve(vec![
+            ("/", "text/html", b"<title>Root</title>".to_vec(), 0),
+            ("/favicon.ico", "image/x-icon", vec![9; 16], 0),
+        ]);
+
+        let preview = fetch_preview(&format!("{}/", base), &limits()).unwrap();
+        assert_eq!(preview.title.as_deref(), Some("Root"));
+        assert_eq!(preview.favicon.map(|f| f.len()), Some(16));
+    }
+
+    #[test]
+    fn test_oversized_favicon_is_dropped() {
+        let base = serve(vec![
+            ("/", "text/html", b"<title>Big icon</title>".to_vec(), 0),
+            ("/favicon.ico", "image/x-icon", vec![0; 4096], 0),
+        ]);
+
+        let preview = fetch_preview(&format!("{}/", base), &limits()).unwrap();
+        assert_eq!(preview.title.as_deref(), Some("Big icon"));
+        assert!(preview.favicon.is_none());
+    }
+
+    #[test]
+    fn test_oversized_page_is_rejected() {
+        let body = format!("<title>x</title>{}", "a".repeat(128 * 1024)).into_bytes();
+        let base = serve(vec![("/", "text/html", body, 0)]);
+
+        assert!(fetch_preview(&format!("{}/", base), &limits()).is_err());
+    }
+
+    #[test]
+    fn test_non_html_is_rejected() {
+        let base = serve(vec![("/file.zip", "application/zip", vec![0; 32], 0)]);
+        assert!(fetch_preview(&format!("{}/file.zip", base), &limits()).is_err());
+    }
+
+    #[test]
+    fn test_slow_server_times_out() {
+        let base = serve(vec![("/", "text/html", b"<title>late</title>".to_vec(), 1500)]);
+        let mut short = limits();
+        short.timeout = Duration::from_millis(300);
+
+        assert!(fetch_preview(&format!("{}/", base), &short).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_scheme() {
+        assert!(fetch_preview("ftp://example.com/", &limits()).is_err());
+    }
+}
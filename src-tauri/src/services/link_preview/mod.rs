@@ -0,0 +1,237 @@
+// 链接预览：为 link 类型条目后台获取标题、描述和网站图标
+mod fetcher;
+mod parser;
+
+pub use parser::extract_first_url;
+
+use crate::services::database::{get_link_metadata, save_link_metadata, LinkMetadata};
+use crate::services::{get_data_directory, get_settings, AppSettings};
+use fetcher::{fetch_preview, FetchLimits};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashSet;
+use std::sync::mpsc::{channel, Sender};
+use std::time::Duration;
+
+const FAVICONS_DIR: &str = "favicons";
+
+// 成功结果的缓存有效期（秒）
+const METADATA_TTL_SECS: i64 = 7 * 24 * 3600;
+// 失败结果的重试间隔（秒）
+const FAILED_RETRY_SECS: i64 = 3600;
+
+static WORKER_SENDER: Lazy<Mutex<Option<Sender<String>>>> = Lazy::new(|| Mutex::new(None));
+
+// 正在排队或下载中的链接
+static PENDING_URLS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+// 提交链接预览任务（不阻塞调用方）
+pub fn enqueue_link_preview(text: &str) {
+    let settings = get_settings();
+    if !settings.link_preview_enabled {
+        return;
+    }
+
+    let Some(url) = extract_first_url(text) else { return };
+    if !is_domain_allowed(&url, &settings) || !needs_fetch(&url) {
+        return;
+    }
+
+    submit(url);
+}
+
+// 强制重新获取链接预览
+pub fn refresh_link_preview(url: &str) -> Result<(), String> {
+    let settings = get_settings();
+    if !settings.link_preview_enabled {
+        return Err("链接预览已关闭".to_string());
+    }
+    if !is_domain_allowed(url, &settings) {
+        return Err("该域名不允许获取链接预览".to_string());
+    }
+
+    submit(url.to_string());
+    Ok(())
+}
+
+fn submit(url: String) {
+    if !PENDING_URLS.lock().insert(url.clone()) {
+        return;
+    }
+
+    let mut sender = WORKER_SENDER.lock();
+    if sender.is_none() {
+        *sender = Some(start_worker());
+    }
+
+    if let Some(tx) = sender.as_ref() {
+        if tx.send(url.clone()).is_err() {
+            PENDING_URLS.lock().remove(&url);
+            *sender = None;
+        }
+    }
+}
+
+// 启动后台下载线程（与剪贴板监听线程分离）
+fn start_worker() -> Sender<String> {
+    let (tx, rx) = channel::<String>();
+
+    std::thread::spawn(move || {
+        for url in rx {
+            process_url(&url);
+            PENDING_URLS.lock().remove(&url);
+        }
+    });
+
+    tx
+}
+
+fn process_url(url: &str) {
+    // 任务排队期间设置可能已变化
+    let settings = get_settings();
+    if !settings.link_preview_enabled || !is_domain_allowed(url, &settings) {
+        return;
+    }
+
+    let limits = FetchLimits {
+        timeout: Duration::from_secs(settings.link_preview_timeout_secs.max(1) as u64),
+        max_page_bytes: settings.link_preview_max_page_bytes,
+        max_favicon_bytes: settings.link_preview_max_favicon_bytes,
+    };
+
+    let now = chrono::Local::now().timestamp();
+    let metadata = match fetch_preview(url, &limits) {
+        Ok(preview) => LinkMetadata {
+            url: url.to_string(),
+            title: preview.title,
+            description: preview.description,
+            favicon_hash: preview.favicon.and_then(|data| save_favicon(&data)),
+            status: "ok".to_string(),
+            fetched_at: now,
+        },
+        Err(e) => {
+            eprintln!("获取链接预览失败 [{}]: {}", url, e);
+            LinkMetadata {
+                url: url.to_string(),
+                title: None,
+                description: None,
+                favicon_hash: None,
+                status: "failed".to_string(),
+                fetched_at: now,
+            }
+        }
+    };
+
+    if let Err(e) = save_link_metadata(&metadata) {
+        eprintln!("保存链接预览失败: {}", e);
+        return;
+    }
+
+    emit_link_preview_updated(&metadata);
+}
+
+// 判断是否需要（重新）获取
+fn needs_fetch(url: &str) -> bool {
+    let now = chrono::Local::now().timestamp();
+    match get_link_metadata(url) {
+        Ok(Some(meta)) if meta.status == "ok" => now - meta.fetched_at > METADATA_TTL_SECS,
+        Ok(Some(meta)) => now - meta.fetched_at > FAILED_RETRY_SECS,
+        _ => true,
+    }
+}
+
+// 检查域名是否允许获取（黑名单/白名单，支持 * 和 ? 通配符）
+fn is_domain_allowed(url: &str, settings: &AppSettings) -> bool {
+    let Some(host) = reqwest::Url::parse(url).ok().and_then(|u| u.host_str().map(|h| h.to_lowercase())) else {
+        return false;
+    };
+
+    let matches = settings.link_preview_domain_list.iter().any(|rule| matches_domain_rule(&host, rule));
+
+    match settings.link_preview_domain_mode.as_str() {
+        "whitelist" => matches,
+        _ => !matches,
+    }
+}
+
+fn matches_domain_rule(host: &str, rule: &str) -> bool {
+    let rule = rule.trim().trim_start_matches('.').to_lowercase();
+    if rule.is_empty() {
+        return false;
+    }
+
+    if rule.contains('*') || rule.contains('?') {
+        crate::services::system::app_filter::wildcard_match(&rule, host)
+    } else {
+        host == rule || host.ends_with(&format!(".{}", rule))
+    }
+}
+
+// 保存网站图标到 favicons 目录，返回图标哈希
+fn save_favicon(data: &[u8]) -> Option<String> {
+    use image::ImageFormat;
+    use sha2::{Digest, Sha256};
+    use std::io::Cursor;
+
+    let img = image::ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .ok()?
+        .decode()
+        .ok()?;
+
+    let mut png_data = Vec::new();
+    img.thumbnail(32, 32)
+        .write_to(&mut Cursor::new(&mut png_data), ImageFormat::Png)
+        .ok()?;
+
+    let hash = format!("{:x}", Sha256::digest(&png_data))[..16].to_string();
+
+    let favicons_dir = get_data_directory().ok()?.join(FAVICONS_DIR);
+    if !favicons_dir.exists() {
+        std::fs::create_dir_all(&favicons_dir).ok()?;
+    }
+
+    let icon_path = favicons_dir.join(format!("{}.png", hash));
+    if !icon_path.exists() {
+        std::fs::write(&icon_path, &png_data).ok()?;
+    }
+
+    Some(hash)
+}
+
+fn emit_link_preview_updated(metadata: &LinkMetadata) {
+    use tauri::Emitter;
+    if let Some(app) = crate::services::clipboard::get_app_handle() {
+        let _ = app.emit("link-preview-updated", metadata);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings_with(mode: &str, list: &[&str]) -> AppSettings {
+        AppSettings {
+            link_preview_domain_mode: mode.to_string(),
+            link_preview_domain_list: list.iter().map(|s| s.to_string()).collect(),
+            ..AppSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_domain_blacklist() {
+        let settings = settings_with("blacklist", &["example.com", "*.internal.*"]);
+        assert!(!is_domain_allowed("https://example.com/a", &settings));
+        assert!(!is_domain_allowed("https://docs.example.com/a", &settings));
+        assert!(!is_domain_allowed("http://wiki.internal.corp/", &settings));
+        assert!(is_domain_allowed("https://notexample.com/", &settings));
+    }
+
+    #[test]
+    fn test_domain_whitelist() {
+        let settings = settings_with("whitelist", &["github.com"]);
+        assert!(is_domain_allowed("https://github.com/x/y", &settings));
+        assert!(!is_domain_allowed("https://gitlab.com/x/y", &settings));
+        assert!(!is_domain_allowed("not a url", &settings));
+    }
+}
@@ -0,0 +1,196 @@
+// 网页元数据解析
+use regex::Regex;
+
+// 从 HTML 中提取的页面元数据
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub favicon_href: Option<String>,
+}
+
+const MAX_TITLE_CHARS: usize = 200;
+const MAX_DESCRIPTION_CHARS: usize = 500;
+
+// 提取文本中的第一个链接
+pub fn extract_first_url(text: &str) -> Option<String> {
+    let url_regex = Regex::new(r#"(?i)\b(https?://|www\.)[^\s<>"']+"#).ok()?;
+    let found = url_regex.find(text)?.as_str();
+    let trimmed = found.trim_end_matches(['.', ',', ')', ']', ';', '!', '?']);
+    if trimmed.to_lowercase().starts_with("www.") {
+        Some(format!("https://{}", trimmed))
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+// 解析页面标题、OpenGraph 描述和图标地址
+pub fn parse_page_metadata(html: &str) -> PageMetadata {
+    let mut og_title = None;
+    let mut og_description = None;
+    let mut meta_description = None;
+
+    if let Ok(meta_regex) = Regex::new(r"(?is)<meta\b[^>]*>") {
+        for tag in meta_regex.find_iter(html) {
+            let attrs = parse_attributes(tag.as_str());
+            let key = attr_value(&attrs, "property")
+                .or_else(|| attr_value(&attrs, "name"))
+                .map(|k| k.to_lowercase());
+            let Some(content) = attr_value(&attrs, "content") else { continue };
+
+            match key.as_deref() {
+                Some("og:title") if og_title.is_none() => og_title = Some(content),
+                Some("og:description") if og_description.is_none() => og_description = Some(content),
+                Some("description") if meta_description.is_none() => meta_description = Some(content),
+                _ => {}
+            }
+        }
+    }
+
+    let title_tag = Regex::new(r"(?is)<title[^>]*>(.*?)</title>")
+        .ok()
+        .and_then(|re| re.captures(html).map(|c| c[1].to_string()));
+
+    PageMetadata {
+        title: clean_text(og_title.or(title_tag), MAX_TITLE_CHARS),
+        description: clean_text(og_description.or(meta_description), MAX_DESCRIPTION_CHARS),
+        favicon_href: find_favicon_href(html),
+    }
+}
+
+// 查找图标链接（优先 icon，其次 apple-touch-icon）
+fn find_favicon_href(html: &str) -> Option<String> {
+    let link_regex = Regex::new(r"(?is)<link\b[^>]*>").ok()?;
+    let mut touch_icon = None;
+
+    for tag in link_regex.find_iter(html) {
+        let attrs = parse_attributes(tag.as_str());
+        let Some(rel) = attr_value(&attrs, "rel").map(|r| r.to_lowercase()) else { continue };
+        let Some(href) = attr_value(&attrs, "href").filter(|h| !h.trim().is_empty()) else { continue };
+
+        let rels: Vec<&str> = rel.split_whitespace().collect();
+        if rels.contains(&"icon") {
+            return Some(href.trim().to_string());
+        }
+        if touch_icon.is_none() && rels.iter().any(|r| r.starts_with("apple-touch-icon")) {
+            touch_icon = Some(href.trim().to_string());
+        }
+    }
+
+    touch_icon
+}
+
+// 解析标签属性
+fn parse_attributes(tag: &str) -> Vec<(String, String)> {
+    let Ok(attr_regex) = Regex::new(r#"(?s)([a-zA-Z_:][-a-zA-Z0-9_:.]*)\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+))"#) else {
+        return Vec::new();
+    };
+
+    attr_regex
+        .captures_iter(tag)
+        .map(|c| {
+            let value = c.get(2).or_else(|| c.get(3)).or_else(|| c.get(4))
+                .map(|m| m.as_str())
+                .unwrap_or("");
+            (c[1].to_lowercase(), decode_entities(value))
+        })
+        .collect()
+}
+
+fn attr_value(attrs: &[(String, String)], name: &str) -> Option<String> {
+    attrs.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone())
+}
+
+// 清理空白并限制长度
+fn clean_text(text: Option<String>, max_chars: usize) -> Option<String> {
+    let text = decode_entities(&text?);
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return None;
+    }
+    if collapsed.chars().count() > max_chars {
+        Some(format!("{}...", collapsed.chars().take(max_chars).collect::<String>()))
+    } else {
+        Some(collapsed)
+    }
+}
+
+// 解码常见 HTML 实体
+pub fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+
+    let Ok(entity_regex) = Regex::new(r"&(#[xX][0-9a-fA-F]+|#[0-9]+|[a-zA-Z]+);") else {
+        return text.to_string();
+    };
+
+    entity_regex.replace_all(text, |caps: &regex::Captures| {
+        let entity = &caps[1];
+        let decoded = if let Some(hex) = entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        } else if let Some(dec) = entity.strip_prefix('#') {
+            dec.parse::<u32>().ok().and_then(char::from_u32)
+        } else {
+            match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => None,
+            }
+        };
+        decoded.map(|c| c.to_string()).unwrap_or_else(|| caps[0].to_string())
+    }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_og_metadata() {
+        let html = r#"<html><head>
+            <title>Fallback Title</title>
+            <meta property="og:title" content="OG &amp; Title">
+            <meta property="og:description" content='Short description'>
+            <link rel="shortcut icon" href="/static/favicon.png">
+        </head></html>"#;
+        let meta = parse_page_metadata(html);
+        assert_eq!(meta.title.as_deref(), Some("OG & Title"));
+        assert_eq!(meta.description.as_deref(), Some("Short description"));
+        assert_eq!(meta.favicon_href.as_deref(), Some("/static/favicon.png"));
+    }
+
+    #[test]
+    fn test_parse_title_and_meta_description_fallback() {
+        let html = "<HEAD><TITLE>\n  Plain   Title \n</TITLE><meta name=description content=Hello></HEAD>";
+        let meta = parse_page_metadata(html);
+        assert_eq!(meta.title.as_deref(), Some("Plain Title"));
+        assert_eq!(meta.description.as_deref(), Some("Hello"));
+        assert_eq!(meta.favicon_href, None);
+    }
+
+    #[test]
+    fn test_apple_touch_icon_used_when_no_icon() {
+        let html = r#"<link rel="apple-touch-icon-precomposed" href="/touch.png"><link rel="stylesheet" href="/a.css">"#;
+        assert_eq!(parse_page_metadata(html).favicon_href.as_deref(), Some("/touch.png"));
+    }
+
+    #[test]
+    fn test_extract_first_url() {
+        assert_eq!(
+            extract_first_url("see https://example.com/a?b=1, thanks").as_deref(),
+            Some("https://example.com/a?b=1")
+        );
+        assert_eq!(extract_first_url("www.example.org.").as_deref(), Some("https://www.example.org"));
+        assert_eq!(extract_first_url("no links here"), None);
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("a &lt;b&gt; &#39;c&#x27; &unknown;"), "a <b> 'c' &unknown;");
+    }
+}
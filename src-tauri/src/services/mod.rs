@@ -9,6 +9,7 @@ pub mod sound;
 pub mod screenshot;
 pub mod image_library;
 pub mod low_memory;
+pub mod link_preview;
 
 pub use settings::{AppSettings, get_settings, update_settings, get_data_directory};
 pub use notification::show_startup_notification;
@@ -53,7 +54,7 @@ pub fn resolve_stored_path(stored_path: &str) -> String {
         Some(joined.to_string_lossy().to_string())
     };
 
-    for prefix in ["clipboard_images/", "pin_images/"] {
+    for prefix in ["clipboard_images/", "pin_images/", "file_snapshots/"] {
         if normalized.starts_with(prefix) {
             if let Some(p) = resolve_relative(&normalized) {
                 return p;
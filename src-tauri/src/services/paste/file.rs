@@ -10,6 +10,8 @@ struct FileInfo {
     is_directory: bool,
     icon_data: Option<String>,
     file_type: String,
+    #[serde(default)]
+    snapshot: Option<String>,
 }
 
 // 文件剪贴板数据
@@ -19,7 +21,7 @@ struct FileClipboardData {
     operation: String,
 }
 
-// 粘贴文件路径（只粘贴存在的文件）
+// 粘贴文件路径（只粘贴存在的文件，源文件不存在时使用快照）
 pub fn paste_files(ctx: &ClipboardContext, content: &str) -> Result<(), String> {
     if !content.starts_with("files:") {
         return Err("无效的文件内容格式".to_string());
@@ -29,21 +31,224 @@ pub fn paste_files(ctx: &ClipboardContext, content: &str) -> Result<(), String>
     let file_data: FileClipboardData = serde_json::from_str(json_str)
         .map_err(|e| format!("解析文件数据失败: {}", e))?;
     
+    let mut uses_snapshot = false;
     let file_paths: Vec<String> = file_data.files
         .iter()
-        .map(|f| crate::services::resolve_stored_path(&f.path))
-        .filter(|p| Path::new(p).exists())
+        .filter_map(|f| {
+            let (path, from_snapshot) = existing_file_path(f)?;
+            uses_snapshot |= from_snapshot;
+            Some(path)
+        })
         .collect();
     
     if file_paths.is_empty() {
         return Err("所有文件都不存在".to_string());
     }
     
+    // 粘贴快照时按实际路径预设哈希，避免被重新记录
+    if uses_snapshot {
+        crate::services::clipboard::set_last_hash_paths(&file_paths);
+    }
+    
     ctx.set_files(file_paths)
         .map_err(|e| format!("设置文件到剪贴板失败: {}", e))
 }
 
+// 文件的实际可用路径：源文件存在时使用源文件，否则使用快照（第二项表示是否来自快照）
+fn existing_file_path(file: &FileInfo) -> Option<(String, bool)> {
+    let path = crate::services::resolve_stored_path(&file.path);
+    if Path::new(&path).exists() {
+        return Some((path, false));
+    }
+    let snapshot = crate::services::resolve_stored_path(file.snapshot.as_deref()?);
+    Path::new(&snapshot).exists().then_some((snapshot, true))
+}
+
+// 文件路径的文本形式
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathTextFormat {
+    // 每行一个绝对路径
+    Lines,
+    // 每行一个，使用正斜杠
+    PosixLines,
+    // 按 shell 规则加引号，以空格连接
+    ShellQuoted,
+    // 每行一个 file:// URI
+    FileUri,
+    // 相对于指定目录
+    Relative(String),
+    // 仅文件名
+    NamesOnly,
+}
 
+impl PathTextFormat {
+    // 格式名：paths / paths_posix / paths_quoted / paths_uri / paths_relative / file_names
+    pub fn from_name(name: &str, base_dir: Option<String>) -> Option<Self> {
+        Some(match name {
+            "paths" => PathTextFormat::Lines,
+            "paths_posix" => PathTextFormat::PosixLines,
+            "paths_quoted" => PathTextFormat::ShellQuoted,
+            "paths_uri" => PathTextFormat::FileUri,
+            "paths_relative" => PathTextFormat::Relative(base_dir.unwrap_or_default()),
+            "file_names" => PathTextFormat::NamesOnly,
+            _ => return None,
+        })
+    }
+}
 
+// 以文本形式粘贴文件的原始路径，返回因不存在而跳过的文件名（快照路径是应用内部路径，不用于文本）
+pub fn paste_paths_as_text(ctx: &ClipboardContext, content: &str, format: &PathTextFormat) -> Result<Vec<String>, String> {
+    if let PathTextFormat::Relative(base) = format {
+        if base.trim().is_empty() {
+            return Err("未指定相对路径的基准目录".to_string());
+        }
+    }
 
+    let json_str = content.strip_prefix("files:").ok_or("无效的文件内容格式")?;
+    let file_data: FileClipboardData = serde_json::from_str(json_str)
+        .map_err(|e| format!("解析文件数据失败: {}", e))?;
 
+    let mut paths = Vec::with_capacity(file_data.files.len());
+    let mut skipped = Vec::new();
+    for file in &file_data.files {
+        let path = crate::services::resolve_stored_path(&file.path);
+        if Path::new(&path).exists() {
+            paths.push(path);
+        } else {
+            skipped.push(file.name.clone());
+        }
+    }
+    if paths.is_empty() {
+        return Err("所有文件都不存在".to_string());
+    }
+
+    let text = format_paths(&paths, format, cfg!(windows));
+    crate::services::clipboard::set_last_hash_text(&text);
+    super::text::paste_text(ctx, &text)?;
+    Ok(skipped)
+}
+
+fn format_paths(paths: &[String], format: &PathTextFormat, windows: bool) -> String {
+    match format {
+        PathTextFormat::Lines => paths.join("\n"),
+        PathTextFormat::PosixLines => paths.iter().map(|p| p.replace('\\', "/")).collect::<Vec<_>>().join("\n"),
+        PathTextFormat::ShellQuoted => paths.iter().map(|p| shell_quote(p, windows)).collect::<Vec<_>>().join(" "),
+        PathTextFormat::FileUri => paths.iter().map(|p| file_uri(p)).collect::<Vec<_>>().join("\n"),
+        PathTextFormat::Relative(base) => paths.iter().map(|p| relative_path(p, base, windows)).collect::<Vec<_>>().join("\n"),
+        PathTextFormat::NamesOnly => paths
+            .iter()
+            .map(|p| p.rsplit(['/', '\\']).find(|s| !s.is_empty()).unwrap_or(p).to_string())
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
+
+// Windows 使用双引号（仅在需要时），POSIX 使用单引号
+fn shell_quote(path: &str, windows: bool) -> String {
+    if windows {
+        let needs_quote = path.is_empty() || path.contains(|c: char| c.is_whitespace() || "&()[]{}^=;!'+,`~%".contains(c));
+        return if needs_quote { format!("\"{}\"", path) } else { path.to_string() };
+    }
+    let safe = !path.is_empty() && path.chars().all(|c| c.is_ascii_alphanumeric() || "/._-+:@%,".contains(c));
+    if safe {
+        path.to_string()
+    } else {
+        format!("'{}'", path.replace('\'', "'\\''"))
+    }
+}
+
+fn file_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/:".contains(&b) {
+            uri.push(b as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", b));
+        }
+    }
+    uri
+}
+
+// 计算相对路径；POSIX 绝对路径总有公共根目录，Windows 不同盘符时返回原路径
+fn relative_path(path: &str, base: &str, windows: bool) -> String {
+    let split = |p: &str| -> Vec<String> {
+        p.split(['/', '\\']).filter(|s| !s.is_empty() && *s != ".").map(|s| s.to_string()).collect()
+    };
+    let same = |a: &String, b: &String| if windows { a.eq_ignore_ascii_case(b) } else { a == b };
+    let (target, base_parts) = (split(path), split(base));
+
+    let common = target.iter().zip(&base_parts).take_while(|(a, b)| same(a, b)).count();
+    let share_root = !windows && path.starts_with('/') && base.starts_with('/');
+    if common == 0 && !share_root {
+        return path.to_string();
+    }
+
+    let separator = if windows { "\\" } else { "/" };
+    let mut parts: Vec<&str> = vec![".."; base_parts.len() - common];
+    parts.extend(target[common..].iter().map(|s| s.as_str()));
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join(separator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths() -> Vec<String> {
+        vec!["/home/me/docs/a b.txt".to_string(), "/home/me/src/it's.rs".to_string()]
+    }
+
+    #[test]
+    fn falls_back_to_existing_snapshot() {
+        let dir = std::env::temp_dir().join(format!("qc_paste_file_{}", fastrand::u32(..)));
+        std::fs::create_dir_all(&dir).unwrap();
+        let snapshot = dir.join("a.txt");
+        std::fs::write(&snapshot, "a").unwrap();
+        let snapshot = snapshot.to_string_lossy().to_string();
+        let missing = dir.join("missing.txt").to_string_lossy().to_string();
+
+        let file = |path: &str, snapshot: Option<&str>| FileInfo {
+            path: path.to_string(),
+            name: String::new(),
+            size: 0,
+            is_directory: false,
+            icon_data: None,
+            file_type: String::new(),
+            snapshot: snapshot.map(|s| s.to_string()),
+        };
+        assert_eq!(existing_file_path(&file(&snapshot, None)), Some((snapshot.clone(), false)));
+        assert_eq!(existing_file_path(&file(&missing, Some(&snapshot))), Some((snapshot.clone(), true)));
+        assert_eq!(existing_file_path(&file(&missing, Some(&missing))), None);
+        assert_eq!(existing_file_path(&file(&missing, None)), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn formats_posix_paths() {
+        let paths = paths();
+        assert_eq!(format_paths(&paths, &PathTextFormat::ShellQuoted, false), "'/home/me/docs/a b.txt' '/home/me/src/it'\\''s.rs'");
+        assert_eq!(format_paths(&paths, &PathTextFormat::FileUri, false), "file:///home/me/docs/a%20b.txt\nfile:///home/me/src/it%27s.rs");
+        assert_eq!(format_paths(&paths, &PathTextFormat::Relative("/home/me/src".to_string()), false), "../docs/a b.txt\nit's.rs");
+        assert_eq!(format_paths(&paths, &PathTextFormat::NamesOnly, false), "a b.txt\nit's.rs");
+        // 只有根目录相同
+        assert_eq!(format_paths(&paths[..1], &PathTextFormat::Relative("/var/tmp".to_string()), false), "../../home/me/docs/a b.txt");
+    }
+
+    #[test]
+    fn formats_windows_paths() {
+        let paths = vec![r"C:\Users\Me\My File.txt".to_string(), r"C:\Users\Me\x.txt".to_string()];
+        assert_eq!(format_paths(&paths, &PathTextFormat::ShellQuoted, true), r#""C:\Users\Me\My File.txt" C:\Users\Me\x.txt"#);
+        assert_eq!(format_paths(&paths[..1], &PathTextFormat::PosixLines, true), "C:/Users/Me/My File.txt");
+        assert_eq!(format_paths(&paths[..1], &PathTextFormat::FileUri, true), "file:///C:/Users/Me/My%20File.txt");
+        assert_eq!(format_paths(&paths, &PathTextFormat::Relative(r"c:\users\me\docs".to_string()), true), "..\\My File.txt\n..\\x.txt");
+        assert_eq!(format_paths(&paths[..1], &PathTextFormat::Relative(r"D:\x".to_string()), true), r"C:\Users\Me\My File.txt");
+    }
+}
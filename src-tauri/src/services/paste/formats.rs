@@ -0,0 +1,64 @@
+use clipboard_rs::{Clipboard, ClipboardContext, ClipboardContent, common::{RustImage, RustImageData}};
+use crate::services::database::ClipboardFormat;
+use crate::services::database::formats::{MIME_TEXT, MIME_HTML, MIME_RTF, MIME_IMAGE, MIME_FILES};
+use crate::services::resolve_stored_path;
+use super::text::{default_sanitize_level, generate_cf_html};
+use crate::utils::html_sanitize::{sanitize_html, SanitizeLevel};
+use std::path::Path;
+
+fn load_image(stored_path: &str) -> Result<RustImageData, String> {
+    let path = resolve_stored_path(stored_path);
+    RustImageData::from_path(&path).map_err(|e| format!("读取图片失败 [{}]: {}", path, e))
+}
+
+// 同时写入剪贴板项的全部格式（HTML 按级别清理）
+pub fn paste_all_formats(ctx: &ClipboardContext, formats: &[ClipboardFormat], level: SanitizeLevel) -> Result<(), String> {
+    let mut contents = Vec::new();
+    
+    for format in formats {
+        match format.mime_type.as_str() {
+            MIME_TEXT => contents.push(ClipboardContent::Text(format.data.clone())),
+            MIME_HTML => contents.push(ClipboardContent::Html(generate_cf_html(&sanitize_html(&format.data, level)))),
+            MIME_RTF if level == SanitizeLevel::Original => contents.push(ClipboardContent::Rtf(format.data.clone())),
+            MIME_IMAGE => match load_image(&format.data) {
+                Ok(image) => contents.push(ClipboardContent::Image(image)),
+                Err(e) => eprintln!("{}", e),
+            },
+            _ => {}
+        }
+    }
+    
+    if contents.is_empty() {
+        return Err("没有可粘贴的格式".to_string());
+    }
+    
+    ctx.set(contents)
+        .map_err(|e| format!("设置剪贴板内容失败: {}", e))
+}
+
+// 只写入指定格式（HTML 按默认级别清理）
+pub fn paste_single_format(ctx: &ClipboardContext, format: &ClipboardFormat) -> Result<(), String> {
+    let result = match format.mime_type.as_str() {
+        MIME_TEXT => ctx.set_text(format.data.clone()),
+        MIME_HTML => {
+            let html = sanitize_html(&format.data, default_sanitize_level());
+            ctx.set(vec![ClipboardContent::Html(generate_cf_html(&html))])
+        }
+        MIME_RTF => ctx.set(vec![ClipboardContent::Rtf(format.data.clone())]),
+        MIME_IMAGE => ctx.set_image(load_image(&format.data)?),
+        MIME_FILES => {
+            let paths: Vec<String> = format.data
+                .lines()
+                .map(resolve_stored_path)
+                .filter(|p| Path::new(p).exists())
+                .collect();
+            if paths.is_empty() {
+                return Err("所有文件都不存在".to_string());
+            }
+            ctx.set_files(paths)
+        }
+        other => return Err(format!("不支持的格式: {}", other)),
+    };
+    
+    result.map_err(|e| format!("设置剪贴板内容失败: {}", e))
+}
@@ -0,0 +1,221 @@
+// 图片的其它粘贴形式：Data URI、路径文本、Markdown 图片链接、位图，以及重新编码（可缩放）后的文件
+use clipboard_rs::{common::RustImageData, Clipboard, ClipboardContext};
+use image::DynamicImage;
+use std::path::{Path, PathBuf};
+
+// 未指定时的 JPEG 质量
+const DEFAULT_QUALITY: u8 = 85;
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp", "ico", "svg"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageEncoding {
+    Png,
+    Jpeg,
+    // image 库的 WebP 编码器只支持无损，忽略质量参数（只有 JPEG 使用质量）
+    Webp,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImagePasteFormat {
+    // data:image/...;base64,... 文本
+    DataUri,
+    // 绝对路径文本
+    FilePath,
+    // ![名称](路径)
+    MarkdownLink,
+    // 以位图写入剪贴板（而不是文件）
+    Bitmap,
+    // 重新编码为文件后粘贴，max_dimension 限制最长边
+    Reencode {
+        encoding: ImageEncoding,
+        quality: u8,
+        max_dimension: Option<u32>,
+    },
+}
+
+impl ImagePasteFormat {
+    // 格式名：image_data_uri / image_path / image_markdown / image_bitmap / image_png / image_jpeg / image_webp
+    // quality 只对 image_jpeg 生效，PNG 和 WebP 均为无损编码
+    pub fn from_name(name: &str, quality: Option<u8>, max_dimension: Option<u32>) -> Option<Self> {
+        let reencode = |encoding| ImagePasteFormat::Reencode {
+            encoding,
+            quality: quality.unwrap_or(DEFAULT_QUALITY).clamp(1, 100),
+            max_dimension: max_dimension.filter(|&d| d > 0),
+        };
+        Some(match name {
+            "image_data_uri" => ImagePasteFormat::DataUri,
+            "image_path" => ImagePasteFormat::FilePath,
+            "image_markdown" => ImagePasteFormat::MarkdownLink,
+            "image_bitmap" => ImagePasteFormat::Bitmap,
+            "image_png" => reencode(ImageEncoding::Png),
+            "image_jpeg" => reencode(ImageEncoding::Jpeg),
+            "image_webp" => reencode(ImageEncoding::Webp),
+            _ => return None,
+        })
+    }
+}
+
+// 按指定形式写入图片项（content 为 files:{json}）
+pub fn paste_image_as(ctx: &ClipboardContext, content: &str, format: &ImagePasteFormat) -> Result<(), String> {
+    let (stored_path, path) = image_file_path(content)?;
+
+    let text = match format {
+        ImagePasteFormat::DataUri => image_data_uri(&path)?,
+        ImagePasteFormat::FilePath => path.clone(),
+        ImagePasteFormat::MarkdownLink => markdown_image_link(&path),
+        ImagePasteFormat::Bitmap => {
+            crate::services::clipboard::set_last_hash_file(&crate::services::normalize_path_for_hash(&stored_path));
+            let image = RustImageData::from_path(&path).map_err(|e| format!("读取图片失败 [{}]: {}", path, e))?;
+            return ctx.set_image(image).map_err(|e| format!("设置图片到剪贴板失败: {}", e));
+        }
+        ImagePasteFormat::Reencode { encoding, quality, max_dimension } => {
+            let output = reencode_image(&path, *encoding, *quality, *max_dimension)?;
+            crate::services::clipboard::set_last_hash_paths(std::slice::from_ref(&output));
+            return ctx
+                .set_files(vec![output])
+                .map_err(|e| format!("设置文件到剪贴板失败: {}", e));
+        }
+    };
+
+    crate::services::clipboard::set_last_hash_text(&text);
+    super::text::paste_text(ctx, &text)
+}
+
+// 返回 (存储路径, 实际路径)
+fn image_file_path(content: &str) -> Result<(String, String), String> {
+    let json_str = content.strip_prefix("files:").ok_or("无效的图片内容格式")?;
+    let data: serde_json::Value = serde_json::from_str(json_str)
+        .map_err(|e| format!("解析文件数据失败: {}", e))?;
+    let stored_path = data["files"]
+        .as_array()
+        .and_then(|files| files.first())
+        .and_then(|f| f["path"].as_str())
+        .ok_or("图片数据中没有文件")?
+        .to_string();
+
+    let path = crate::services::resolve_stored_path(&stored_path);
+    let ext = Path::new(&path).extension().and_then(|e| e.to_str()).unwrap_or("").to_ascii_lowercase();
+    if !IMAGE_EXTENSIONS.contains(&ext.as_str()) {
+        return Err(format!("不是图片文件: {}", path));
+    }
+    if !Path::new(&path).exists() {
+        return Err(format!("图片文件不存在: {}", path));
+    }
+    Ok((stored_path, path))
+}
+
+fn image_data_uri(path: &str) -> Result<String, String> {
+    use base64::{Engine as _, engine::general_purpose};
+
+    let data = std::fs::read(path).map_err(|e| format!("读取图片失败 [{}]: {}", path, e))?;
+    Ok(format!("data:{};base64,{}", image_mime_type(path), general_purpose::STANDARD.encode(&data)))
+}
+
+fn image_mime_type(path: &str) -> &'static str {
+    let ext = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    match ext.as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        "svg" => "image/svg+xml",
+        _ => "image/png",
+    }
+}
+
+fn markdown_image_link(path: &str) -> String {
+    let name = Path::new(path).file_stem().and_then(|s| s.to_str()).unwrap_or("image");
+    let url = path.replace('\\', "/");
+    if url.contains([' ', '(', ')']) {
+        format!("![{}](<{}>)", name, url)
+    } else {
+        format!("![{}]({})", name, url)
+    }
+}
+
+// 重新编码到临时目录，返回新文件路径（同一源文件、相同参数复用已生成的文件）
+fn reencode_image(path: &str, encoding: ImageEncoding, quality: u8, max_dimension: Option<u32>) -> Result<String, String> {
+    use image::codecs::{jpeg::JpegEncoder, webp::WebPEncoder};
+    use image::imageops::FilterType;
+
+    let stem = Path::new(path).file_stem().and_then(|s| s.to_str()).unwrap_or("image");
+    let (ext, suffix) = match encoding {
+        ImageEncoding::Png => ("png", String::new()),
+        ImageEncoding::Jpeg => ("jpg", format!("_q{}", quality)),
+        ImageEncoding::Webp => ("webp", String::new()),
+    };
+    let size_suffix = max_dimension.map(|d| format!("_{}px", d)).unwrap_or_default();
+    let output_dir = converted_images_dir()?.join(source_cache_key(path)?);
+    std::fs::create_dir_all(&output_dir).map_err(|e| format!("创建目录失败: {}", e))?;
+    let output = output_dir.join(format!("{}{}{}.{}", stem, suffix, size_suffix, ext));
+    if output.exists() {
+        return Ok(output.to_string_lossy().to_string());
+    }
+
+    let mut img = image::open(path).map_err(|e| format!("图片解码失败: {}", e))?;
+    if let Some(max) = max_dimension {
+        if img.width().max(img.height()) > max {
+            img = img.resize(max, max, FilterType::Lanczos3);
+        }
+    }
+
+    // 先写临时文件再改名，避免复用写了一半的文件
+    let temp_output = output.with_extension(format!("{}.tmp", ext));
+    let file = std::fs::File::create(&temp_output).map_err(|e| format!("创建图片文件失败: {}", e))?;
+    let mut writer = std::io::BufWriter::new(file);
+    let result = match encoding {
+        ImageEncoding::Png => img.write_to(&mut writer, image::ImageFormat::Png),
+        ImageEncoding::Jpeg => DynamicImage::ImageRgb8(flatten_on_white(&img))
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut writer, quality)),
+        ImageEncoding::Webp => DynamicImage::ImageRgba8(img.to_rgba8())
+            .write_with_encoder(WebPEncoder::new_lossless(&mut writer)),
+    };
+    let flushed = writer.into_inner().map(drop).map_err(|e| format!("保存图片失败: {}", e.error()));
+    let result = result
+        .map_err(|e| format!("图片编码失败: {}", e))
+        .and(flushed)
+        .and_then(|_| std::fs::rename(&temp_output, &output).map_err(|e| format!("保存图片失败: {}", e)));
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&temp_output);
+        return Err(e);
+    }
+
+    Ok(output.to_string_lossy().to_string())
+}
+
+// 源文件的缓存键：路径、修改时间和大小的哈希，文件被替换或修改后重新生成
+fn source_cache_key(path: &str) -> Result<String, String> {
+    use sha2::{Digest, Sha256};
+
+    let metadata = std::fs::metadata(path).map_err(|e| format!("读取图片信息失败 [{}]: {}", path, e))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let hash = Sha256::digest(format!("{}|{}|{}", path, modified, metadata.len()));
+    Ok(format!("{:x}", hash)[..16].to_string())
+}
+
+// JPEG 不支持透明度，透明区域按白色背景合成
+fn flatten_on_white(img: &DynamicImage) -> image::RgbImage {
+    let rgba = img.to_rgba8();
+    image::RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |c: u8| ((c as u16 * a as u16 + 255 * (255 - a as u16)) / 255) as u8;
+        image::Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+fn converted_images_dir() -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join("quickclipboard_converted_images");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("创建目录失败: {}", e))?;
+    Ok(dir)
+}
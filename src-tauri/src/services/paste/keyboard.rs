@@ -1,4 +1,5 @@
 use enigo::{Enigo, Direction, Key, Keyboard, Settings};
+use std::sync::atomic::{AtomicBool, Ordering};
 use crate::services::system::input_monitor::get_modifier_keys_state;
 
 #[cfg(target_os = "windows")]
@@ -207,3 +208,124 @@ pub fn simulate_paste() -> Result<(), String> {
 
     Ok(())
 }
+
+// 按指定按键方式模拟粘贴（粘贴配置使用），未知方式时使用全局设置
+pub fn simulate_paste_with_mode(mode: &str) -> Result<(), String> {
+    match mode {
+        "ctrl_shift_v" => simulate_paste_ctrl_shift_v(),
+        #[cfg(target_os = "windows")]
+        "ctrl_v" => simulate_paste_ctrl_v(),
+        #[cfg(target_os = "windows")]
+        "shift_insert" => simulate_paste_shift_insert(),
+        _ => simulate_paste(),
+    }
+}
+
+// Ctrl+Shift+V 粘贴（终端等应用），macOS 上为 Cmd+Shift+V
+fn simulate_paste_ctrl_shift_v() -> Result<(), String> {
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("创建键盘模拟器失败: {}", e))?;
+
+    release_modifier_keys()?;
+
+    let modifier = if cfg!(target_os = "macos") { Key::Meta } else { Key::Control };
+    enigo.key(modifier, Direction::Press)
+        .map_err(|e| format!("按下Ctrl失败: {}", e))?;
+    enigo.key(Key::Shift, Direction::Press)
+        .map_err(|e| format!("按下Shift失败: {}", e))?;
+    let result = enigo.key(Key::Unicode('v'), Direction::Click);
+    enigo.key(Key::Shift, Direction::Release)
+        .map_err(|e| format!("释放Shift失败: {}", e))?;
+    enigo.key(modifier, Direction::Release)
+        .map_err(|e| format!("释放Ctrl失败: {}", e))?;
+
+    result.map_err(|e| format!("按下V失败: {}", e))
+}
+
+// 模拟按左方向键，将光标向左移动指定字符数
+pub fn move_caret_left(count: usize) -> Result<(), String> {
+    if count == 0 {
+        return Ok(());
+    }
+
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("创建键盘模拟器失败: {}", e))?;
+
+    for _ in 0..count {
+        enigo.key(Key::LeftArrow, Direction::Click)
+            .map_err(|e| format!("按下左方向键失败: {}", e))?;
+        std::thread::sleep(std::time::Duration::from_millis(2));
+    }
+
+    Ok(())
+}
+
+// 键入粘贴取消标志
+static TYPING_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+// 取消正在进行的键入粘贴
+pub fn cancel_typing() {
+    TYPING_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+// 以模拟键入的方式输入文本（用于禁止粘贴的应用），被取消时返回 false
+pub fn type_text(text: &str) -> Result<bool, String> {
+    use crate::services::system::hotkey;
+
+    let settings = crate::get_settings();
+    TYPING_CANCELLED.store(false, Ordering::SeqCst);
+
+    // 键入期间临时注册取消快捷键
+    if !settings.type_paste_cancel_shortcut.is_empty() {
+        if let Err(e) = hotkey::register_typing_cancel_hotkey(&settings.type_paste_cancel_shortcut) {
+            eprintln!("注册键入取消快捷键失败: {}", e);
+        }
+    }
+
+    let result = release_modifier_keys().and_then(|_| {
+        type_text_with(text, settings.type_paste_speed, &settings.type_paste_newline_mode)
+    });
+    hotkey::unregister_shortcut("typing_cancel");
+    result
+}
+
+fn type_text_with(text: &str, chars_per_second: u32, newline_mode: &str) -> Result<bool, String> {
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("创建键盘模拟器失败: {}", e))?;
+    let interval = std::time::Duration::from_millis(1000 / chars_per_second.max(1) as u64);
+
+    for c in text.chars() {
+        if TYPING_CANCELLED.load(Ordering::SeqCst) {
+            return Ok(false);
+        }
+
+        match c {
+            '\r' => continue,
+            '\n' if newline_mode == "shift_enter" => {
+                enigo.key(Key::Shift, Direction::Press)
+                    .map_err(|e| format!("按下Shift失败: {}", e))?;
+                let result = enigo.key(Key::Return, Direction::Click);
+                enigo.key(Key::Shift, Direction::Release)
+                    .map_err(|e| format!("释放Shift失败: {}", e))?;
+                result.map_err(|e| format!("按下Enter失败: {}", e))?;
+            }
+            '\n' => {
+                enigo.key(Key::Return, Direction::Click)
+                    .map_err(|e| format!("按下Enter失败: {}", e))?;
+            }
+            '\t' => {
+                enigo.key(Key::Tab, Direction::Click)
+                    .map_err(|e| format!("按下Tab失败: {}", e))?;
+            }
+            _ => {
+                // enigo 会为非 ASCII 字符使用 Unicode 输入
+                enigo.text(c.encode_utf8(&mut [0u8; 4]))
+                    .map_err(|e| format!("输入字符失败: {}", e))?;
+            }
+        }
+
+        std::thread::sleep(interval);
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,50 @@
+// 剪贴板项的 Markdown 转换（富文本 → Markdown，Markdown → 富文本）
+use crate::services::database::ClipboardItem;
+use crate::utils::markdown::{html_to_markdown, markdown_to_html};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertedContent {
+    pub text: String,
+    pub html: Option<String>,
+    // 另存为新条目时的 ID
+    pub item_id: Option<i64>,
+}
+
+// 富文本项转换为 Markdown，没有 HTML 的项返回原文本
+pub fn item_to_markdown(item: &ClipboardItem) -> String {
+    match item.html_content.as_deref().filter(|html| !html.trim().is_empty()) {
+        Some(html) => html_to_markdown(html, &stored_image_path),
+        None => item.content.clone(),
+    }
+}
+
+// 将项的文本作为 Markdown 渲染为 HTML
+pub fn item_markdown_to_html(item: &ClipboardItem) -> String {
+    markdown_to_html(&item.content)
+}
+
+// 按目标格式转换：markdown（纯文本）或 html（原 Markdown 文本 + 渲染后的 HTML）
+pub fn convert_item(item: &ClipboardItem, target: &str) -> Result<ConvertedContent, String> {
+    let primary_type = item.content_type.split(',').next().unwrap_or("");
+    if !matches!(primary_type, "text" | "link" | "rich_text") {
+        return Err("只能对文本内容进行 Markdown 转换".to_string());
+    }
+
+    match target {
+        "markdown" => Ok(ConvertedContent { text: item_to_markdown(item), html: None, item_id: None }),
+        "html" => Ok(ConvertedContent {
+            text: item.content.clone(),
+            html: Some(item_markdown_to_html(item)),
+            item_id: None,
+        }),
+        _ => Err(format!("不支持的转换目标: {}", target)),
+    }
+}
+
+// data-image-id 对应的已保存图片路径，文件不存在时保留原 src
+fn stored_image_path(image_id: &str) -> Option<String> {
+    let path = crate::services::resolve_stored_path(&format!("clipboard_images/{}.png", image_id));
+    std::path::Path::new(&path).exists().then(|| path.replace('\\', "/"))
+}
@@ -0,0 +1,211 @@
+// 多项合并：按选择顺序拼接文本/HTML，或合并为一次多文件粘贴
+use crate::services::database::{get_clipboard_item_by_id, get_favorite_by_id, ClipboardItem};
+use clipboard_rs::ClipboardContext;
+use serde::Deserialize;
+
+// 合并项引用（剪贴板项或收藏项）
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeItemRef {
+    #[serde(default)]
+    pub clipboard_id: Option<i64>,
+    #[serde(default)]
+    pub favorite_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeOptions {
+    // newline / comma / tab / space / custom
+    #[serde(default)]
+    pub separator: Option<String>,
+    #[serde(default)]
+    pub custom_separator: Option<String>,
+    // 每项的模板，支持 {index} 和 {text}，如 "{index}. {text}"
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+// 单个文本片段
+pub struct MergePart {
+    pub text: String,
+    pub html: Option<String>,
+}
+
+// 合并结果
+pub enum MergedContent {
+    Text { text: String, html: Option<String> },
+    // files:{json} 格式
+    Files(String),
+}
+
+impl MergeOptions {
+    fn separator(&self) -> String {
+        match self.separator.as_deref().unwrap_or("newline") {
+            "comma" => ",".to_string(),
+            "tab" => "\t".to_string(),
+            "space" => " ".to_string(),
+            "custom" => self.custom_separator.clone().unwrap_or_default(),
+            _ => "\n".to_string(),
+        }
+    }
+}
+
+// 拼接文本片段；任一片段带 HTML 且未使用模板时同时生成 HTML
+pub fn join_parts(parts: &[MergePart], options: &MergeOptions) -> (String, Option<String>) {
+    let separator = options.separator();
+    let texts: Vec<String> = parts
+        .iter()
+        .enumerate()
+        .map(|(i, part)| match &options.template {
+            Some(template) => template
+                .replace("{index}", &(i + 1).to_string())
+                .replace("{text}", &part.text),
+            None => part.text.clone(),
+        })
+        .collect();
+    let text = texts.join(&separator);
+
+    if options.template.is_some() || parts.iter().all(|p| p.html.is_none()) {
+        return (text, None);
+    }
+
+    let html_separator = if separator == "\n" { "<br>".to_string() } else { text_to_html(&separator) };
+    let html = parts
+        .iter()
+        .map(|p| p.html.clone().unwrap_or_else(|| text_to_html(&p.text)))
+        .collect::<Vec<_>>()
+        .join(&html_separator);
+    (text, Some(html))
+}
+
+fn text_to_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('\n', "<br>")
+}
+
+fn load_item(item_ref: &MergeItemRef) -> Result<ClipboardItem, String> {
+    if let Some(id) = item_ref.clipboard_id {
+        get_clipboard_item_by_id(id)?.ok_or_else(|| format!("剪贴板项不存在: {}", id))
+    } else if let Some(id) = &item_ref.favorite_id {
+        Ok(get_favorite_by_id(id)?
+            .ok_or_else(|| format!("收藏项不存在: {}", id))?
+            .into_clipboard_item())
+    } else {
+        Err("必须 clipboard_id 或 favorite_id".to_string())
+    }
+}
+
+// 加载并合并所选项（文本与文件不能混合）
+pub fn merge_items(refs: &[MergeItemRef], options: &MergeOptions) -> Result<MergedContent, String> {
+    if refs.is_empty() {
+        return Err("未选择要合并的项".to_string());
+    }
+
+    let mut parts = Vec::new();
+    let mut files = Vec::new();
+    for item_ref in refs {
+        let item = load_item(item_ref)?;
+        let primary_type = item.content_type.split(',').next().unwrap_or("");
+        match primary_type {
+            "text" | "link" | "rich_text" => parts.push(MergePart {
+                text: item.content,
+                html: item.html_content,
+            }),
+            "image" | "file" => {
+                let content = if item.content.starts_with("files:") {
+                    item.content
+                } else {
+                    super::paste_handler::convert_legacy_image_format(&item)?
+                };
+                let data: serde_json::Value = serde_json::from_str(content.strip_prefix("files:").unwrap_or(""))
+                    .map_err(|e| format!("解析文件数据失败: {}", e))?;
+                files.extend(data["files"].as_array().cloned().unwrap_or_default());
+            }
+            _ => return Err(format!("不支持合并的内容类型: {}", item.content_type)),
+        }
+    }
+
+    if !parts.is_empty() && !files.is_empty() {
+        return Err("不能同时合并文本和文件".to_string());
+    }
+
+    if !files.is_empty() {
+        let data = serde_json::json!({ "files": files, "operation": "copy" });
+        return Ok(MergedContent::Files(format!("files:{}", data)));
+    }
+
+    let (text, html) = join_parts(&parts, options);
+    Ok(MergedContent::Text { text, html })
+}
+
+// 粘贴合并结果
+pub fn paste_merged(merged: &MergedContent) -> Result<(), String> {
+    crate::services::clipboard::prepare_clipboard_restore();
+    let ctx = ClipboardContext::new()
+        .map_err(|e| format!("创建剪贴板上下文失败: {}", e))?;
+
+    match merged {
+        MergedContent::Text { text, html } => {
+            crate::services::clipboard::set_last_hash_text(text);
+            super::text::paste_rich_text(&ctx, text, html, &None)?;
+        }
+        MergedContent::Files(content) => {
+            crate::services::clipboard::set_last_hash_files(content);
+            super::file::paste_files(&ctx, content)?;
+        }
+    }
+
+    super::paste_handler::trigger_paste(super::profile::resolve_paste_profile().as_ref())
+}
+
+// 将合并结果保存为新的剪贴板项
+pub fn save_merged(merged: MergedContent) -> Result<i64, String> {
+    match merged {
+        MergedContent::Text { text, html } => {
+            let content_type = if html.is_some() { "rich_text" } else { "text" };
+            crate::services::clipboard::store_composed_item(text, html, content_type)
+        }
+        MergedContent::Files(content) => crate::services::clipboard::store_composed_item(content, None, "file"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(separator: &str, custom: Option<&str>, template: Option<&str>) -> MergeOptions {
+        MergeOptions {
+            separator: Some(separator.to_string()),
+            custom_separator: custom.map(|s| s.to_string()),
+            template: template.map(|s| s.to_string()),
+        }
+    }
+
+    fn parts() -> Vec<MergePart> {
+        vec![
+            MergePart { text: "a<1>".to_string(), html: None },
+            MergePart { text: "b".to_string(), html: Some("<b>b</b>".to_string()) },
+        ]
+    }
+
+    #[test]
+    fn joins_text_and_html() {
+        let (text, html) = join_parts(&parts(), &options("newline", None, None));
+        assert_eq!(text, "a<1>\nb");
+        assert_eq!(html.as_deref(), Some("a&lt;1&gt;<br><b>b</b>"));
+
+        let (text, html) = join_parts(&parts()[..1], &options("custom", Some(" | "), None));
+        assert_eq!((text.as_str(), html), ("a<1>", None));
+        assert_eq!(join_parts(&parts(), &options("tab", None, None)).0, "a<1>\tb");
+    }
+
+    #[test]
+    fn applies_item_template() {
+        let (text, html) = join_parts(&parts(), &options("comma", None, Some("{index}. {text}")));
+        assert_eq!(text, "1. a<1>,2. b");
+        assert_eq!(html, None);
+    }
+}
@@ -1,9 +1,18 @@
 pub mod paste_handler;
 mod text;
-mod file;
+pub mod file;
+mod formats;
+pub mod transform;
+pub mod template;
+pub mod queue;
+pub mod merge;
+pub mod profile;
+pub mod markdown;
+pub mod image;
 pub mod keyboard;
 
 pub use text::PasteFormat;
+pub(crate) use text::generate_cf_html;
 
 
 
@@ -1,8 +1,13 @@
 use clipboard_rs::ClipboardContext;
 use crate::services::database::ClipboardItem;
-use super::text::{paste_text, paste_rich_text, paste_rich_text_with_format, PasteFormat};
+use super::text::{default_sanitize_level, paste_text, paste_rich_text, paste_rich_text_with_format, PasteFormat};
 use super::file::paste_files;
-use super::keyboard::simulate_paste;
+use super::formats::{paste_all_formats, paste_single_format};
+use super::transform::apply_transforms;
+use crate::services::database::formats::MIME_IMAGE;
+use super::keyboard::{simulate_paste, simulate_paste_with_mode, type_text};
+use super::profile::{profile_paste_format, resolve_paste_profile};
+use crate::services::settings::PasteProfile;
 use chrono;
 
 fn emit_paste_count_updated(id: i64) {
@@ -21,16 +26,21 @@ fn emit_favorite_paste_count_updated(id: &str) {
 
 // 直接粘贴文本
 pub fn paste_text_direct(text: &str) -> Result<(), String> {
+    let profile = resolve_paste_profile();
+    if profile.as_ref().is_some_and(|p| p.shortcut_mode == "type") {
+        type_text(text)?;
+        return Ok(());
+    }
+    
     crate::services::clipboard::set_last_hash_text(text);
     
+    crate::services::clipboard::prepare_clipboard_restore();
     let ctx = ClipboardContext::new()
         .map_err(|e| format!("创建剪贴板上下文失败: {}", e))?;
     
     paste_text(&ctx, text)?;
     
-    std::thread::sleep(std::time::Duration::from_millis(50));
-    simulate_paste()?;
-    std::thread::sleep(std::time::Duration::from_millis(100));
+    trigger_paste(profile.as_ref())?;
     
     Ok(())
 }
@@ -47,15 +57,14 @@ pub fn paste_image_file(file_path: &str) -> Result<(), String> {
     
     crate::services::clipboard::set_last_hash_file(file_path);
     
+    crate::services::clipboard::prepare_clipboard_restore();
     let ctx = ClipboardContext::new()
         .map_err(|e| format!("创建剪贴板上下文失败: {}", e))?;
     
     ctx.set_files(vec![file_path.to_string()])
         .map_err(|e| format!("设置剪贴板失败: {}", e))?;
     
-    std::thread::sleep(std::time::Duration::from_millis(50));
-    simulate_paste()?;
-    std::thread::sleep(std::time::Duration::from_millis(100));
+    trigger_paste(resolve_paste_profile().as_ref())?;
     
     Ok(())
 }
@@ -113,6 +122,25 @@ fn paste_item_internal(
     format: Option<PasteFormat>
 ) -> Result<(), String> {
     let primary_type = item.content_type.split(',').next().unwrap_or(&item.content_type);
+    let is_text = matches!(primary_type, "text" | "link" | "rich_text");
+    
+    // 按目标应用配置确定文本的粘贴格式
+    let profile = resolve_paste_profile();
+    let format = match (format, &profile) {
+        (None, Some(profile)) if is_text => profile_paste_format(profile)?,
+        (format, _) => format,
+    };
+    
+    // 键入粘贴不经过剪贴板，无需预设哈希
+    if format == Some(PasteFormat::Typed) || (format.is_none() && is_text && should_type_for_item(item)) {
+        if !is_text {
+            return Err("只能以键入方式输入文本内容".to_string());
+        }
+        if !type_text(&item.content)? {
+            println!("键入粘贴已取消");
+        }
+        return Ok(());
+    }
     
     // 检查并转换旧格式图片
     let content = if primary_type == "image" && !item.content.starts_with("files:") {
@@ -121,8 +149,8 @@ fn paste_item_internal(
         // 更新数据库并刷新时间戳
         if let Some(id) = clipboard_id {
             update_item_content(Some(id), None, &new_content)?;
-        } else if let Some(id) = favorite_id {
-            update_item_content(None, Some(&id), &new_content)?;
+        } else if let Some(ref id) = favorite_id {
+            update_item_content(None, Some(id), &new_content)?;
         }
         
         new_content
@@ -140,31 +168,127 @@ fn paste_item_internal(
         _ => {}
     }
     
+    // 同一次复制保存的全部格式（剪贴板历史项，或从历史添加的收藏项）
+    let formats = match (clipboard_id, favorite_id.as_deref()) {
+        (Some(id), _) => crate::services::database::get_clipboard_formats(id).ok(),
+        (None, Some(id)) => crate::services::database::get_favorite_formats(id).ok(),
+        _ => None,
+    }
+    .unwrap_or_default();
+    
     // 设置剪贴板
+    crate::services::clipboard::prepare_clipboard_restore();
     let ctx = ClipboardContext::new()
         .map_err(|e| format!("创建剪贴板上下文失败: {}", e))?;
     
-    match primary_type {
-        "text" | "link" | "rich_text" => {
-            if let Some(paste_format) = format {
-                paste_rich_text_with_format(&ctx, &item.content, &item.html_content, paste_format)?
+    match (format, primary_type) {
+        (Some(PasteFormat::Mime(mime)), _) => {
+            let selected = formats.iter()
+                .find(|f| f.mime_type == mime)
+                .ok_or_else(|| format!("该条目不包含格式: {}", mime))?;
+            if mime == MIME_IMAGE {
+                crate::services::clipboard::set_last_hash_file(&crate::services::normalize_path_for_hash(&selected.data));
+            }
+            paste_single_format(&ctx, selected)?
+        },
+        (Some(PasteFormat::Transformed(transforms)), "text" | "link" | "rich_text") => {
+            let text = apply_transforms(&item.content, &transforms)?;
+            crate::services::clipboard::set_last_hash_text(&text);
+            paste_text(&ctx, &text)?
+        },
+        (Some(PasteFormat::Transformed(_)), _) => return Err("只能对文本内容应用转换".to_string()),
+        (Some(PasteFormat::Markdown), "text" | "link" | "rich_text") => {
+            let text = super::markdown::item_to_markdown(item);
+            crate::services::clipboard::set_last_hash_text(&text);
+            paste_text(&ctx, &text)?
+        },
+        (Some(PasteFormat::MarkdownAsHtml), "text" | "link" | "rich_text") => {
+            let html = Some(super::markdown::item_markdown_to_html(item));
+            paste_rich_text_with_format(&ctx, &item.content, &html, &None, PasteFormat::WithFormat)?
+        },
+        (Some(PasteFormat::Markdown | PasteFormat::MarkdownAsHtml), _) => return Err("只能对文本内容进行 Markdown 转换".to_string()),
+        (Some(PasteFormat::Image(image_format)), "image" | "file") => {
+            super::image::paste_image_as(&ctx, &content, &image_format)?
+        },
+        (Some(PasteFormat::Image(_)), _) => return Err("只能对图片内容使用图片粘贴格式".to_string()),
+        (Some(PasteFormat::FilePaths(path_format)), "image" | "file") => {
+            let skipped = super::file::paste_paths_as_text(&ctx, &content, &path_format)?;
+            notify_skipped_files(&skipped);
+        },
+        (Some(PasteFormat::FilePaths(_)), _) => return Err("只能对文件内容使用路径粘贴格式".to_string()),
+        (format, "text" | "link" | "rich_text") => {
+            let with_format = match &format {
+                Some(f) => matches!(f, PasteFormat::WithFormat | PasteFormat::Sanitized(_)),
+                None => crate::services::get_settings().paste_with_format,
+            };
+            
+            if with_format && formats.iter().any(|f| f.mime_type == MIME_IMAGE) {
+                let level = match &format {
+                    Some(PasteFormat::Sanitized(level)) => *level,
+                    _ => default_sanitize_level(),
+                };
+                paste_all_formats(&ctx, &formats, level)?
+            } else if let Some(paste_format) = format {
+                paste_rich_text_with_format(&ctx, &item.content, &item.html_content, &item.rtf_content, paste_format)?
             } else {
-                paste_rich_text(&ctx, &item.content, &item.html_content)?
+                paste_rich_text(&ctx, &item.content, &item.html_content, &item.rtf_content)?
             }
         },
-        "image" | "file" => paste_files(&ctx, &content)?,
+        (_, "image" | "file") => paste_files(&ctx, &content)?,
         _ => return Err(format!("不支持的内容类型: {}", item.content_type)),
     }
     
-    std::thread::sleep(std::time::Duration::from_millis(50));
-    simulate_paste()?;
-    std::thread::sleep(std::time::Duration::from_millis(100));
+    trigger_paste(profile.as_ref())?;
+    
+    Ok(())
+}
+
+// 提示以路径文本粘贴时因不存在而跳过的文件
+fn notify_skipped_files(names: &[String]) {
+    if names.is_empty() {
+        return;
+    }
+    if let Some(app) = crate::services::clipboard::get_app_handle() {
+        let body = format!("以下文件不存在，已跳过：{}", names.join("、"));
+        let _ = crate::services::notification::show_notification(&app, "部分文件未粘贴", &body);
+    }
+}
+
+// 按配置的按键方式和延迟模拟粘贴，并安排恢复剪贴板
+pub(super) fn trigger_paste(profile: Option<&PasteProfile>) -> Result<(), String> {
+    let (before, after) = profile
+        .map(|p| (p.pre_paste_delay_ms, p.post_paste_delay_ms))
+        .unwrap_or((50, 100));
+    
+    std::thread::sleep(std::time::Duration::from_millis(before as u64));
+    let result = match profile.map(|p| p.shortcut_mode.as_str()).filter(|m| !m.is_empty()) {
+        Some(mode) => simulate_paste_with_mode(mode),
+        None => simulate_paste(),
+    };
+    crate::services::clipboard::schedule_clipboard_restore();
+    result?;
+    std::thread::sleep(std::time::Duration::from_millis(after as u64));
     
     Ok(())
 }
 
+// 按来源应用或目标应用规则判断是否使用键入粘贴
+fn should_type_for_item(item: &ClipboardItem) -> bool {
+    use crate::services::system::app_filter::{app_matches_rules, source_from_process_name};
+
+    let settings = crate::services::get_settings();
+    if !settings.type_paste_source_apps.is_empty() {
+        let source = source_from_process_name(item.source_app.as_deref().unwrap_or(""));
+        if app_matches_rules(&source, &settings.type_paste_source_apps) {
+            return true;
+        }
+    }
+    !settings.type_paste_target_apps.is_empty()
+        && app_matches_rules(&crate::services::system::get_paste_target_app(), &settings.type_paste_target_apps)
+}
+
 // 转换旧格式图片为新格式（files:{json}）
-fn convert_legacy_image_format(item: &ClipboardItem) -> Result<String, String> {
+pub(super) fn convert_legacy_image_format(item: &ClipboardItem) -> Result<String, String> {
     use crate::services::get_data_directory;
     
     let image_id = item.image_id.as_deref()
@@ -0,0 +1,35 @@
+// 按粘贴目标应用解析粘贴配置（终端、聊天软件、文档编辑器等使用不同的粘贴方式）
+use super::{transform, PasteFormat};
+use crate::services::settings::PasteProfile;
+use crate::services::system::app_filter::app_matches_rules;
+
+// 获取匹配当前目标应用的第一个配置
+pub fn resolve_paste_profile() -> Option<PasteProfile> {
+    let settings = crate::services::get_settings();
+    if settings.paste_profiles.is_empty() {
+        return None;
+    }
+
+    let target = crate::services::system::get_paste_target_app();
+    settings
+        .paste_profiles
+        .into_iter()
+        .find(|p| app_matches_rules(&target, &p.apps))
+}
+
+// 配置对应的文本粘贴格式（调用方未指定格式时使用）
+pub fn profile_paste_format(profile: &PasteProfile) -> Result<Option<PasteFormat>, String> {
+    if profile.shortcut_mode == "type" {
+        return Ok(Some(PasteFormat::Typed));
+    }
+    if !profile.transform_chain.is_empty() {
+        let transforms = transform::resolve_transform_chain(&profile.transform_chain)?;
+        return Ok(Some(PasteFormat::Transformed(transforms)));
+    }
+    Ok(match profile.format.as_str() {
+        "plain" => Some(PasteFormat::PlainText),
+        "html" => Some(PasteFormat::WithFormat),
+        "markdown" => Some(PasteFormat::Markdown),
+        _ => None,
+    })
+}
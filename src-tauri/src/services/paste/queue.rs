@@ -0,0 +1,256 @@
+// 粘贴队列：依次复制多项内容后按 FIFO/LIFO 顺序逐条粘贴
+use crate::services::database::{get_clipboard_item_by_id, ClipboardItem};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueueOrder {
+    #[default]
+    Fifo,
+    Lifo,
+}
+
+impl QueueOrder {
+    pub fn from_name(name: &str) -> Self {
+        if name.eq_ignore_ascii_case("lifo") {
+            QueueOrder::Lifo
+        } else {
+            QueueOrder::Fifo
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueEntry {
+    pub item_id: i64,
+    pub preview: String,
+    // 文本项入队时保存内容，历史记录被清理后仍可粘贴
+    #[serde(skip)]
+    pub text: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasteQueueStatus {
+    pub active: bool,
+    pub order: QueueOrder,
+    pub auto_stop: bool,
+    pub length: usize,
+    // 按粘贴顺序排列
+    pub entries: Vec<QueueEntry>,
+}
+
+#[derive(Debug, Default)]
+struct PasteQueue {
+    active: bool,
+    order: QueueOrder,
+    auto_stop: bool,
+    entries: VecDeque<QueueEntry>,
+}
+
+impl PasteQueue {
+    fn pop_next(&mut self) -> Option<QueueEntry> {
+        let entry = match self.order {
+            QueueOrder::Fifo => self.entries.pop_front(),
+            QueueOrder::Lifo => self.entries.pop_back(),
+        };
+        if self.auto_stop && self.entries.is_empty() {
+            self.active = false;
+        }
+        entry
+    }
+
+    // 粘贴失败时放回原位置
+    fn restore(&mut self, entry: QueueEntry) {
+        match self.order {
+            QueueOrder::Fifo => self.entries.push_front(entry),
+            QueueOrder::Lifo => self.entries.push_back(entry),
+        }
+        self.active = true;
+    }
+
+    fn status(&self) -> PasteQueueStatus {
+        let entries: Vec<QueueEntry> = match self.order {
+            QueueOrder::Fifo => self.entries.iter().cloned().collect(),
+            QueueOrder::Lifo => self.entries.iter().rev().cloned().collect(),
+        };
+        PasteQueueStatus {
+            active: self.active,
+            order: self.order,
+            auto_stop: self.auto_stop,
+            length: entries.len(),
+            entries,
+        }
+    }
+}
+
+static PASTE_QUEUE: Lazy<Mutex<PasteQueue>> = Lazy::new(|| Mutex::new(PasteQueue::default()));
+
+// 开启队列模式（未指定顺序时使用设置）
+pub fn start(order: Option<QueueOrder>) {
+    let settings = crate::services::get_settings();
+    {
+        let mut queue = PASTE_QUEUE.lock();
+        queue.active = true;
+        queue.order = order.unwrap_or_else(|| QueueOrder::from_name(&settings.paste_queue_order));
+        queue.auto_stop = settings.paste_queue_auto_stop;
+    }
+    notify_changed();
+}
+
+// 关闭队列模式并清空队列
+pub fn stop() {
+    {
+        let mut queue = PASTE_QUEUE.lock();
+        queue.active = false;
+        queue.entries.clear();
+    }
+    notify_changed();
+}
+
+// 切换队列模式（托盘菜单）
+pub fn toggle() {
+    if is_active() {
+        stop();
+    } else {
+        start(None);
+    }
+}
+
+pub fn clear() {
+    PASTE_QUEUE.lock().entries.clear();
+    notify_changed();
+}
+
+pub fn is_active() -> bool {
+    PASTE_QUEUE.lock().active
+}
+
+pub fn status() -> PasteQueueStatus {
+    PASTE_QUEUE.lock().status()
+}
+
+// 从历史记录中显式加入队列（未开启时自动开启）
+pub fn enqueue_items(ids: &[i64]) -> Result<usize, String> {
+    let mut entries = Vec::with_capacity(ids.len());
+    for &id in ids {
+        let item = get_clipboard_item_by_id(id)?
+            .ok_or_else(|| format!("剪贴板项不存在: {}", id))?;
+        entries.push(entry_from_item(&item));
+    }
+
+    if !is_active() {
+        start(None);
+    }
+    PASTE_QUEUE.lock().entries.extend(entries);
+    notify_changed();
+    Ok(ids.len())
+}
+
+// 队列模式下复制新内容时自动入队
+pub fn on_item_captured(item_id: i64) {
+    if !is_active() || !crate::services::get_settings().paste_queue_capture_on_copy {
+        return;
+    }
+    if let Ok(Some(item)) = get_clipboard_item_by_id(item_id) {
+        PASTE_QUEUE.lock().entries.push_back(entry_from_item(&item));
+        notify_changed();
+    }
+}
+
+// 粘贴下一项并前进，队列为空时返回 false
+pub fn paste_next() -> Result<bool, String> {
+    let entry = {
+        let mut queue = PASTE_QUEUE.lock();
+        if !queue.active {
+            return Ok(false);
+        }
+        queue.pop_next()
+    };
+    let Some(entry) = entry else {
+        return Ok(false);
+    };
+
+    // 粘贴函数会预设 LAST_CONTENT_HASHES，避免粘贴内容被重新捕获入队
+    let result = match get_clipboard_item_by_id(entry.item_id)? {
+        Some(item) => super::paste_handler::paste_clipboard_item_with_update(&item),
+        None => match &entry.text {
+            Some(text) => super::paste_handler::paste_text_direct(text),
+            None => Err(format!("剪贴板项不存在: {}", entry.item_id)),
+        },
+    };
+
+    if result.is_err() {
+        PASTE_QUEUE.lock().restore(entry);
+    }
+    notify_changed();
+    result.map(|_| true)
+}
+
+fn entry_from_item(item: &ClipboardItem) -> QueueEntry {
+    let primary_type = item.content_type.split(',').next().unwrap_or("");
+    let text = matches!(primary_type, "text" | "link" | "rich_text").then(|| item.content.clone());
+    QueueEntry {
+        item_id: item.id,
+        preview: crate::windows::tray::native_menu::format_item_label(item),
+        text,
+    }
+}
+
+// 通知前端并更新托盘提示
+fn notify_changed() {
+    use tauri::Emitter;
+
+    let status = status();
+    let Some(app) = crate::services::clipboard::get_app_handle() else {
+        return;
+    };
+
+    let tooltip = if status.active {
+        format!("快速剪贴板 - 粘贴队列: {} 项 ({})", status.length, if status.order == QueueOrder::Lifo { "LIFO" } else { "FIFO" })
+    } else {
+        "快速剪贴板".to_string()
+    };
+    if let Some(tray) = app.tray_by_id(&tauri::tray::TrayIconId::new("main-tray")) {
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+    if crate::services::low_memory::is_low_memory_mode() {
+        let _ = crate::windows::tray::native_menu::update_native_menu(&app);
+    }
+
+    let _ = app.emit("paste-queue-changed", status);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(order: QueueOrder, ids: &[i64]) -> PasteQueue {
+        PasteQueue {
+            active: true,
+            order,
+            auto_stop: true,
+            entries: ids.iter().map(|&id| QueueEntry { item_id: id, preview: String::new(), text: None }).collect(),
+        }
+    }
+
+    #[test]
+    fn pops_in_order_and_auto_stops() {
+        let mut fifo = queue(QueueOrder::Fifo, &[1, 2]);
+        assert_eq!(fifo.status().entries.iter().map(|e| e.item_id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(fifo.pop_next().unwrap().item_id, 1);
+        assert!(fifo.active);
+        assert_eq!(fifo.pop_next().unwrap().item_id, 2);
+        assert!(!fifo.active);
+
+        let mut lifo = queue(QueueOrder::Lifo, &[1, 2]);
+        assert_eq!(lifo.status().entries[0].item_id, 2);
+        let entry = lifo.pop_next().unwrap();
+        lifo.restore(entry);
+        assert_eq!(lifo.pop_next().unwrap().item_id, 2);
+    }
+}
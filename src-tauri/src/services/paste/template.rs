@@ -0,0 +1,326 @@
+// 收藏片段模板：粘贴前展开 {date} {time} {clipboard} {uuid} {random:n} {input:提示} {cursor} 占位符
+//
+// 只有已知名称的占位符会被识别，其它花括号内容（JSON、代码等）原样保留；
+// 在占位符前加反斜杠（如 \{date}）可输出占位符原文。
+use chrono::format::{Item, StrftimeItems};
+use std::collections::HashMap;
+
+const PLACEHOLDER_NAMES: &[&str] = &["date", "time", "clipboard", "uuid", "random", "input", "cursor"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Placeholder {
+    Date(String),
+    Time(String),
+    Clipboard,
+    Uuid,
+    Random(u64),
+    Input(String),
+    Cursor,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+// 展开结果，cursor_offset 为光标位置距文本末尾的字符数
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpandedTemplate {
+    pub text: String,
+    pub cursor_offset: Option<usize>,
+}
+
+// 占位符取值来源（便于测试时替换）
+pub trait TemplateContext {
+    fn now(&self) -> chrono::DateTime<chrono::Local>;
+    fn clipboard_text(&self) -> Result<String, String>;
+    fn uuid(&self) -> String;
+    fn random(&self, max: u64) -> u64;
+    fn input(&self, prompt: &str) -> Result<String, String>;
+}
+
+// 解析模板
+pub fn parse_template(text: &str) -> Result<Vec<Segment>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut cursor_seen = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let escaped = chars[i] == '\\' && chars.get(i + 1) == Some(&'{');
+        let start = if escaped { i + 1 } else { i };
+
+        if chars[start] != '{' {
+            literal.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        let Some((name, arg, end)) = scan_placeholder(&chars, start)? else {
+            literal.push(chars[i]);
+            i += 1;
+            continue;
+        };
+
+        if escaped {
+            literal.extend(&chars[start..end]);
+        } else {
+            let placeholder = build_placeholder(&name, arg, start)?;
+            if placeholder == Placeholder::Cursor {
+                if cursor_seen {
+                    return Err(format!("模板中只能有一个 {{cursor}} (位置 {})", start));
+                }
+                cursor_seen = true;
+            }
+            if !literal.is_empty() {
+                segments.push(Segment::Literal(std::mem::take(&mut literal)));
+            }
+            segments.push(Segment::Placeholder(placeholder));
+        }
+        i = end;
+    }
+
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+// 解析需要展开的模板：包含占位符或转义时返回片段，纯文本返回 None；解析失败返回错误
+pub fn parse_if_template(text: &str) -> Result<Option<Vec<Segment>>, String> {
+    let segments = parse_template(text)?;
+    let has_placeholder = segments.iter().any(|s| matches!(s, Segment::Placeholder(_)));
+    // 只有转义（\{date}）时展开结果与原文不同，同样需要展开
+    let literal: String = segments
+        .iter()
+        .filter_map(|s| match s {
+            Segment::Literal(l) => Some(l.as_str()),
+            Segment::Placeholder(_) => None,
+        })
+        .collect();
+    Ok((has_placeholder || literal != text).then_some(segments))
+}
+
+// 模板中需要用户输入的提示（去重，保持出现顺序）
+pub fn input_prompts(segments: &[Segment]) -> Vec<String> {
+    let mut prompts: Vec<String> = Vec::new();
+    for segment in segments {
+        if let Segment::Placeholder(Placeholder::Input(prompt)) = segment {
+            if !prompts.contains(prompt) {
+                prompts.push(prompt.clone());
+            }
+        }
+    }
+    prompts
+}
+
+// 展开模板
+pub fn expand_template(segments: &[Segment], ctx: &impl TemplateContext) -> Result<ExpandedTemplate, String> {
+    let now = ctx.now();
+    let mut text = String::new();
+    let mut cursor_at = None;
+
+    for segment in segments {
+        match segment {
+            Segment::Literal(s) => text.push_str(s),
+            Segment::Placeholder(p) => match p {
+                Placeholder::Date(fmt) | Placeholder::Time(fmt) => text.push_str(&now.format(fmt).to_string()),
+                Placeholder::Clipboard => text.push_str(&ctx.clipboard_text()?),
+                Placeholder::Uuid => text.push_str(&ctx.uuid()),
+                Placeholder::Random(max) => text.push_str(&ctx.random(*max).to_string()),
+                Placeholder::Input(prompt) => text.push_str(&ctx.input(prompt)?),
+                Placeholder::Cursor => cursor_at = Some(text.len()),
+            },
+        }
+    }
+
+    // 换行（含 \r\n）按一次方向键计算
+    let cursor_offset = cursor_at.map(|at| text[at..].replace("\r\n", "\n").chars().count());
+    Ok(ExpandedTemplate { text, cursor_offset })
+}
+
+// 从 start 处的 '{' 开始识别占位符，返回 (名称, 参数, 结束位置)；不是占位符时返回 None
+fn scan_placeholder(chars: &[char], start: usize) -> Result<Option<(String, Option<String>, usize)>, String> {
+    let mut i = start + 1;
+    while i < chars.len() && chars[i].is_ascii_lowercase() {
+        i += 1;
+    }
+    let name: String = chars[start + 1..i].iter().collect();
+    if !PLACEHOLDER_NAMES.contains(&name.as_str()) {
+        return Ok(None);
+    }
+
+    match chars.get(i) {
+        Some('}') => Ok(Some((name, None, i + 1))),
+        Some(':') => {
+            let arg_start = i + 1;
+            let mut j = arg_start;
+            while j < chars.len() && chars[j] != '}' && chars[j] != '\n' {
+                j += 1;
+            }
+            if chars.get(j) != Some(&'}') {
+                return Err(format!("占位符 {{{}:...}} 未闭合 (位置 {})", name, start));
+            }
+            Ok(Some((name, Some(chars[arg_start..j].iter().collect()), j + 1)))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn build_placeholder(name: &str, arg: Option<String>, pos: usize) -> Result<Placeholder, String> {
+    let no_arg = |p: Placeholder| match &arg {
+        Some(_) => Err(format!("占位符 {{{}}} 不接受参数 (位置 {})", name, pos)),
+        None => Ok(p),
+    };
+
+    match name {
+        "date" => Ok(Placeholder::Date(check_time_format(arg.unwrap_or_else(|| "%Y-%m-%d".to_string()), pos)?)),
+        "time" => Ok(Placeholder::Time(check_time_format(arg.unwrap_or_else(|| "%H:%M:%S".to_string()), pos)?)),
+        "clipboard" => no_arg(Placeholder::Clipboard),
+        "uuid" => no_arg(Placeholder::Uuid),
+        "cursor" => no_arg(Placeholder::Cursor),
+        "random" => {
+            let arg = arg.ok_or_else(|| format!("占位符 {{random:n}} 缺少上限 (位置 {})", pos))?;
+            match arg.trim().parse::<u64>() {
+                Ok(max) if max > 0 => Ok(Placeholder::Random(max)),
+                _ => Err(format!("占位符 {{random:n}} 的上限必须是正整数: {} (位置 {})", arg, pos)),
+            }
+        }
+        "input" => {
+            let prompt = arg.map(|a| a.trim().to_string()).filter(|a| !a.is_empty());
+            Ok(Placeholder::Input(prompt.unwrap_or_else(|| "请输入内容".to_string())))
+        }
+        _ => Err(format!("未知的占位符: {} (位置 {})", name, pos)),
+    }
+}
+
+fn check_time_format(fmt: String, pos: usize) -> Result<String, String> {
+    if StrftimeItems::new(&fmt).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("无效的日期时间格式: {} (位置 {})", fmt, pos));
+    }
+    Ok(fmt)
+}
+
+// 运行时上下文：输入值由调用方预先通过对话框收集
+pub struct RuntimeContext {
+    pub inputs: HashMap<String, String>,
+}
+
+impl TemplateContext for RuntimeContext {
+    fn now(&self) -> chrono::DateTime<chrono::Local> {
+        chrono::Local::now()
+    }
+
+    fn clipboard_text(&self) -> Result<String, String> {
+        use clipboard_rs::{Clipboard, ClipboardContext};
+        let ctx = ClipboardContext::new().map_err(|e| format!("创建剪贴板上下文失败: {}", e))?;
+        Ok(ctx.get_text().unwrap_or_default())
+    }
+
+    fn uuid(&self) -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
+
+    fn random(&self, max: u64) -> u64 {
+        fastrand::u64(1..=max)
+    }
+
+    fn input(&self, prompt: &str) -> Result<String, String> {
+        self.inputs
+            .get(prompt)
+            .cloned()
+            .ok_or_else(|| format!("缺少输入: {}", prompt))
+    }
+}
+
+// 依次弹出输入对话框收集 {input} 的值，用户取消时中止粘贴
+pub async fn collect_template_inputs(app: &tauri::AppHandle, prompts: &[String]) -> Result<HashMap<String, String>, String> {
+    use crate::windows::plugins::input_dialog::window::{show_dialog, InputDialogOptions, InputType};
+
+    let mut inputs = HashMap::new();
+    for prompt in prompts {
+        let options = InputDialogOptions {
+            title: "填写片段".to_string(),
+            message: prompt.clone(),
+            placeholder: None,
+            default_value: None,
+            input_type: InputType::Text,
+            min_value: None,
+            max_value: None,
+        };
+        let value = show_dialog(app.clone(), options)
+            .await?
+            .ok_or_else(|| "已取消输入，粘贴已中止".to_string())?;
+        inputs.insert(prompt.clone(), value);
+    }
+    Ok(inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    struct MockContext;
+
+    impl TemplateContext for MockContext {
+        fn now(&self) -> chrono::DateTime<chrono::Local> {
+            chrono::Local.with_ymd_and_hms(2024, 3, 5, 9, 7, 1).unwrap()
+        }
+        fn clipboard_text(&self) -> Result<String, String> {
+            Ok("CLIP".to_string())
+        }
+        fn uuid(&self) -> String {
+            "UUID".to_string()
+        }
+        fn random(&self, max: u64) -> u64 {
+            max
+        }
+        fn input(&self, prompt: &str) -> Result<String, String> {
+            Ok(format!("<{}>", prompt))
+        }
+    }
+
+    fn expand(text: &str) -> ExpandedTemplate {
+        expand_template(&parse_template(text).unwrap(), &MockContext).unwrap()
+    }
+
+    #[test]
+    fn expands_placeholders() {
+        let result = expand("{date} {time} {date:%d/%m} [{clipboard}] {uuid} {random:6} {input:Name}{input}");
+        assert_eq!(result.text, "2024-03-05 09:07:01 05/03 [CLIP] UUID 6 <Name><请输入内容>");
+        assert_eq!(result.cursor_offset, None);
+    }
+
+    #[test]
+    fn tracks_cursor_from_end() {
+        let result = expand("<b>{cursor}</b>\r\n");
+        assert_eq!(result.text, "<b></b>\r\n");
+        assert_eq!(result.cursor_offset, Some(5));
+    }
+
+    #[test]
+    fn keeps_unknown_braces_and_escapes() {
+        let plain = r#"{"a": {b}} fn x() { y } {name:v} \{x}"#;
+        assert_eq!(parse_if_template(plain), Ok(None));
+        let text = format!(r#"{} \{{date}}"#, plain);
+        let segments = parse_if_template(&text).unwrap().expect("转义需要展开");
+        let expanded = expand_template(&segments, &MockContext).unwrap();
+        assert_eq!(expanded.text, r#"{"a": {b}} fn x() { y } {name:v} \{x} {date}"#);
+        assert_eq!(input_prompts(&parse_template("{input:A}{input:B}{input:A}").unwrap()), vec!["A", "B"]);
+    }
+
+    #[test]
+    fn reports_errors_with_position() {
+        let err = |text: &str| parse_template(text).unwrap_err();
+        assert!(err("ab{date:%Y").contains("未闭合 (位置 2)"));
+        assert!(err("{random:x}").contains("正整数"));
+        assert!(err("{random}").contains("缺少上限"));
+        assert!(err("x{uuid:1}").contains("不接受参数 (位置 1)"));
+        assert!(err("{date:%Q}").contains("无效的日期时间格式"));
+        assert!(err("{cursor}{cursor}").contains("只能有一个"));
+        assert!(parse_if_template("{date:%Y").is_err());
+    }
+}
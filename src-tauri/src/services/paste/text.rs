@@ -1,9 +1,29 @@
 use clipboard_rs::{Clipboard, ClipboardContext, ClipboardContent};
+use super::file::PathTextFormat;
+use super::image::ImagePasteFormat;
+use super::transform::TextTransform;
+use crate::utils::html_sanitize::{sanitize_html, SanitizeLevel};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum PasteFormat {
     PlainText,
     WithFormat,
+    // 带格式粘贴并指定 HTML 清理级别（覆盖设置）
+    Sanitized(SanitizeLevel),
+    // 仅粘贴指定 MIME 类型的格式
+    Mime(String),
+    // 经过转换管道后以纯文本粘贴
+    Transformed(Vec<TextTransform>),
+    // 模拟键入输入（用于禁止粘贴的应用）
+    Typed,
+    // HTML 转为 Markdown 后以纯文本粘贴
+    Markdown,
+    // 将文本作为 Markdown 渲染为 HTML 后以富文本粘贴
+    MarkdownAsHtml,
+    // 图片以其它形式粘贴（Data URI、路径、位图、重新编码等）
+    Image(ImagePasteFormat),
+    // 文件项以路径文本粘贴
+    FilePaths(PathTextFormat),
 }
 
 // 粘贴纯文本
@@ -12,7 +32,7 @@ pub fn paste_text(ctx: &ClipboardContext, text: &str) -> Result<(), String> {
         .map_err(|e| format!("粘贴文本失败: {}", e))
 }
 
-fn generate_cf_html(html: &str) -> String {
+pub(crate) fn generate_cf_html(html: &str) -> String {
     let html_content = if !html.contains("<html") {
         format!(
             "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n</head>\n<body>\n<!--StartFragment-->{}\n<!--EndFragment-->\n</body>\n</html>",
@@ -42,29 +62,46 @@ fn generate_cf_html(html: &str) -> String {
     )
 }
 
-// 粘贴富文本（HTML）
-pub fn paste_rich_text(
+// 组装富文本剪贴板内容（纯文本 + HTML + RTF）
+fn set_rich_contents(
     ctx: &ClipboardContext,
     text: &str,
     html_content: &Option<String>,
+    rtf_content: &Option<String>,
+    level: SanitizeLevel,
 ) -> Result<(), String> {
-    let settings = crate::services::get_settings();
+    // RTF 无法按级别清理，清理样式时只提供 HTML
+    let rtf_content = if level == SanitizeLevel::Original { rtf_content } else { &None };
+    if html_content.is_none() && rtf_content.is_none() {
+        return paste_text(ctx, text);
+    }
     
+    let mut contents = vec![ClipboardContent::Text(text.to_string())];
     if let Some(html) = html_content {
-        if !settings.paste_with_format {
-            return paste_text(ctx, text);
-        }
-        
-        let cf_html = generate_cf_html(html);
-        
-        ctx.set(vec![
-            ClipboardContent::Text(text.to_string()),
-            ClipboardContent::Html(cf_html),
-        ])
+        contents.push(ClipboardContent::Html(generate_cf_html(&sanitize_html(html, level))));
+    }
+    if let Some(rtf) = rtf_content {
+        contents.push(ClipboardContent::Rtf(rtf.clone()));
+    }
+    
+    ctx.set(contents)
         .map_err(|e| format!("设置剪贴板内容失败: {}", e))
-    } else {
-        paste_text(ctx, text)
+}
+
+// 粘贴富文本（HTML / RTF）
+pub fn paste_rich_text(
+    ctx: &ClipboardContext,
+    text: &str,
+    html_content: &Option<String>,
+    rtf_content: &Option<String>,
+) -> Result<(), String> {
+    let settings = crate::services::get_settings();
+    
+    if !settings.paste_with_format {
+        return paste_text(ctx, text);
     }
+    
+    set_rich_contents(ctx, text, html_content, rtf_content, SanitizeLevel::from_name(&settings.paste_html_sanitize_level))
 }
 
 // 粘贴富文本（指定格式）
@@ -72,22 +109,16 @@ pub fn paste_rich_text_with_format(
     ctx: &ClipboardContext,
     text: &str,
     html_content: &Option<String>,
+    rtf_content: &Option<String>,
     format: PasteFormat,
 ) -> Result<(), String> {
     match format {
-        PasteFormat::PlainText => paste_text(ctx, text),
-        PasteFormat::WithFormat => {
-            if let Some(html) = html_content {
-                let cf_html = generate_cf_html(html);
-                
-                ctx.set(vec![
-                    ClipboardContent::Text(text.to_string()),
-                    ClipboardContent::Html(cf_html),
-                ])
-                .map_err(|e| format!("设置剪贴板内容失败: {}", e))
-            } else {
-                paste_text(ctx, text)
-            }
-        }
+        PasteFormat::WithFormat => set_rich_contents(ctx, text, html_content, rtf_content, default_sanitize_level()),
+        PasteFormat::Sanitized(level) => set_rich_contents(ctx, text, html_content, rtf_content, level),
+        _ => paste_text(ctx, text),
     }
 }
+
+pub(super) fn default_sanitize_level() -> SanitizeLevel {
+    SanitizeLevel::from_name(&crate::services::get_settings().paste_html_sanitize_level)
+}
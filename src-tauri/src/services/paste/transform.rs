@@ -0,0 +1,313 @@
+// "粘贴为"文本转换管道：多个转换步骤按顺序组合
+use base64::{Engine as _, engine::general_purpose};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextTransform {
+    Upper,
+    Lower,
+    TitleCase,
+    CamelCase,
+    SnakeCase,
+    TrimLines,
+    RemoveEmptyLines,
+    SortLines,
+    DedupeLines,
+    JsonEscape,
+    JsonUnescape,
+    UrlEncode,
+    UrlDecode,
+    Base64Encode,
+    Base64Decode,
+    HtmlEncode,
+    WrapDoubleQuotes,
+    WrapSingleQuotes,
+}
+
+pub const ALL_TRANSFORMS: &[TextTransform] = &[
+    TextTransform::Upper,
+    TextTransform::Lower,
+    TextTransform::TitleCase,
+    TextTransform::CamelCase,
+    TextTransform::SnakeCase,
+    TextTransform::TrimLines,
+    TextTransform::RemoveEmptyLines,
+    TextTransform::SortLines,
+    TextTransform::DedupeLines,
+    TextTransform::JsonEscape,
+    TextTransform::JsonUnescape,
+    TextTransform::UrlEncode,
+    TextTransform::UrlDecode,
+    TextTransform::Base64Encode,
+    TextTransform::Base64Decode,
+    TextTransform::HtmlEncode,
+    TextTransform::WrapDoubleQuotes,
+    TextTransform::WrapSingleQuotes,
+];
+
+impl TextTransform {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextTransform::Upper => "upper",
+            TextTransform::Lower => "lower",
+            TextTransform::TitleCase => "title_case",
+            TextTransform::CamelCase => "camel_case",
+            TextTransform::SnakeCase => "snake_case",
+            TextTransform::TrimLines => "trim_lines",
+            TextTransform::RemoveEmptyLines => "remove_empty_lines",
+            TextTransform::SortLines => "sort_lines",
+            TextTransform::DedupeLines => "dedupe_lines",
+            TextTransform::JsonEscape => "json_escape",
+            TextTransform::JsonUnescape => "json_unescape",
+            TextTransform::UrlEncode => "url_encode",
+            TextTransform::UrlDecode => "url_decode",
+            TextTransform::Base64Encode => "base64_encode",
+            TextTransform::Base64Decode => "base64_decode",
+            TextTransform::HtmlEncode => "html_encode",
+            TextTransform::WrapDoubleQuotes => "wrap_double_quotes",
+            TextTransform::WrapSingleQuotes => "wrap_single_quotes",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        ALL_TRANSFORMS.iter().copied().find(|t| t.name() == name)
+    }
+
+    pub fn apply(&self, text: &str) -> Result<String, String> {
+        Ok(match self {
+            TextTransform::Upper => text.to_uppercase(),
+            TextTransform::Lower => text.to_lowercase(),
+            TextTransform::TitleCase => map_lines(text, title_case),
+            TextTransform::CamelCase => map_lines(text, camel_case),
+            TextTransform::SnakeCase => map_lines(text, snake_case),
+            TextTransform::TrimLines => map_lines(text, |l| l.trim().to_string()),
+            TextTransform::RemoveEmptyLines => {
+                text.lines().filter(|l| !l.trim().is_empty()).collect::<Vec<_>>().join("\n")
+            }
+            TextTransform::SortLines => {
+                let mut lines: Vec<&str> = text.lines().collect();
+                lines.sort();
+                lines.join("\n")
+            }
+            TextTransform::DedupeLines => {
+                let mut seen = std::collections::HashSet::new();
+                text.lines().filter(|l| seen.insert(*l)).collect::<Vec<_>>().join("\n")
+            }
+            TextTransform::JsonEscape => {
+                let quoted = serde_json::to_string(text).map_err(|e| format!("JSON转义失败: {}", e))?;
+                quoted[1..quoted.len() - 1].to_string()
+            }
+            TextTransform::JsonUnescape => {
+                let inner = text.trim();
+                let inner = inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')).unwrap_or(inner);
+                serde_json::from_str::<String>(&format!("\"{}\"", inner))
+                    .map_err(|e| format!("JSON反转义失败: {}", e))?
+            }
+            TextTransform::UrlEncode => url_encode(text),
+            TextTransform::UrlDecode => url_decode(text)?,
+            TextTransform::Base64Encode => general_purpose::STANDARD.encode(text.as_bytes()),
+            TextTransform::Base64Decode => {
+                let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+                let bytes = general_purpose::STANDARD
+                    .decode(compact)
+                    .map_err(|e| format!("Base64解码失败: {}", e))?;
+                String::from_utf8(bytes).map_err(|_| "Base64解码结果不是有效的UTF-8文本".to_string())?
+            }
+            TextTransform::HtmlEncode => html_encode(text),
+            TextTransform::WrapDoubleQuotes => format!("\"{}\"", text),
+            TextTransform::WrapSingleQuotes => format!("'{}'", text),
+        })
+    }
+}
+
+// 解析转换步骤名称
+pub fn parse_transform_steps(steps: &[String]) -> Result<Vec<TextTransform>, String> {
+    steps
+        .iter()
+        .map(|s| TextTransform::from_name(s).ok_or_else(|| format!("未知的转换步骤: {}", s)))
+        .collect()
+}
+
+// 根据ID获取设置中的转换链
+pub fn resolve_transform_chain(chain_id: &str) -> Result<Vec<TextTransform>, String> {
+    let settings = crate::services::get_settings();
+    let chain = settings.paste_transform_chains
+        .iter()
+        .find(|c| c.id == chain_id)
+        .ok_or_else(|| format!("转换链不存在: {}", chain_id))?;
+    parse_transform_steps(&chain.steps)
+}
+
+// 按顺序执行转换管道
+pub fn apply_transforms(text: &str, transforms: &[TextTransform]) -> Result<String, String> {
+    transforms.iter().try_fold(text.to_string(), |acc, t| t.apply(&acc))
+}
+
+fn map_lines(text: &str, f: impl Fn(&str) -> String) -> String {
+    text.lines().map(f).collect::<Vec<_>>().join("\n")
+}
+
+// 按分隔符和大小写边界拆分单词（parseHTTPRequest → parse / HTTP / Request）
+fn split_words(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(|c| c.to_lowercase())).collect(),
+        None => String::new(),
+    }
+}
+
+fn title_case(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut at_word_start = true;
+    for c in line.chars() {
+        if c.is_alphanumeric() {
+            if at_word_start {
+                result.extend(c.to_uppercase());
+            } else {
+                result.extend(c.to_lowercase());
+            }
+            at_word_start = false;
+        } else {
+            result.push(c);
+            at_word_start = c.is_whitespace() || c == '-' || c == '_';
+        }
+    }
+    result
+}
+
+fn camel_case(line: &str) -> String {
+    split_words(line)
+        .iter()
+        .enumerate()
+        .map(|(i, w)| if i == 0 { w.to_lowercase() } else { capitalize(w) })
+        .collect()
+}
+
+fn snake_case(line: &str) -> String {
+    split_words(line)
+        .iter()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+fn url_encode(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => result.push(byte as char),
+            _ => result.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    result
+}
+
+fn url_decode(text: &str) -> Result<String, String> {
+    let bytes = text.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(b) => {
+                        result.push(b);
+                        i += 3;
+                    }
+                    Err(_) => {
+                        result.push(b'%');
+                        i += 1;
+                    }
+                }
+            }
+            b'+' => {
+                result.push(b' ');
+                i += 1;
+            }
+            b => {
+                result.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(result).map_err(|_| "URL解码结果不是有效的UTF-8文本".to_string())
+}
+
+fn html_encode(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &str, steps: &[&str]) -> String {
+        let steps: Vec<String> = steps.iter().map(|s| s.to_string()).collect();
+        apply_transforms(text, &parse_transform_steps(&steps).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn converts_case() {
+        assert_eq!(run("hello world-foo", &["title_case"]), "Hello World-Foo");
+        assert_eq!(run("user_name ID", &["camel_case"]), "userNameId");
+        assert_eq!(run("parseHTTPRequest now", &["snake_case"]), "parse_http_request_now");
+    }
+
+    #[test]
+    fn chains_line_operations() {
+        assert_eq!(run("  b \n\n a\n b\n", &["trim_lines", "remove_empty_lines", "dedupe_lines", "sort_lines"]), "a\nb");
+    }
+
+    #[test]
+    fn round_trips_encodings() {
+        let text = "a b/ü\"<x>\n";
+        for (enc, dec) in [("json_escape", "json_unescape"), ("url_encode", "url_decode"), ("base64_encode", "base64_decode")] {
+            assert_eq!(run(text, &[enc, dec]), text, "{}", enc);
+        }
+        assert_eq!(run("<a href=\"x\">&</a>", &["html_encode"]), "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;");
+        assert_eq!(run("x", &["wrap_double_quotes"]), "\"x\"");
+    }
+
+    #[test]
+    fn rejects_unknown_step() {
+        assert!(parse_transform_steps(&["reverse".to_string()]).is_err());
+    }
+}
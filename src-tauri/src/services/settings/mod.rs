@@ -2,5 +2,5 @@ mod model;
 pub mod storage;
 mod state;
 
-pub use model::AppSettings;
+pub use model::{AppSettings, PasteProfile, PasteTransformChain};
 pub use state::{get_settings, update_settings, update_with, get_data_directory};
@@ -1,5 +1,49 @@
 use serde::{Deserialize, Serialize};
 
+// 命名的"粘贴为"转换链
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PasteTransformChain {
+    pub id: String,
+    pub name: String,
+    // 转换步骤名称（见 services::paste::transform）
+    pub steps: Vec<String>,
+    pub shortcut: String,
+}
+
+// 按目标应用匹配的粘贴配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PasteProfile {
+    pub id: String,
+    pub name: String,
+    // 应用匹配规则（与应用过滤相同的通配符规则）
+    pub apps: Vec<String>,
+    // ctrl_v / shift_insert / ctrl_shift_v / type，为空时使用全局设置
+    pub shortcut_mode: String,
+    // plain / html / markdown，为空时使用全局设置
+    pub format: String,
+    // 转换链ID
+    pub transform_chain: String,
+    pub pre_paste_delay_ms: u32,
+    pub post_paste_delay_ms: u32,
+}
+
+impl Default for PasteProfile {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            apps: vec![],
+            shortcut_mode: String::new(),
+            format: String::new(),
+            transform_chain: String::new(),
+            pre_paste_delay_ms: 50,
+            post_paste_delay_ms: 100,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct AppSettings {
@@ -22,6 +66,17 @@ pub struct AppSettings {
     pub ignore_duplicates: bool,
     pub save_images: bool,
     pub image_preview: bool,
+    pub fetch_remote_images: bool,
+    pub remote_image_max_bytes: u64,
+    pub remote_image_total_max_bytes: u64,
+    pub remote_image_concurrency: u32,
+    pub remote_image_timeout_secs: u32,
+    pub file_snapshot_enabled: bool,
+    pub file_snapshot_max_bytes: u64,
+    // 单次复制的文件快照总大小上限
+    pub file_snapshot_total_max_bytes: u64,
+    // 回收站保留天数（0 表示不自动清除）
+    pub trash_retention_days: u32,
 
     // 音效设置
     pub sound_enabled: bool,
@@ -84,6 +139,14 @@ pub struct AppSettings {
     pub app_filter_mode: String,
     pub app_filter_list: Vec<String>,
 
+    // 链接预览设置
+    pub link_preview_enabled: bool,
+    pub link_preview_timeout_secs: u32,
+    pub link_preview_max_page_bytes: u64,
+    pub link_preview_max_favicon_bytes: u64,
+    pub link_preview_domain_mode: String,
+    pub link_preview_domain_list: Vec<String>,
+
     // 窗口设置
     pub window_position_mode: String,
     pub remember_window_size: bool,
@@ -103,9 +166,14 @@ pub struct AppSettings {
 
     // 格式设置
     pub paste_with_format: bool,
+    // 富文本粘贴的 HTML 清理级别：structure / no_colors / original
+    pub paste_html_sanitize_level: String,
     pub paste_shortcut_mode: String,
     
     pub paste_to_top: bool,
+    pub restore_clipboard_after_paste: bool,
+    pub restore_clipboard_delay_ms: u32,
+    pub paste_profiles: Vec<PasteProfile>,
     pub show_badges: bool,
     pub show_source_icon: bool,
 
@@ -124,6 +192,20 @@ pub struct AppSettings {
     pub toggle_clipboard_monitor_shortcut: String,
     pub toggle_paste_with_format_shortcut: String,
     pub paste_plain_text_shortcut: String,
+    pub paste_transform_chains: Vec<PasteTransformChain>,
+    pub paste_queue_shortcut: String,
+
+    // 粘贴队列设置
+    pub paste_queue_order: String,
+    pub paste_queue_auto_stop: bool,
+    pub paste_queue_capture_on_copy: bool,
+
+    // 键入粘贴设置
+    pub type_paste_speed: u32,
+    pub type_paste_newline_mode: String,
+    pub type_paste_cancel_shortcut: String,
+    pub type_paste_target_apps: Vec<String>,
+    pub type_paste_source_apps: Vec<String>,
 
     // 数据存储设置
     #[serde(alias = "custom_storage_path")]
@@ -152,6 +234,15 @@ impl Default for AppSettings {
             ignore_duplicates: true,
             save_images: true,
             image_preview: false,
+            fetch_remote_images: false,
+            remote_image_max_bytes: 5 * 1024 * 1024,
+            remote_image_total_max_bytes: 20 * 1024 * 1024,
+            remote_image_concurrency: 2,
+            remote_image_timeout_secs: 10,
+            file_snapshot_enabled: false,
+            file_snapshot_max_bytes: 10 * 1024 * 1024,
+            file_snapshot_total_max_bytes: 50 * 1024 * 1024,
+            trash_retention_days: 30,
 
             sound_enabled: true,
             sound_volume: 50.0,
@@ -206,6 +297,13 @@ impl Default for AppSettings {
             app_filter_mode: "blacklist".to_string(),
             app_filter_list: vec![],
 
+            link_preview_enabled: false,
+            link_preview_timeout_secs: 5,
+            link_preview_max_page_bytes: 512 * 1024,
+            link_preview_max_favicon_bytes: 64 * 1024,
+            link_preview_domain_mode: "blacklist".to_string(),
+            link_preview_domain_list: vec![],
+
             window_position_mode: "smart".to_string(),
             remember_window_size: false,
             saved_window_position: None,
@@ -220,8 +318,12 @@ impl Default for AppSettings {
             title_bar_position: "top".to_string(),
 
             paste_with_format: true,
+            paste_html_sanitize_level: "original".to_string(),
             paste_shortcut_mode: "ctrl_v".to_string(),
             paste_to_top: false,
+            restore_clipboard_after_paste: false,
+            restore_clipboard_delay_ms: 500,
+            paste_profiles: vec![],
             show_badges: true,
             show_source_icon: true,
 
@@ -239,6 +341,18 @@ impl Default for AppSettings {
             toggle_clipboard_monitor_shortcut: if cfg!(target_os = "macos") { "Option+Shift+Z".to_string() } else { "Ctrl+Shift+Z".to_string() },
             toggle_paste_with_format_shortcut: if cfg!(target_os = "macos") { "Option+Shift+X".to_string() } else { "Ctrl+Shift+X".to_string() },
             paste_plain_text_shortcut: String::new(),
+            paste_transform_chains: vec![],
+            paste_queue_shortcut: String::new(),
+
+            paste_queue_order: "fifo".to_string(),
+            paste_queue_auto_stop: true,
+            paste_queue_capture_on_copy: true,
+
+            type_paste_speed: 50,
+            type_paste_newline_mode: "enter".to_string(),
+            type_paste_cancel_shortcut: "Escape".to_string(),
+            type_paste_target_apps: vec![],
+            type_paste_source_apps: vec![],
 
             custom_storage_path: None,
             use_custom_storage: false,
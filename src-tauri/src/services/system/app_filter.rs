@@ -137,6 +137,36 @@ mod windows_impl {
         get_clipboard_source_internal()
     }
 
+    // 获取粘贴目标应用（前台窗口为本程序时使用上一个焦点窗口）
+    pub fn get_paste_target_app() -> ClipboardSourceInfo {
+        use windows::Win32::Foundation::HWND;
+        use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+        unsafe {
+            let mut hwnd = GetForegroundWindow();
+            let mut process_id: u32 = 0;
+            GetWindowThreadProcessId(hwnd, Some(&mut process_id));
+            if process_id == std::process::id() {
+                if let Some(last) = crate::services::system::focus::get_last_focus_hwnd() {
+                    hwnd = HWND(last as *mut std::ffi::c_void);
+                }
+            }
+
+            if !hwnd.is_invalid() && hwnd.0 as usize != 0 {
+                if let Some(info) = get_process_info_from_hwnd(hwnd, ClipboardSourceType::ForegroundWindow) {
+                    return info;
+                }
+            }
+
+            ClipboardSourceInfo {
+                process_name: String::new(),
+                process_path: String::new(),
+                window_title: String::new(),
+                source_type: ClipboardSourceType::Unknown,
+            }
+        }
+    }
+
     // 获取剪贴板来源
     fn get_clipboard_source_internal() -> ClipboardSourceInfo {
         use windows::Win32::System::DataExchange::GetClipboardOwner;
@@ -370,6 +400,49 @@ pub fn get_clipboard_source() -> ClipboardSourceInfo {
     }
 }
 
+// 上次记录的非本程序前台应用
+#[cfg(not(target_os = "windows"))]
+static LAST_FOCUS_APP: parking_lot::Mutex<Option<ClipboardSourceInfo>> = parking_lot::Mutex::new(None);
+
+// 记录当前前台应用（前台为本程序时保留上次记录）
+#[cfg(not(target_os = "windows"))]
+pub fn remember_focus_app() {
+    let Ok(win) = get_active_window() else {
+        return;
+    };
+    if win.process_id == std::process::id() as u64 {
+        return;
+    }
+    let name = win.process_path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string();
+    if name.is_empty() {
+        return;
+    }
+    *LAST_FOCUS_APP.lock() = Some(ClipboardSourceInfo {
+        process_name: name,
+        process_path: win.process_path.to_string_lossy().to_string(),
+        window_title: win.title,
+        source_type: ClipboardSourceType::ForegroundWindow,
+    });
+}
+
+// 获取粘贴目标应用（前台窗口为本程序时使用上次记录的焦点应用）
+#[cfg(not(target_os = "windows"))]
+pub fn get_paste_target_app() -> ClipboardSourceInfo {
+    let current = get_clipboard_source();
+    let is_self = get_active_window()
+        .map(|win| win.process_id == std::process::id() as u64)
+        .unwrap_or(false);
+    if is_self || current.process_name.is_empty() {
+        if let Some(last) = LAST_FOCUS_APP.lock().clone() {
+            return last;
+        }
+    }
+    current
+}
+
 #[cfg(not(target_os = "windows"))]
 pub fn get_all_windows_info() -> Vec<AppInfo> {
     Vec::new()
@@ -379,12 +452,13 @@ pub fn get_all_windows_info() -> Vec<AppInfo> {
 pub use windows_impl::{
     get_all_windows_info,
     get_clipboard_source,
+    get_paste_target_app,
     start_clipboard_source_monitor,
     stop_clipboard_source_monitor,
 };
 
 // 通配符匹配（支持 * 和 ?）
-fn wildcard_match(pattern: &str, text: &str) -> bool {
+pub(crate) fn wildcard_match(pattern: &str, text: &str) -> bool {
     let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
     let text: Vec<char> = text.to_lowercase().chars().collect();
 
@@ -431,6 +505,22 @@ fn matches_filter_rule(source: &ClipboardSourceInfo, filter: &str) -> bool {
     }
 }
 
+// 检查应用是否匹配任一规则
+pub fn app_matches_rules(source: &ClipboardSourceInfo, rules: &[String]) -> bool {
+    source.source_type != ClipboardSourceType::Unknown
+        && rules.iter().any(|f| matches_filter_rule(source, f))
+}
+
+// 按进程名构造来源信息（用于匹配条目记录的来源应用）
+pub fn source_from_process_name(name: &str) -> ClipboardSourceInfo {
+    ClipboardSourceInfo {
+        process_name: name.to_string(),
+        process_path: String::new(),
+        window_title: String::new(),
+        source_type: if name.is_empty() { ClipboardSourceType::Unknown } else { ClipboardSourceType::ClipboardOwner },
+    }
+}
+
 // 检查当前应用是否允许记录剪贴板
 pub fn is_current_app_allowed(
     app_filter_enabled: bool,
@@ -57,6 +57,9 @@ pub fn focus_clipboard_window(window: WebviewWindow) -> Result<(), String> {
 
 // 仅保存当前焦点（手动）
 pub fn save_current_focus(_app_handle: tauri::AppHandle) -> Result<(), String> {
+    // Windows 由焦点钩子持续记录，其他平台在显示窗口前记录前台应用
+    #[cfg(not(windows))]
+    super::app_filter::remember_focus_app();
     Ok(())
 }
 
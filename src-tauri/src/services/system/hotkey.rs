@@ -316,8 +316,7 @@ fn handle_paste_plain_text_press(app: &AppHandle) -> Result<(), String> {
         let items = query_clipboard_items(QueryParams {
             offset: 0,
             limit: 1,
-            search: None,
-            content_type: None,
+            ..Default::default()
         })?
         .items;
 
@@ -331,6 +330,75 @@ fn handle_paste_plain_text_press(app: &AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+// 注册转换链快捷键
+pub fn register_transform_chain_hotkey(chain_id: &str, shortcut_str: &str) -> Result<(), String> {
+    let chain_id = chain_id.to_string();
+    register_shortcut(&format!("transform_chain_{}", chain_id), shortcut_str, move |app| {
+        let app = app.clone();
+        let chain_id = chain_id.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = handle_transform_chain_press(&app, &chain_id) {
+                eprintln!("转换粘贴失败: {}", e);
+            }
+        });
+    })
+}
+
+// 注册粘贴队列快捷键（粘贴下一项并前进）
+pub fn register_paste_queue_hotkey(shortcut_str: &str) -> Result<(), String> {
+    register_shortcut("paste_queue_next", shortcut_str, |_app| {
+        std::thread::spawn(|| {
+            match crate::services::paste::queue::paste_next() {
+                Ok(false) => println!("粘贴队列为空或未开启"),
+                Ok(true) => {}
+                Err(e) => eprintln!("粘贴队列粘贴失败: {}", e),
+            }
+        });
+    })
+}
+
+// 注册键入粘贴取消快捷键（仅在键入期间注册）
+pub fn register_typing_cancel_hotkey(shortcut_str: &str) -> Result<(), String> {
+    register_shortcut("typing_cancel", shortcut_str, |_app| {
+        crate::services::paste::keyboard::cancel_typing();
+    })
+}
+
+// 窗口可见时转换选中项，否则转换最新一项
+fn handle_transform_chain_press(app: &AppHandle, chain_id: &str) -> Result<(), String> {
+    use crate::services::database::{query_clipboard_items, get_clipboard_item_by_id, QueryParams};
+    use crate::services::paste::paste_handler::paste_clipboard_item_with_format;
+    use crate::services::paste::{transform, PasteFormat};
+
+    let state = crate::get_window_state();
+    let is_window_visible = state.state == crate::WindowState::Visible && !state.is_hidden;
+
+    // 选中项由前端确定，交给主窗口的 paste-transform-selected 监听处理
+    if is_window_visible {
+        let window = app.get_webview_window("main").ok_or("主窗口不存在")?;
+        window.emit("paste-transform-selected", chain_id)
+            .map_err(|e| format!("通知主窗口转换粘贴失败: {}", e))?;
+        println!("已通知主窗口转换粘贴选中项: {}", chain_id);
+        return Ok(());
+    }
+
+    let transforms = transform::resolve_transform_chain(chain_id)?;
+    let items = query_clipboard_items(QueryParams {
+        offset: 0,
+        limit: 1,
+        ..Default::default()
+    })?
+    .items;
+
+    if let Some(item) = items.first() {
+        let full_item = get_clipboard_item_by_id(item.id)?
+            .ok_or_else(|| format!("剪贴板项 {} 不存在", item.id))?;
+        paste_clipboard_item_with_format(&full_item, Some(PasteFormat::Transformed(transforms)))?;
+    }
+
+    Ok(())
+}
+
 pub fn register_number_shortcuts(modifier: &str) -> Result<(), String> {
     let app = get_app()?;
     
@@ -444,8 +512,7 @@ fn handle_number_shortcut_press(index: usize) -> Result<(), String> {
     let items = query_clipboard_items(QueryParams {
         offset: 0,
         limit: 9,
-        search: None,
-        content_type: None,
+        ..Default::default()
     })?
     .items;
 
@@ -602,6 +669,18 @@ pub fn reload_from_settings() -> Result<(), String> {
             }
         }
         
+        for chain in settings.paste_transform_chains.iter().filter(|c| !c.shortcut.is_empty()) {
+            if let Err(e) = register_transform_chain_hotkey(&chain.id, &chain.shortcut) {
+                eprintln!("注册转换链快捷键 [{}] 失败: {}", chain.name, e);
+            }
+        }
+        
+        if !settings.paste_queue_shortcut.is_empty() {
+            if let Err(e) = register_paste_queue_hotkey(&settings.paste_queue_shortcut) {
+                eprintln!("注册粘贴队列快捷键失败: {}", e);
+            }
+        }
+        
         if settings.number_shortcuts && !settings.number_shortcuts_modifier.is_empty() {
             if let Err(e) = register_number_shortcuts(&settings.number_shortcuts_modifier) {
                 eprintln!("注册数字快捷键失败: {}", e);
@@ -0,0 +1,101 @@
+// 按行比较两段文本（最长公共子序列），用于历史版本对比
+use serde::Serialize;
+
+// 超过该规模（行数乘积）时不再逐行对齐，整体视为删除旧文本、插入新文本
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    // 去掉相同的首尾行，缩小比较范围
+    let prefix = old_lines.iter().zip(&new_lines).take_while(|(a, b)| a == b).count();
+    let suffix = old_lines[prefix..]
+        .iter()
+        .rev()
+        .zip(new_lines[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old_lines[prefix..old_lines.len() - suffix];
+    let new_mid = &new_lines[prefix..new_lines.len() - suffix];
+
+    let line = |op, text: &str| DiffLine { op, text: text.to_string() };
+    let mut result: Vec<DiffLine> = old_lines[..prefix].iter().map(|l| line(DiffOp::Equal, l)).collect();
+
+    if old_mid.len().saturating_mul(new_mid.len()) > MAX_DIFF_CELLS {
+        result.extend(old_mid.iter().map(|l| line(DiffOp::Delete, l)));
+        result.extend(new_mid.iter().map(|l| line(DiffOp::Insert, l)));
+    } else {
+        result.extend(lcs_diff(old_mid, new_mid));
+    }
+
+    result.extend(old_lines[old_lines.len() - suffix..].iter().map(|l| line(DiffOp::Equal, l)));
+    result
+}
+
+fn lcs_diff(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    let (n, m) = (old.len(), new.len());
+    // lengths[i][j]：old[i..] 与 new[j..] 的最长公共子序列长度
+    let mut lengths = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            result.push(DiffLine { op: DiffOp::Equal, text: old[i].to_string() });
+            i += 1;
+            j += 1;
+        } else if j < m && (i == n || lengths[i][j + 1] >= lengths[i + 1][j]) {
+            result.push(DiffLine { op: DiffOp::Insert, text: new[j].to_string() });
+            j += 1;
+        } else {
+            result.push(DiffLine { op: DiffOp::Delete, text: old[i].to_string() });
+            i += 1;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_changed_lines() {
+        let ops: Vec<(DiffOp, String)> = diff_lines("a\nb\nc\nd", "a\nc\nx\nd")
+            .into_iter()
+            .map(|l| (l.op, l.text))
+            .collect();
+        let expected = [
+            (DiffOp::Equal, "a"),
+            (DiffOp::Delete, "b"),
+            (DiffOp::Equal, "c"),
+            (DiffOp::Insert, "x"),
+            (DiffOp::Equal, "d"),
+        ];
+        assert_eq!(ops, expected.map(|(op, text)| (op, text.to_string())));
+    }
+}
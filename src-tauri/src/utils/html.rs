@@ -101,3 +101,42 @@ pub fn truncate_html(html: String, max_visible_len: usize) -> String {
     
     result
 }
+
+// 为指定 src 的 <img> 标签添加 data-image-id 属性，没有可修改的标签时返回 None
+pub fn add_image_id_to_img_tags(html: &str, src: &str, image_id: &str) -> Option<String> {
+    let re = regex::Regex::new(r#"<img\b[^>]*>"#).ok()?;
+    let src_patterns = [format!("src=\"{}\"", src), format!("src='{}'", src)];
+    let mut changed = false;
+
+    let result = re.replace_all(html, |caps: &regex::Captures| {
+        let tag = &caps[0];
+        let normalized = tag.replace(" = ", "=").replace(" =", "=").replace("= ", "=");
+        if tag.contains("data-image-id") || !src_patterns.iter().any(|p| normalized.contains(p.as_str())) {
+            return tag.to_string();
+        }
+        changed = true;
+        tag.replacen("<img", &format!(r#"<img data-image-id="{}""#, image_id), 1)
+    });
+
+    changed.then(|| result.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_image_id_to_matching_tags() {
+        let html = r#"<p><img src="https://a.com/x.png" alt="x"><img src='https://a.com/x.png'><img src="https://a.com/y.png"></p>"#;
+        let result = add_image_id_to_img_tags(html, "https://a.com/x.png", "abc").unwrap();
+        assert_eq!(result.matches(r#"data-image-id="abc""#).count(), 2);
+        assert!(result.contains(r#"<img src="https://a.com/y.png">"#));
+    }
+
+    #[test]
+    fn skips_tags_already_localized() {
+        let html = r#"<img data-image-id="old" src="https://a.com/x.png">"#;
+        assert!(add_image_id_to_img_tags(html, "https://a.com/x.png", "abc").is_none());
+        assert!(add_image_id_to_img_tags("<p>无图片</p>", "https://a.com/x.png", "abc").is_none());
+    }
+}
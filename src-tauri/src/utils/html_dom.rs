@@ -0,0 +1,321 @@
+// 轻量 HTML 解析（容错处理剪贴板中的不规范 HTML），供 Markdown 转换和清理使用
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+#[derive(Debug)]
+pub(crate) struct Element {
+    pub tag: String,
+    pub attrs: HashMap<String, String>,
+    pub children: Vec<Node>,
+}
+
+impl Element {
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.get(name).map(|s| s.as_str())
+    }
+
+    // 小写且去掉空白的 style，便于匹配
+    pub fn style(&self) -> String {
+        self.attr("style")
+            .unwrap_or("")
+            .to_ascii_lowercase()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect()
+    }
+}
+
+enum Tag {
+    Open { name: String, attrs: HashMap<String, String>, self_closing: bool },
+    Close(String),
+}
+
+pub(crate) const VOID_TAGS: &[&str] = &[
+    "br", "hr", "img", "input", "meta", "link", "area", "base", "col", "embed", "source", "track", "wbr",
+];
+
+// 内容不参与转换的标签
+const SKIP_TAGS: &[&str] = &["script", "style", "head", "title", "template", "noscript"];
+
+pub(crate) fn parse_html(html: &str) -> Vec<Node> {
+    let root = Element { tag: String::new(), attrs: HashMap::new(), children: Vec::new() };
+    let mut stack = vec![root];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(lt) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        if lt > 0 {
+            push_text(&mut stack, &rest[..lt]);
+        }
+        rest = &rest[lt..];
+
+        // 注释、条件注释（<![if !supportLists]>）和声明
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map(|i| &after[i + 3..]).unwrap_or("");
+            continue;
+        }
+        if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map(|i| &rest[i + 1..]).unwrap_or("");
+            continue;
+        }
+
+        let Some((tag, consumed)) = parse_tag(rest) else {
+            push_text(&mut stack, "<");
+            rest = &rest[1..];
+            continue;
+        };
+        rest = &rest[consumed..];
+
+        match tag {
+            Tag::Close(name) => close_element(&mut stack, &name),
+            Tag::Open { name, attrs, self_closing } => {
+                if SKIP_TAGS.contains(&name.as_str()) && !self_closing {
+                    let close = format!("</{}", name);
+                    if let Some(i) = rest.to_ascii_lowercase().find(&close) {
+                        let after = &rest[i..];
+                        rest = after.find('>').map(|j| &after[j + 1..]).unwrap_or("");
+                    }
+                    continue;
+                }
+                auto_close(&mut stack, &name);
+                let element = Element { tag: name, attrs, children: Vec::new() };
+                if self_closing || VOID_TAGS.contains(&element.tag.as_str()) {
+                    if let Some(parent) = stack.last_mut() {
+                        parent.children.push(Node::Element(element));
+                    }
+                } else {
+                    stack.push(element);
+                }
+            }
+        }
+    }
+
+    while stack.len() > 1 {
+        pop_into_parent(&mut stack);
+    }
+    stack.pop().map(|root| root.children).unwrap_or_default()
+}
+
+fn push_text(stack: &mut [Element], text: &str) {
+    let Some(parent) = stack.last_mut() else {
+        return;
+    };
+    let text = decode_entities(text);
+    match parent.children.last_mut() {
+        Some(Node::Text(last)) => last.push_str(&text),
+        _ => parent.children.push(Node::Text(text)),
+    }
+}
+
+fn pop_into_parent(stack: &mut Vec<Element>) {
+    if let Some(element) = stack.pop() {
+        if let Some(parent) = stack.last_mut() {
+            parent.children.push(Node::Element(element));
+        }
+    }
+}
+
+// 关闭到最近的同名元素，没有匹配的开始标签时忽略
+fn close_element(stack: &mut Vec<Element>, name: &str) {
+    if let Some(pos) = stack.iter().skip(1).rposition(|e| e.tag == name) {
+        while stack.len() > pos + 1 {
+            pop_into_parent(stack);
+        }
+    }
+}
+
+// 省略结束标签的元素（<li>、<p>、<tr>、<td>）在遇到同级元素时自动关闭
+fn auto_close(stack: &mut Vec<Element>, name: &str) {
+    let (closes, boundary): (&[&str], &[&str]) = match name {
+        "li" => (&["li"], &["ul", "ol"]),
+        "tr" => (&["tr", "td", "th"], &["table", "thead", "tbody", "tfoot"]),
+        "td" | "th" => (&["td", "th"], &["tr", "table"]),
+        "p" => (&["p"], &["div", "td", "th", "li", "blockquote", "body"]),
+        _ => return,
+    };
+    let found = stack
+        .iter()
+        .enumerate()
+        .skip(1)
+        .rev()
+        .find(|(_, e)| closes.contains(&e.tag.as_str()) || boundary.contains(&e.tag.as_str()));
+    if let Some((pos, element)) = found {
+        if closes.contains(&element.tag.as_str()) {
+            while stack.len() > pos {
+                pop_into_parent(stack);
+            }
+        }
+    }
+}
+
+fn parse_tag(s: &str) -> Option<(Tag, usize)> {
+    let end = find_tag_end(s)?;
+    let inner = &s[1..end];
+
+    if let Some(name) = inner.strip_prefix('/') {
+        let name = name.split_whitespace().next().unwrap_or("").to_ascii_lowercase();
+        return is_valid_tag_name(&name).then_some((Tag::Close(name), end + 1));
+    }
+
+    let self_closing = inner.ends_with('/');
+    let inner = inner.trim_end_matches('/');
+    // 标签名后可以直接跟 '/'（<img/src=x>）
+    let name_end = inner.find(|c: char| c.is_whitespace() || c == '/').unwrap_or(inner.len());
+    let name = inner[..name_end].to_ascii_lowercase();
+    if !is_valid_tag_name(&name) {
+        return None;
+    }
+    let attrs = parse_attrs(&inner[name_end..]);
+    Some((Tag::Open { name, attrs, self_closing }, end + 1))
+}
+
+// 查找标签结束的 '>'（忽略引号内的内容）
+fn find_tag_end(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '>') => return Some(i),
+            (None, '<') => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn is_valid_tag_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == ':' || c == '-')
+}
+
+fn parse_attrs(s: &str) -> HashMap<String, String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut attrs = HashMap::new();
+    let mut i = 0;
+
+    // 属性之间的 '/' 与空白等价
+    let is_separator = |c: char| c.is_whitespace() || c == '/';
+    while i < chars.len() {
+        while i < chars.len() && is_separator(chars[i]) {
+            i += 1;
+        }
+        let start = i;
+        while i < chars.len() && !is_separator(chars[i]) && chars[i] != '=' {
+            i += 1;
+        }
+        let name: String = chars[start..i].iter().collect::<String>().to_ascii_lowercase();
+        while i < chars.len() && chars[i].is_whitespace() {
+            i += 1;
+        }
+
+        let mut value = String::new();
+        if chars.get(i) == Some(&'=') {
+            i += 1;
+            while i < chars.len() && chars[i].is_whitespace() {
+                i += 1;
+            }
+            match chars.get(i) {
+                Some(&q) if q == '"' || q == '\'' => {
+                    i += 1;
+                    while i < chars.len() && chars[i] != q {
+                        value.push(chars[i]);
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                _ => {
+                    while i < chars.len() && !chars[i].is_whitespace() {
+                        value.push(chars[i]);
+                        i += 1;
+                    }
+                }
+            }
+        }
+
+        if !name.is_empty() {
+            attrs.entry(name).or_insert_with(|| decode_entities(&value));
+        }
+    }
+    attrs
+}
+
+pub(crate) fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let re = regex::Regex::new(r"&(#[xX][0-9a-fA-F]+|#[0-9]+|[a-zA-Z]+);").unwrap();
+    re.replace_all(text, |caps: &regex::Captures| {
+        let entity = &caps[1];
+        let decoded = if let Some(hex) = entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        } else if let Some(dec) = entity.strip_prefix('#') {
+            dec.parse::<u32>().ok().and_then(char::from_u32)
+        } else {
+            named_entity(entity)
+        };
+        decoded.map(|c| c.to_string()).unwrap_or_else(|| caps[0].to_string())
+    })
+    .into_owned()
+}
+
+fn named_entity(name: &str) -> Option<char> {
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "copy" => '©',
+        "reg" => '®',
+        "trade" => '™',
+        "hellip" => '…',
+        "mdash" => '—',
+        "ndash" => '–',
+        "lsquo" => '‘',
+        "rsquo" => '’',
+        "ldquo" => '“',
+        "rdquo" => '”',
+        "bull" => '•',
+        "middot" => '·',
+        _ => return None,
+    })
+}
+
+// 剪贴板 HTML 只取 StartFragment 与 EndFragment 之间的片段
+pub(crate) fn extract_fragment(html: &str) -> &str {
+    match (html.find("<!--StartFragment-->"), html.find("<!--EndFragment-->")) {
+        (Some(start), Some(end)) if start < end => &html[start + "<!--StartFragment-->".len()..end],
+        _ => html,
+    }
+}
+
+pub(crate) fn push_escaped(out: &mut String, c: char) {
+    match c {
+        '&' => out.push_str("&amp;"),
+        '<' => out.push_str("&lt;"),
+        '>' => out.push_str("&gt;"),
+        '"' => out.push_str("&quot;"),
+        '\'' => out.push_str("&#39;"),
+        _ => out.push(c),
+    }
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        push_escaped(&mut out, c);
+    }
+    out
+}
@@ -0,0 +1,196 @@
+// 富文本粘贴前的 HTML 清理：按级别去除样式，并始终移除脚本、事件属性和未本地化的远程图片
+use super::html_dom::{escape_html, extract_fragment, parse_html, Element, Node, VOID_TAGS};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SanitizeLevel {
+    // 只保留结构（标题、列表、表格、链接、加粗/斜体等）
+    Structure,
+    // 去除颜色和字体，保留其余样式
+    NoColors,
+    // 保留原始标签和样式（仍移除不安全内容）
+    #[default]
+    Original,
+}
+
+impl SanitizeLevel {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "structure" => SanitizeLevel::Structure,
+            "no_colors" => SanitizeLevel::NoColors,
+            _ => SanitizeLevel::Original,
+        }
+    }
+}
+
+// 仅保留结构时允许的标签，其它标签只保留内容
+const STRUCTURE_TAGS: &[&str] = &[
+    "p", "div", "br", "hr", "h1", "h2", "h3", "h4", "h5", "h6", "ul", "ol", "li", "dl", "dt", "dd",
+    "table", "thead", "tbody", "tfoot", "tr", "td", "th", "a", "b", "strong", "i", "em", "u", "s",
+    "del", "strike", "sub", "sup", "blockquote", "pre", "code", "img",
+];
+
+// 连同内容一起移除的标签
+const DANGEROUS_TAGS: &[&str] = &[
+    "script", "iframe", "object", "embed", "frame", "frameset", "base", "link", "meta",
+];
+
+// 需要检查协议的 URL 属性
+const URL_ATTRS: &[&str] = &["href", "src", "action", "formaction", "xlink:href", "background"];
+
+pub fn sanitize_html(html: &str, level: SanitizeLevel) -> String {
+    let nodes = parse_html(extract_fragment(html));
+    let mut out = String::with_capacity(html.len() / 2);
+    write_nodes(&nodes, level, &mut out);
+    out
+}
+
+fn write_nodes(nodes: &[Node], level: SanitizeLevel, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(&escape_html(text)),
+            Node::Element(e) => write_element(e, level, out),
+        }
+    }
+}
+
+fn write_element(e: &Element, level: SanitizeLevel, out: &mut String) {
+    let tag = e.tag.as_str();
+    if DANGEROUS_TAGS.contains(&tag) || (tag == "img" && !is_kept_image(e)) {
+        return;
+    }
+
+    let keep_tag = match level {
+        SanitizeLevel::Structure => STRUCTURE_TAGS.contains(&tag),
+        // <font> 和 Office 的命名空间标签（<o:p>）只保留内容
+        SanitizeLevel::NoColors => tag != "font" && !tag.contains(':'),
+        SanitizeLevel::Original => true,
+    };
+    if !keep_tag {
+        write_nodes(&e.children, level, out);
+        return;
+    }
+
+    out.push('<');
+    out.push_str(tag);
+    for (name, value) in allowed_attrs(e, level) {
+        out.push_str(&format!(" {}=\"{}\"", name, escape_html(&value)));
+    }
+    out.push('>');
+
+    if VOID_TAGS.contains(&tag) {
+        return;
+    }
+    write_nodes(&e.children, level, out);
+    out.push_str(&format!("</{}>", tag));
+}
+
+// 按名称排序输出，保证结果稳定
+fn allowed_attrs(e: &Element, level: SanitizeLevel) -> Vec<(String, String)> {
+    let mut attrs: Vec<(String, String)> = e
+        .attrs
+        .iter()
+        .filter(|(name, value)| {
+            !name.starts_with("on") && (!URL_ATTRS.contains(&name.as_str()) || is_safe_url(value))
+        })
+        .filter_map(|(name, value)| match level {
+            SanitizeLevel::Structure => {
+                let allowed = matches!(
+                    (e.tag.as_str(), name.as_str()),
+                    ("a", "href" | "title")
+                        | ("img", "src" | "alt" | "width" | "height" | "data-image-id")
+                        | ("td" | "th", "colspan" | "rowspan")
+                        | ("ol", "start")
+                );
+                allowed.then(|| (name.clone(), value.clone()))
+            }
+            SanitizeLevel::Original => Some((name.clone(), value.clone())),
+            SanitizeLevel::NoColors => match name.as_str() {
+                "color" | "bgcolor" | "background" | "face" => None,
+                "style" => {
+                    let style = strip_color_font_styles(value);
+                    (!style.is_empty()).then(|| (name.clone(), style))
+                }
+                _ => Some((name.clone(), value.clone())),
+            },
+        })
+        .collect();
+    attrs.sort();
+    attrs
+}
+
+// 移除颜色、背景和字体相关声明（保留加粗、斜体）
+fn strip_color_font_styles(style: &str) -> String {
+    style
+        .split(';')
+        .filter_map(|decl| {
+            let (prop, value) = decl.split_once(':')?;
+            let prop = prop.trim().to_ascii_lowercase();
+            let removed = prop.contains("color")
+                || prop.starts_with("background")
+                || (prop.contains("font") && prop != "font-weight" && prop != "font-style");
+            (!removed).then(|| format!("{}: {}", prop, value.trim()))
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+// 已本地化（data-image-id）或非远程的图片才保留
+fn is_kept_image(e: &Element) -> bool {
+    e.attr("data-image-id").is_some() || !e.attr("src").is_some_and(is_remote_src)
+}
+
+fn is_remote_src(src: &str) -> bool {
+    let src = src.trim().to_ascii_lowercase();
+    src.starts_with("http://") || src.starts_with("https://") || src.starts_with("//")
+}
+
+fn is_safe_url(url: &str) -> bool {
+    let url: String = url.chars().filter(|c| !c.is_whitespace() && !c.is_control()).collect::<String>().to_ascii_lowercase();
+    !(url.starts_with("javascript:") || url.starts_with("vbscript:") || url.starts_with("data:text/html"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r##"<html><head><style>p{color:red}</style></head><body><!--StartFragment--><h2 style="color:#333;font-family:Arial">Title</h2><p class="lead" style="font-size:18px; font-weight:bold; margin:0" onclick="track()"><font color="red" face="Arial">Hello</font> <span style="background-color:yellow">world</span><img src="https://t.example.com/pixel.gif" width="1"><img data-image-id="abc" src="https://x.com/a.png" onerror="x()"></p><script>alert(1)</script><table border="1"><tr><td colspan="2" bgcolor="#eee"><a href="javascript:alert(1)">bad</a> <a href="https://a.com" target="_blank">ok</a></td></tr></table><!--EndFragment--></body></html>"##;
+
+    #[test]
+    fn keeps_only_structure() {
+        assert_eq!(
+            sanitize_html(PAGE, SanitizeLevel::Structure),
+            r#"<h2>Title</h2><p>Hello world<img data-image-id="abc" src="https://x.com/a.png"></p><table><tr><td colspan="2"><a>bad</a> <a href="https://a.com">ok</a></td></tr></table>"#
+        );
+    }
+
+    #[test]
+    fn removes_colors_and_fonts() {
+        assert_eq!(
+            sanitize_html(PAGE, SanitizeLevel::NoColors),
+            r#"<h2>Title</h2><p class="lead" style="font-weight: bold; margin: 0">Hello <span>world</span><img data-image-id="abc" src="https://x.com/a.png"></p><table border="1"><tr><td colspan="2"><a>bad</a> <a href="https://a.com" target="_blank">ok</a></td></tr></table>"#
+        );
+    }
+
+    #[test]
+    fn original_only_strips_unsafe_content() {
+        assert_eq!(
+            sanitize_html(PAGE, SanitizeLevel::Original),
+            r##"<h2 style="color:#333;font-family:Arial">Title</h2><p class="lead" style="font-size:18px; font-weight:bold; margin:0"><font color="red" face="Arial">Hello</font> <span style="background-color:yellow">world</span><img data-image-id="abc" src="https://x.com/a.png"></p><table border="1"><tr><td bgcolor="#eee" colspan="2"><a>bad</a> <a href="https://a.com" target="_blank">ok</a></td></tr></table>"##
+        );
+    }
+
+    #[test]
+    fn original_resists_malformed_markup() {
+        let cases = [
+            ("<img/onerror=alert(1) src=x>", r#"<img src="x">"#),
+            (r#"<a title="a>b" onclick="evil()">x</a>"#, r#"<a title="a&gt;b">x</a>"#),
+            (r#"<img alt="x>y" src="https://t.example/p.gif">"#, ""),
+            (r#"<a href="jav&#x61;script:alert(1)">x</a>"#, "<a>x</a>"),
+            (r#"<a href=" java&#9;script:alert(1)">x</a>"#, "<a>x</a>"),
+            (r#"<svg><a xlink:href="javascript:alert(1)">x</a></svg>"#, "<svg><a>x</a></svg>"),
+        ];
+        for (html, expected) in cases {
+            assert_eq!(sanitize_html(html, SanitizeLevel::Original), expected, "{}", html);
+        }
+    }
+}
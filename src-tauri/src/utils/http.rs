@@ -0,0 +1,24 @@
+// HTTP 下载工具函数
+use reqwest::blocking::Response;
+use std::io::Read;
+
+// 读取响应体，超出上限时返回错误
+pub fn read_limited_body(response: Response, max_bytes: u64) -> Result<Vec<u8>, String> {
+    if let Some(len) = response.content_length() {
+        if len > max_bytes {
+            return Err(format!("响应过大: {} 字节（上限 {} 字节）", len, max_bytes));
+        }
+    }
+
+    let mut data = Vec::new();
+    response
+        .take(max_bytes + 1)
+        .read_to_end(&mut data)
+        .map_err(|e| format!("读取响应失败: {}", e))?;
+
+    if data.len() as u64 > max_bytes {
+        return Err(format!("响应过大（上限 {} 字节）", max_bytes));
+    }
+
+    Ok(data)
+}
@@ -0,0 +1,1125 @@
+// HTML 与 Markdown 互相转换（覆盖 Office、浏览器和代码编辑器复制的常见片段）
+use super::html_dom::{escape_html, extract_fragment, parse_html, push_escaped, Element, Node};
+
+// ==================== HTML → Markdown ====================
+
+const BLOCK_TAGS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "body", "dd", "div", "dl", "dt", "figcaption", "figure",
+    "footer", "form", "h1", "h2", "h3", "h4", "h5", "h6", "header", "hr", "html", "li", "main", "nav",
+    "ol", "p", "pre", "section", "table", "ul",
+];
+
+// 将 HTML 转换为 Markdown；image_src 根据 data-image-id 返回已保存图片的地址
+pub fn html_to_markdown(html: &str, image_src: &dyn Fn(&str) -> Option<String>) -> String {
+    let nodes = parse_html(extract_fragment(html));
+    let writer = MarkdownWriter { image_src };
+    writer.blocks(&nodes).join("\n\n")
+}
+
+struct MarkdownWriter<'a> {
+    image_src: &'a dyn Fn(&str) -> Option<String>,
+}
+
+impl MarkdownWriter<'_> {
+    fn blocks(&self, nodes: &[Node]) -> Vec<String> {
+        let mut blocks = Vec::new();
+        let mut inline = String::new();
+        // 上一块是否为 Office 列表段落（连续的列表段落合并为一个列表）
+        let mut in_office_list = false;
+
+        for node in nodes {
+            match node {
+                Node::Element(e) if is_block(e) => {
+                    if flush_paragraph(&mut inline, &mut blocks) {
+                        in_office_list = false;
+                    }
+                    if let Some(item) = self.office_list_item(e) {
+                        match blocks.last_mut() {
+                            Some(last) if in_office_list => {
+                                last.push('\n');
+                                last.push_str(&item);
+                            }
+                            _ => blocks.push(item),
+                        }
+                        in_office_list = true;
+                        continue;
+                    }
+                    let before = blocks.len();
+                    self.block(e, &mut blocks);
+                    if blocks.len() != before {
+                        in_office_list = false;
+                    }
+                }
+                _ => inline.push_str(&self.inline(node)),
+            }
+        }
+        flush_paragraph(&mut inline, &mut blocks);
+        blocks
+    }
+
+    fn block(&self, e: &Element, blocks: &mut Vec<String>) {
+        match e.tag.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = e.tag[1..].parse::<usize>().unwrap_or(1);
+                let text = clean_inline(&self.inline_children(e)).replace('\n', " ");
+                if !text.is_empty() {
+                    blocks.push(format!("{} {}", "#".repeat(level), text));
+                }
+            }
+            "hr" => blocks.push("---".to_string()),
+            "pre" => blocks.push(code_block(&raw_text(&e.children), &code_language(e))),
+            "div" if is_code_container(e) => blocks.push(code_block(&raw_text(&e.children), "")),
+            "blockquote" => {
+                let inner = self.blocks(&e.children).join("\n\n");
+                if !inner.is_empty() {
+                    blocks.push(prefix_lines(&inner, "> ", "> "));
+                }
+            }
+            "ul" | "ol" => {
+                let list = self.list(e);
+                if !list.is_empty() {
+                    blocks.push(list);
+                }
+            }
+            "table" => {
+                if let Some(table) = self.table(e) {
+                    blocks.push(table);
+                }
+            }
+            _ => blocks.extend(self.blocks(&e.children)),
+        }
+    }
+
+    fn list(&self, e: &Element) -> String {
+        let ordered = e.tag == "ol";
+        let mut number = e.attr("start").and_then(|s| s.trim().parse::<u32>().ok()).unwrap_or(1);
+        let mut items: Vec<String> = Vec::new();
+
+        for child in &e.children {
+            let Node::Element(li) = child else {
+                continue;
+            };
+            // 部分编辑器把子列表直接放在列表下，归入上一项
+            if li.tag == "ul" || li.tag == "ol" {
+                let nested = self.list(li);
+                match items.last_mut() {
+                    Some(last) if !nested.is_empty() => {
+                        last.push('\n');
+                        last.push_str(&prefix_lines(&nested, "    ", "    "));
+                    }
+                    _ => items.push(nested),
+                }
+                continue;
+            }
+            if li.tag != "li" {
+                continue;
+            }
+            let marker = if ordered { format!("{}. ", number) } else { "- ".to_string() };
+            number += 1;
+            let body = self.blocks(&li.children).join("\n");
+            items.push(prefix_lines(&body, &marker, &" ".repeat(marker.len())));
+        }
+        items.join("\n")
+    }
+
+    fn table(&self, e: &Element) -> Option<String> {
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        self.collect_rows(e, &mut rows);
+        let columns = rows.iter().map(|r| r.len()).max().filter(|&n| n > 0)?;
+
+        let format_row = |row: &[String]| {
+            let cells: Vec<&str> = (0..columns).map(|i| row.get(i).map(|s| s.as_str()).unwrap_or("")).collect();
+            format!("| {} |", cells.join(" | "))
+        };
+        let mut lines = vec![format_row(&rows[0]), format!("|{}", " --- |".repeat(columns))];
+        lines.extend(rows[1..].iter().map(|r| format_row(r)));
+        Some(lines.join("\n"))
+    }
+
+    fn collect_rows(&self, e: &Element, rows: &mut Vec<Vec<String>>) {
+        for child in &e.children {
+            let Node::Element(child) = child else {
+                continue;
+            };
+            match child.tag.as_str() {
+                "thead" | "tbody" | "tfoot" => self.collect_rows(child, rows),
+                "tr" => {
+                    let mut cells = Vec::new();
+                    for cell in &child.children {
+                        let Node::Element(cell) = cell else {
+                            continue;
+                        };
+                        if cell.tag != "td" && cell.tag != "th" {
+                            continue;
+                        }
+                        let text = self.blocks(&cell.children).join(" ").replace('\n', " ").replace('|', "\\|");
+                        cells.push(text);
+                        let span = cell.attr("colspan").and_then(|s| s.trim().parse::<usize>().ok()).unwrap_or(1);
+                        cells.extend(std::iter::repeat_n(String::new(), span.saturating_sub(1).min(50)));
+                    }
+                    rows.push(cells);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Word 列表段落：<p style="mso-list:l0 level2 lfo1"><span style="mso-list:Ignore">1.</span>内容</p>
+    fn office_list_item(&self, e: &Element) -> Option<String> {
+        if e.tag != "p" {
+            return None;
+        }
+        let style = e.style();
+        let spec = &style[style.find("mso-list:")? + "mso-list:".len()..];
+        if spec.starts_with("ignore") || spec.starts_with("none") {
+            return None;
+        }
+        let level = spec
+            .find("level")
+            .and_then(|i| spec[i + 5..].chars().take_while(|c| c.is_ascii_digit()).collect::<String>().parse::<usize>().ok())
+            .unwrap_or(1)
+            .max(1);
+
+        let marker = find_office_list_marker(&e.children).unwrap_or_default();
+        let marker = marker.replace('\u{a0}', " ");
+        let marker = marker.trim();
+        let ordered = (marker.ends_with('.') || marker.ends_with(')'))
+            && marker.len() > 1
+            && marker[..marker.len() - 1].chars().all(|c| c.is_alphanumeric());
+
+        let content = clean_inline(&self.inline_children(e)).replace('\n', " ");
+        let prefix = if ordered { "1. " } else { "- " };
+        Some(format!("{}{}{}", "    ".repeat(level - 1), prefix, content))
+    }
+
+    fn inline_children(&self, e: &Element) -> String {
+        e.children.iter().map(|n| self.inline(n)).collect()
+    }
+
+    fn inline(&self, node: &Node) -> String {
+        let e = match node {
+            Node::Text(text) => return escape_markdown(&collapse_whitespace(text)),
+            Node::Element(e) => e,
+        };
+        let style = e.style();
+
+        match e.tag.as_str() {
+            "br" => "\n".to_string(),
+            "img" => self.image(e),
+            "a" => self.link(e),
+            "code" | "kbd" | "samp" | "tt" => code_span(&collapse_whitespace(&raw_text(&e.children))),
+            "b" | "strong" if !style.contains("font-weight:normal") => wrap_emphasis(&self.inline_children(e), "**"),
+            "i" | "em" if !style.contains("font-style:normal") => wrap_emphasis(&self.inline_children(e), "*"),
+            "s" | "del" | "strike" => wrap_emphasis(&self.inline_children(e), "~~"),
+            "span" | "font" if style.contains("mso-list:ignore") => String::new(),
+            "span" | "font" => {
+                let mut text = self.inline_children(e);
+                if style.contains("text-decoration:line-through") {
+                    text = wrap_emphasis(&text, "~~");
+                }
+                if style.contains("font-style:italic") {
+                    text = wrap_emphasis(&text, "*");
+                }
+                if is_bold_style(&style) {
+                    text = wrap_emphasis(&text, "**");
+                }
+                text
+            }
+            _ => self.inline_children(e),
+        }
+    }
+
+    fn image(&self, e: &Element) -> String {
+        let src = e
+            .attr("data-image-id")
+            .and_then(|id| (self.image_src)(id))
+            .or_else(|| e.attr("src").map(|s| s.trim().to_string()))
+            .unwrap_or_default();
+        if src.is_empty() {
+            return String::new();
+        }
+        let alt = e.attr("alt").unwrap_or("").replace('[', "\\[").replace(']', "\\]");
+        format!("![{}]({})", alt, markdown_url(&src))
+    }
+
+    fn link(&self, e: &Element) -> String {
+        let text = self.inline_children(e);
+        let href = e.attr("href").map(|s| s.trim()).unwrap_or("");
+        if href.is_empty() || href.to_ascii_lowercase().starts_with("javascript:") {
+            return text;
+        }
+        let label = clean_inline(&text).replace('\n', " ");
+        if label.is_empty() {
+            return text;
+        }
+        let plain = href.strip_prefix("mailto:").unwrap_or(href);
+        if label == escape_markdown(plain) && href.contains(':') {
+            return format!("<{}>", href);
+        }
+        format!("[{}]({})", label, markdown_url(href))
+    }
+}
+
+// 输出段落，返回是否产生了新块
+fn flush_paragraph(inline: &mut String, blocks: &mut Vec<String>) -> bool {
+    let text = clean_inline(&std::mem::take(inline));
+    if text.is_empty() {
+        return false;
+    }
+    let lines: Vec<String> = text.lines().map(escape_block_start).collect();
+    blocks.push(lines.join("  \n"));
+    true
+}
+
+// 合并多余空格并去掉空行（换行来自 <br>）
+fn clean_inline(text: &str) -> String {
+    text.replace('\u{a0}', " ")
+        .split('\n')
+        .map(|line| line.split(' ').filter(|s| !s.is_empty()).collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() && c != '\u{a0}' {
+            if !last_space {
+                out.push(' ');
+            }
+            last_space = true;
+        } else {
+            out.push(c);
+            last_space = false;
+        }
+    }
+    out
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+// 避免段落开头的文字被识别为标题、引用或列表
+fn escape_block_start(line: &str) -> String {
+    if line.starts_with(['#', '>', '-', '+']) {
+        return format!("\\{}", line);
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 && line[digits..].starts_with(". ") {
+        return format!("{}\\{}", &line[..digits], &line[digits..]);
+    }
+    line.to_string()
+}
+
+// 强调标记需要紧贴文字，首尾空白移到标记外
+fn wrap_emphasis(text: &str, marker: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+    let lead = &text[..text.len() - text.trim_start().len()];
+    let trail = &text[text.trim_end().len()..];
+    format!("{}{}{}{}{}", lead, marker, trimmed, marker, trail)
+}
+
+fn is_bold_style(style: &str) -> bool {
+    ["font-weight:bold", "font-weight:bolder", "font-weight:600", "font-weight:700", "font-weight:800", "font-weight:900"]
+        .iter()
+        .any(|s| style.contains(s))
+}
+
+fn is_block(e: &Element) -> bool {
+    BLOCK_TAGS.contains(&e.tag.as_str())
+        || e.children.iter().any(|n| matches!(n, Node::Element(child) if is_block(child)))
+}
+
+// VS Code 等编辑器复制的代码是 white-space: pre 的 div，每行一个子 div
+fn is_code_container(e: &Element) -> bool {
+    let style = e.style();
+    style.contains("white-space:pre;") || style.ends_with("white-space:pre")
+}
+
+fn code_language(pre: &Element) -> String {
+    let code_class = pre.children.iter().find_map(|n| match n {
+        Node::Element(e) if e.tag == "code" => e.attr("class"),
+        _ => None,
+    });
+    [pre.attr("class"), code_class]
+        .into_iter()
+        .flatten()
+        .flat_map(|class| class.split_whitespace())
+        .find_map(|c| c.strip_prefix("language-").or_else(|| c.strip_prefix("lang-")))
+        .unwrap_or("")
+        .to_string()
+}
+
+// 保留原始空白的文本，块级子元素换行
+fn raw_text(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(e) if e.tag == "br" => out.push('\n'),
+            Node::Element(e) if matches!(e.tag.as_str(), "div" | "p" | "li" | "tr") => {
+                if !out.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str(raw_text(&e.children).trim_end_matches('\n'));
+                out.push('\n');
+            }
+            Node::Element(e) => out.push_str(&raw_text(&e.children)),
+        }
+    }
+    out
+}
+
+fn code_block(code: &str, language: &str) -> String {
+    let code = code.replace('\u{a0}', " ").replace("\r\n", "\n");
+    let code = code.trim_start_matches('\n').trim_end();
+    let fence = "`".repeat(longest_run(code, '`').max(2) + 1);
+    format!("{}{}\n{}\n{}", fence, language, code, fence)
+}
+
+fn code_span(code: &str) -> String {
+    if code.trim().is_empty() {
+        return code.to_string();
+    }
+    let fence = "`".repeat(longest_run(code, '`') + 1);
+    let pad = if code.starts_with('`') || code.ends_with('`') { " " } else { "" };
+    format!("{}{}{}{}{}", fence, pad, code, pad, fence)
+}
+
+fn longest_run(text: &str, ch: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        current = if c == ch { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    longest
+}
+
+fn find_office_list_marker(nodes: &[Node]) -> Option<String> {
+    nodes.iter().find_map(|n| match n {
+        Node::Element(e) if e.style().contains("mso-list:ignore") => Some(raw_text(&e.children)),
+        Node::Element(e) => find_office_list_marker(&e.children),
+        Node::Text(_) => None,
+    })
+}
+
+fn prefix_lines(text: &str, first: &str, rest: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = if i == 0 { first } else { rest };
+            if line.is_empty() {
+                prefix.trim_end().to_string()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn markdown_url(url: &str) -> String {
+    if url.contains([' ', '(', ')']) {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
+}
+
+// ==================== Markdown → HTML ====================
+
+// 将 Markdown 渲染为 HTML；不透传原始 HTML，并过滤不安全的链接协议
+pub fn markdown_to_html(markdown: &str) -> String {
+    let text = markdown.replace("\r\n", "\n").replace('\t', "    ");
+    let lines: Vec<&str> = text.lines().collect();
+    render_blocks(&lines).trim_end().to_string()
+}
+
+fn render_blocks(lines: &[&str]) -> String {
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            i += 1;
+            continue;
+        }
+
+        if let Some((fence, language)) = fence_start(line) {
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && !is_fence_end(lines[i], &fence) {
+                code.push(lines[i]);
+                i += 1;
+            }
+            i += 1;
+            let class = if language.is_empty() {
+                String::new()
+            } else {
+                format!(" class=\"language-{}\"", escape_html(&language))
+            };
+            out.push_str(&format!("<pre><code{}>{}</code></pre>\n", class, escape_html(&code.join("\n"))));
+            continue;
+        }
+
+        if let Some((level, content)) = heading(trimmed) {
+            out.push_str(&format!("<h{0}>{1}</h{0}>\n", level, render_inline(content)));
+            i += 1;
+            continue;
+        }
+
+        if is_rule(trimmed) {
+            out.push_str("<hr>\n");
+            i += 1;
+            continue;
+        }
+
+        if trimmed.starts_with('>') {
+            let mut inner = Vec::new();
+            while i < lines.len() && lines[i].trim_start().starts_with('>') {
+                let content = &lines[i].trim_start()[1..];
+                inner.push(content.strip_prefix(' ').unwrap_or(content));
+                i += 1;
+            }
+            out.push_str(&format!("<blockquote>\n{}</blockquote>\n", render_blocks(&inner)));
+            continue;
+        }
+
+        if list_marker(line).is_some() {
+            let end = list_end(lines, i);
+            out.push_str(&render_list(&lines[i..end]));
+            i = end;
+            continue;
+        }
+
+        if is_table_start(lines, i) {
+            let mut end = i + 2;
+            while end < lines.len() && lines[end].contains('|') && !lines[end].trim().is_empty() {
+                end += 1;
+            }
+            out.push_str(&render_table(&lines[i..end]));
+            i = end;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < lines.len() && !lines[i].trim().is_empty() && !starts_block(lines, i) {
+            i += 1;
+        }
+        out.push_str(&format!("<p>{}</p>\n", render_paragraph(&lines[start..i])));
+    }
+    out
+}
+
+fn starts_block(lines: &[&str], i: usize) -> bool {
+    let trimmed = lines[i].trim();
+    fence_start(lines[i]).is_some()
+        || heading(trimmed).is_some()
+        || is_rule(trimmed)
+        || trimmed.starts_with('>')
+        || list_marker(lines[i]).is_some()
+        || is_table_start(lines, i)
+}
+
+// 行尾两个空格或反斜杠表示硬换行
+fn render_paragraph(lines: &[&str]) -> String {
+    let mut out = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        let hard_break = line.ends_with("  ") || line.ends_with('\\');
+        let content = line.trim();
+        let content = if hard_break { content.trim_end_matches('\\') } else { content };
+        out.push_str(&render_inline(content));
+        if i + 1 < lines.len() {
+            out.push_str(if hard_break { "<br>\n" } else { "\n" });
+        }
+    }
+    out
+}
+
+fn fence_start(line: &str) -> Option<(String, String)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let ch = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let count = trimmed.chars().take_while(|c| *c == ch).count();
+    if count < 3 {
+        return None;
+    }
+    let info = trimmed[count..].trim();
+    if ch == '`' && info.contains('`') {
+        return None;
+    }
+    let language = info.split_whitespace().next().unwrap_or("").to_string();
+    Some((trimmed[..count].to_string(), language))
+}
+
+fn is_fence_end(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let ch = fence.chars().next().unwrap_or('`');
+    trimmed.len() >= fence.len() && trimmed.chars().all(|c| c == ch)
+}
+
+fn heading(trimmed: &str) -> Option<(usize, &str)> {
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    let content = rest.trim().trim_end_matches('#').trim_end();
+    Some((level, content))
+}
+
+fn is_rule(trimmed: &str) -> bool {
+    let Some(ch) = trimmed.chars().next().filter(|c| matches!(c, '-' | '*' | '_')) else {
+        return false;
+    };
+    trimmed.chars().all(|c| c == ch || c == ' ') && trimmed.chars().filter(|c| *c == ch).count() >= 3
+}
+
+struct ListMarker {
+    indent: usize,
+    ordered: bool,
+    number: u32,
+    content_offset: usize,
+}
+
+fn list_marker(line: &str) -> Option<ListMarker> {
+    let rest = line.trim_start_matches(' ');
+    let indent = line.len() - rest.len();
+
+    let (ordered, number, marker_len) = if rest.starts_with(['-', '*', '+']) {
+        (false, 0, 1)
+    } else {
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 || digits > 9 || !rest[digits..].starts_with(['.', ')']) {
+            return None;
+        }
+        (true, rest[..digits].parse().ok()?, digits + 1)
+    };
+
+    let after = &rest[marker_len..];
+    if !after.is_empty() && !after.starts_with(' ') {
+        return None;
+    }
+    let spaces = after.chars().take_while(|c| *c == ' ').count();
+    let spaces = if (1..=4).contains(&spaces) { spaces } else { 1 };
+    Some(ListMarker { indent, ordered, number, content_offset: indent + marker_len + spaces })
+}
+
+// 列表结束的位置（不含）
+fn list_end(lines: &[&str], start: usize) -> usize {
+    let Some(first) = list_marker(lines[start]) else {
+        return start + 1;
+    };
+    let mut i = start + 1;
+    while i < lines.len() {
+        let line = lines[i];
+        let indent = line.len() - line.trim_start_matches(' ').len();
+
+        if line.trim().is_empty() {
+            let next = (i + 1..lines.len()).find(|&j| !lines[j].trim().is_empty());
+            let continues = next.is_some_and(|j| {
+                let next_indent = lines[j].len() - lines[j].trim_start_matches(' ').len();
+                next_indent > first.indent
+                    || list_marker(lines[j]).is_some_and(|m| m.indent == first.indent && m.ordered == first.ordered)
+            });
+            if !continues {
+                break;
+            }
+        } else if let Some(marker) = list_marker(line) {
+            if marker.indent <= first.indent && marker.ordered != first.ordered {
+                break;
+            }
+        } else if indent <= first.indent {
+            // 惰性续行：紧跟在非空行后且不开始新块
+            if lines[i - 1].trim().is_empty() || starts_block(lines, i) {
+                break;
+            }
+        }
+        i += 1;
+    }
+    i
+}
+
+fn render_list(lines: &[&str]) -> String {
+    let Some(first) = list_marker(lines[0]) else {
+        return String::new();
+    };
+    let mut items: Vec<Vec<String>> = Vec::new();
+    let mut offset = first.content_offset;
+    let mut loose = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        match list_marker(line) {
+            Some(marker) if marker.indent <= first.indent => {
+                offset = marker.content_offset;
+                items.push(vec![line[offset.min(line.len())..].to_string()]);
+            }
+            _ => {
+                let Some(item) = items.last_mut() else {
+                    continue;
+                };
+                if line.trim().is_empty() {
+                    if lines.get(i + 1).and_then(|l| list_marker(l)).is_some_and(|m| m.indent <= first.indent) {
+                        loose = true;
+                    }
+                    item.push(String::new());
+                    continue;
+                }
+                let indent = line.len() - line.trim_start_matches(' ').len();
+                item.push(line[indent.min(offset)..].to_string());
+            }
+        }
+    }
+
+    let tag = if first.ordered { "ol" } else { "ul" };
+    let start = if first.ordered && first.number != 1 {
+        format!(" start=\"{}\"", first.number)
+    } else {
+        String::new()
+    };
+    let mut out = format!("<{}{}>\n", tag, start);
+    for item in items {
+        let item_lines: Vec<&str> = item.iter().map(|s| s.as_str()).collect();
+        let mut inner = render_blocks(&item_lines);
+        let item_loose = loose || item.iter().rev().skip_while(|l| l.is_empty()).any(|l| l.is_empty());
+        if !item_loose {
+            // 紧凑列表不包裹段落（文本已转义，不会误删内容）
+            inner = inner.replace("<p>", "").replace("</p>", "");
+        }
+        out.push_str(&format!("<li>{}</li>\n", inner.trim_end()));
+    }
+    out.push_str(&format!("</{}>\n", tag));
+    out
+}
+
+fn is_table_start(lines: &[&str], i: usize) -> bool {
+    lines[i].contains('|') && lines.get(i + 1).is_some_and(|next| is_table_separator(next))
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let cells = split_table_row(line);
+    !cells.is_empty()
+        && cells.iter().all(|c| {
+            let c = c.trim();
+            let inner = c.trim_start_matches(':').trim_end_matches(':');
+            !inner.is_empty() && inner.chars().all(|ch| ch == '-')
+        })
+}
+
+fn split_table_row(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let trimmed = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let trimmed = if trimmed.ends_with('|') && !trimmed.ends_with("\\|") {
+        &trimmed[..trimmed.len() - 1]
+    } else {
+        trimmed
+    };
+
+    let mut cells = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in trimmed.chars() {
+        if c == '|' && !escaped {
+            cells.push(current.trim().to_string());
+            current.clear();
+        } else {
+            current.push(c);
+        }
+        escaped = c == '\\' && !escaped;
+    }
+    cells.push(current.trim().to_string());
+    cells
+}
+
+fn render_table(lines: &[&str]) -> String {
+    let header = split_table_row(lines[0]);
+    let aligns: Vec<&str> = split_table_row(lines[1])
+        .iter()
+        .map(|c| match (c.starts_with(':'), c.ends_with(':')) {
+            (true, true) => "center",
+            (false, true) => "right",
+            (true, false) => "left",
+            _ => "",
+        })
+        .collect();
+
+    let cell = |tag: &str, text: &str, col: usize| {
+        let align = aligns.get(col).copied().unwrap_or("");
+        let style = if align.is_empty() { String::new() } else { format!(" style=\"text-align:{}\"", align) };
+        format!("<{0}{1}>{2}</{0}>", tag, style, render_inline(text))
+    };
+
+    let mut out = String::from("<table>\n<thead>\n<tr>");
+    for (col, text) in header.iter().enumerate() {
+        out.push_str(&cell("th", text, col));
+    }
+    out.push_str("</tr>\n</thead>\n<tbody>\n");
+    for line in &lines[2..] {
+        let cells = split_table_row(line);
+        out.push_str("<tr>");
+        for col in 0..header.len() {
+            out.push_str(&cell("td", cells.get(col).map(|s| s.as_str()).unwrap_or(""), col));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n");
+    out
+}
+
+fn render_inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if chars.get(i + 1).is_some_and(|n| n.is_ascii_punctuation()) => {
+                push_escaped(&mut out, chars[i + 1]);
+                i += 2;
+            }
+            '`' => {
+                let n = run_length(&chars, i, '`');
+                match find_code_end(&chars, i + n, n) {
+                    Some(end) => {
+                        let code: String = chars[i + n..end].iter().collect();
+                        let code = match code.strip_prefix(' ').and_then(|c| c.strip_suffix(' ')) {
+                            Some(inner) if !inner.trim().is_empty() => inner.to_string(),
+                            _ => code,
+                        };
+                        out.push_str(&format!("<code>{}</code>", escape_html(&code)));
+                        i = end + n;
+                    }
+                    None => {
+                        out.push_str(&"`".repeat(n));
+                        i += n;
+                    }
+                }
+            }
+            '!' if chars.get(i + 1) == Some(&'[') => match parse_link(&chars, i + 1) {
+                Some((alt, url, end)) => {
+                    match safe_url(&url, true) {
+                        Some(src) => out.push_str(&format!("<img src=\"{}\" alt=\"{}\">", src, escape_html(&unescape_markdown(&alt)))),
+                        None => out.push_str(&escape_html(&unescape_markdown(&alt))),
+                    }
+                    i = end;
+                }
+                None => {
+                    out.push('!');
+                    i += 1;
+                }
+            },
+            '[' => match parse_link(&chars, i) {
+                Some((label, url, end)) => {
+                    let label = render_inline(&label);
+                    match safe_url(&url, false) {
+                        Some(href) => out.push_str(&format!("<a href=\"{}\">{}</a>", href, label)),
+                        None => out.push_str(&label),
+                    }
+                    i = end;
+                }
+                None => {
+                    out.push('[');
+                    i += 1;
+                }
+            },
+            '<' => {
+                let end = chars[i + 1..].iter().position(|c| *c == '>' || c.is_whitespace()).map(|p| i + 1 + p);
+                let url: Option<String> = end.filter(|&e| chars[e] == '>').map(|e| chars[i + 1..e].iter().collect());
+                match url.as_deref().filter(|u| u.contains(':')).and_then(|u| safe_url(u, false)) {
+                    Some(href) => {
+                        let label = escape_html(url.as_deref().unwrap_or("").trim_start_matches("mailto:"));
+                        out.push_str(&format!("<a href=\"{}\">{}</a>", href, label));
+                        i = end.unwrap_or(i) + 1;
+                    }
+                    None => {
+                        out.push_str("&lt;");
+                        i += 1;
+                    }
+                }
+            }
+            '*' | '_' | '~' => {
+                let n = run_length(&chars, i, c);
+                match parse_emphasis(&chars, i, c, n) {
+                    Some((html, end)) => {
+                        out.push_str(&html);
+                        i = end;
+                    }
+                    None => {
+                        for _ in 0..n {
+                            out.push(c);
+                        }
+                        i += n;
+                    }
+                }
+            }
+            _ => {
+                push_escaped(&mut out, c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn run_length(chars: &[char], start: usize, ch: char) -> usize {
+    chars[start..].iter().take_while(|c| **c == ch).count()
+}
+
+fn find_code_end(chars: &[char], from: usize, n: usize) -> Option<usize> {
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == '`' {
+            let run = run_length(chars, i, '`');
+            if run == n {
+                return Some(i);
+            }
+            i += run;
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+// 解析 *强调*、**加粗**、***加粗斜体*** 与 ~~删除线~~，返回 (HTML, 结束位置)
+fn parse_emphasis(chars: &[char], start: usize, ch: char, n: usize) -> Option<(String, usize)> {
+    if (ch == '~' && n != 2) || n > 3 {
+        return None;
+    }
+    let open_end = start + n;
+    if chars.get(open_end).is_none_or(|c| c.is_whitespace()) {
+        return None;
+    }
+    if ch == '_' && start > 0 && chars[start - 1].is_alphanumeric() {
+        return None;
+    }
+
+    let mut i = open_end;
+    while i < chars.len() {
+        if chars[i] == '\\' {
+            i += 2;
+            continue;
+        }
+        if chars[i] == '`' {
+            let run = run_length(chars, i, '`');
+            i = find_code_end(chars, i + run, run).map(|e| e + run).unwrap_or(i + run);
+            continue;
+        }
+        if chars[i] != ch {
+            i += 1;
+            continue;
+        }
+        let run = run_length(chars, i, ch);
+        let closes = run == n
+            && i > open_end
+            && !chars[i - 1].is_whitespace()
+            && !(ch == '_' && chars.get(i + run).is_some_and(|c| c.is_alphanumeric()));
+        if closes {
+            let inner = render_inline(&chars[open_end..i].iter().collect::<String>());
+            let html = match (ch, n) {
+                ('~', _) => format!("<del>{}</del>", inner),
+                (_, 1) => format!("<em>{}</em>", inner),
+                (_, 2) => format!("<strong>{}</strong>", inner),
+                _ => format!("<strong><em>{}</em></strong>", inner),
+            };
+            return Some((html, i + run));
+        }
+        i += run;
+    }
+    None
+}
+
+// 解析 [文本](地址 "标题")，返回 (文本, 地址, 结束位置)
+fn parse_link(chars: &[char], open: usize) -> Option<(String, String, usize)> {
+    let mut depth = 0;
+    let mut i = open;
+    let close = loop {
+        match chars.get(i)? {
+            '\\' => i += 1,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    break i;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    };
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut j = close + 1;
+    let end = loop {
+        match chars.get(j)? {
+            '\\' => j += 1,
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    break j;
+                }
+            }
+            _ => {}
+        }
+        j += 1;
+    };
+
+    let label: String = chars[open + 1..close].iter().collect();
+    let target: String = chars[close + 2..end].iter().collect();
+    let target = target.trim();
+    let url = match target.strip_prefix('<') {
+        Some(rest) => rest.split('>').next().unwrap_or("").to_string(),
+        None => target.split_whitespace().next().unwrap_or("").to_string(),
+    };
+    Some((label, url, end + 1))
+}
+
+// 只允许安全的协议，返回已转义的属性值
+fn safe_url(url: &str, is_image: bool) -> Option<String> {
+    let url = unescape_markdown(url.trim());
+    let lower = url.to_ascii_lowercase();
+    let scheme_end = lower.find(':');
+    let path_start = lower.find(['/', '?', '#']).unwrap_or(lower.len());
+
+    let allowed = match scheme_end {
+        Some(end) if end < path_start => {
+            let scheme = &lower[..end];
+            match scheme {
+                "http" | "https" | "file" => true,
+                "mailto" | "tel" | "ftp" => !is_image,
+                "data" => {
+                    is_image
+                        && ["data:image/png", "data:image/jpeg", "data:image/gif", "data:image/webp", "data:image/bmp"]
+                            .iter()
+                            .any(|p| lower.starts_with(p))
+                }
+                // Windows 盘符路径
+                s => s.len() == 1 && s.chars().all(|c| c.is_ascii_alphabetic()),
+            }
+        }
+        _ => true,
+    };
+    allowed.then(|| escape_html(&url))
+}
+
+fn unescape_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(&next) = chars.peek().filter(|n| n.is_ascii_punctuation()) {
+                out.push(next);
+                chars.next();
+                continue;
+            }
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_md(html: &str) -> String {
+        html_to_markdown(html, &|id| Some(format!("clipboard_images/{}.png", id)))
+    }
+
+    #[test]
+    fn converts_browser_fragment() {
+        let html = r#"Version:0.9
+<html><body><!--StartFragment--><h2 id="x">Install <code>cargo</code></h2>
+<p>Run the <a href="https://doc.rust-lang.org/cargo/">Cargo book</a> steps, <strong>then </strong><em>check</em> 2*3.</p>
+<ul><li>One<ul><li>Nested</li></ul></li><li>Two</li></ul>
+<ol start="3"><li><p>Three</p></li></ol>
+<pre><code class="language-rust">fn main() {
+    println!("hi");
+}</code></pre>
+<p><img data-image-id="abc123" src="https://x.com/a.png" alt="logo"><br>Next line</p>
+<table><thead><tr><th>Name</th><th>Value</th></tr></thead><tbody><tr><td>a|b</td><td colspan="1"><b>1</b></td></tr><tr><td>only</td></tr></tbody></table>
+<script>alert(1)</script><!--EndFragment--></body></html>"#;
+        let expected = "## Install `cargo`\n\n\
+Run the [Cargo book](https://doc.rust-lang.org/cargo/) steps, **then** *check* 2\\*3.\n\n\
+- One\n  - Nested\n- Two\n\n\
+3. Three\n\n\
+```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n\n\
+![logo](clipboard_images/abc123.png)  \nNext line\n\n\
+| Name | Value |\n| --- | --- |\n| a\\|b | **1** |\n| only |  |";
+        assert_eq!(to_md(html), expected);
+    }
+
+    #[test]
+    fn converts_office_fragment() {
+        let html = r#"<html xmlns:o="urn:schemas-microsoft-com:office:office"><head><style>p.MsoNormal{margin:0}</style></head><body lang=ZH-CN>
+<!--StartFragment--><p class=MsoNormal><b><span lang=EN-US style='font-size:14.0pt'>Report<o:p></o:p></span></b></p>
+<p class=MsoListParagraph style='text-indent:-18.0pt;mso-list:l0 level1 lfo1'><![if !supportLists]><span lang=EN-US style='mso-list:Ignore'>1.<span style='font:7.0pt "Times New Roman"'>&nbsp;&nbsp; </span></span><![endif]>First&nbsp;item</p>
+<p class=MsoListParagraph style='mso-list:l0 level2 lfo1'><![if !supportLists]><span style='font-family:Symbol;mso-list:Ignore'>·<span>&nbsp; </span></span><![endif]>Sub item</p>
+<p class=MsoNormal>&nbsp;</p>
+<table class=MsoTableGrid border=1><tr><td width=100><p class=MsoNormal>A<o:p></o:p></p></td><td><p class=MsoNormal><i>B</i></p></td></tr></table>
+<p class=MsoNormal><span style='font-weight:bold'>Total:</span> 5</p><!--EndFragment--></body></html>"#;
+        let expected = "**Report**\n\n1. First item\n    - Sub item\n\n| A | *B* |\n| --- | --- |\n\n**Total:** 5";
+        assert_eq!(to_md(html), expected);
+    }
+
+    #[test]
+    fn converts_ide_fragment() {
+        let vscode = r#"<div style="color: #cccccc;background-color: #1f1f1f;font-family: Consolas, 'Courier New', monospace;font-weight: normal;font-size: 14px;line-height: 19px;white-space: pre;"><div><span style="color: #569cd6;">let</span><span style="color: #cccccc;"> </span><span style="color: #9cdcfe;">x</span><span style="color: #cccccc;"> = </span><span style="color: #b5cea8;">1</span>;</div><div><br></div><div><span>  x * 2 &lt; 3</span></div></div>"#;
+        assert_eq!(to_md(vscode), "```\nlet x = 1;\n\n  x * 2 < 3\n```");
+
+        let docs = r#"<b style="font-weight:normal;" id="docs-internal-guid-1"><p dir="ltr"><span style="font-weight:700;">Bold</span><span> and </span><span style="font-style:italic;">italic</span></p><p><span># not a heading</span></p></b>"#;
+        assert_eq!(to_md(docs), "**Bold** and *italic*\n\n\\# not a heading");
+    }
+
+    #[test]
+    fn renders_markdown_to_html() {
+        let md = "# Title\n\nSome **bold**, *em*, `a<b>` and [link](https://a.com/x_(y)).  \nNext\n\n\
+- one\n- two\n  1. nested\n\n\
+| Left | Right |\n|:--|--:|\n| a \\| b | c |\n\n\
+```js\nif (a < b) {}\n```\n\n> quote\n\n---";
+        let expected = "<h1>Title</h1>\n\
+<p>Some <strong>bold</strong>, <em>em</em>, <code>a&lt;b&gt;</code> and <a href=\"https://a.com/x_(y)\">link</a>.<br>\nNext</p>\n\
+<ul>\n<li>one</li>\n<li>two\n<ol>\n<li>nested</li>\n</ol></li>\n</ul>\n\
+<table>\n<thead>\n<tr><th style=\"text-align:left\">Left</th><th style=\"text-align:right\">Right</th></tr>\n</thead>\n<tbody>\n\
+<tr><td style=\"text-align:left\">a | b</td><td style=\"text-align:right\">c</td></tr>\n</tbody>\n</table>\n\
+<pre><code class=\"language-js\">if (a &lt; b) {}</code></pre>\n\
+<blockquote>\n<p>quote</p>\n</blockquote>\n<hr>";
+        assert_eq!(markdown_to_html(md), expected);
+    }
+
+    #[test]
+    fn sanitizes_markdown_output() {
+        let html = markdown_to_html("<script>alert(1)</script> [x](javascript:alert(1)) ![i](data:image/svg+xml,x) <https://a.com> snake_case_name");
+        assert_eq!(
+            html,
+            "<p>&lt;script&gt;alert(1)&lt;/script&gt; x i <a href=\"https://a.com\">https://a.com</a> snake_case_name</p>"
+        );
+    }
+}
@@ -6,9 +6,15 @@ pub mod image_http_server;
 pub mod system;
 pub mod text;
 pub mod html;
+pub mod http;
+pub mod rtf;
+mod html_dom;
+pub mod markdown;
+pub mod html_sanitize;
+pub mod diff;
 
 pub use screen::init_screen_utils;
 pub use system::get_text_scale_factor;
 pub use text::{truncate_string, truncate_around_keyword};
-pub use html::truncate_html;
+pub use html::{truncate_html, add_image_id_to_img_tags};
 
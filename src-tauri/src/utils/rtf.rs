@@ -0,0 +1,272 @@
+// RTF 处理工具函数
+
+use encoding_rs::{Encoding, WINDOWS_1252};
+use std::collections::HashMap;
+
+// 不输出文本的目标组（字体表、颜色表、图片等）
+const SKIP_DESTINATIONS: &[&str] = &[
+    "fonttbl", "colortbl", "stylesheet", "info", "pict", "header", "footer",
+    "headerl", "headerr", "footerl", "footerr", "listtable", "listoverridetable",
+    "rsidtbl", "themedata", "colorschememapping", "latentstyles", "datastore",
+    "generator", "xmlnstbl", "object", "fldinst",
+];
+
+#[derive(Clone, Copy)]
+struct GroupState {
+    skip: bool,
+    // \ucN：\u 之后需要跳过的替代字符数
+    uc: usize,
+    // 当前字体（\fN），决定 \'xx 字节的字符集
+    font: Option<i32>,
+}
+
+// \fcharsetN 对应的 Windows 代码页（0/1 表示使用文档的 \ansicpg）
+fn charset_code_page(charset: i32) -> Option<u32> {
+    match charset {
+        128 => Some(932),
+        129 => Some(949),
+        134 => Some(936),
+        136 => Some(950),
+        161 => Some(1253),
+        162 => Some(1254),
+        163 => Some(1258),
+        177 => Some(1255),
+        178 => Some(1256),
+        186 => Some(1257),
+        204 => Some(1251),
+        222 => Some(874),
+        238 => Some(1250),
+        _ => None,
+    }
+}
+
+fn code_page_encoding(code_page: u32) -> &'static Encoding {
+    let label = match code_page {
+        65001 => "utf-8".to_string(),
+        936 => "gbk".to_string(),
+        932 => "shift_jis".to_string(),
+        949 => "euc-kr".to_string(),
+        950 => "big5".to_string(),
+        10000 => "macintosh".to_string(),
+        _ => format!("windows-{}", code_page),
+    };
+    Encoding::for_label(label.as_bytes()).unwrap_or(WINDOWS_1252)
+}
+
+// 输出缓冲：连续的 \'xx 字节先暂存，遇到其它字符或编码变化时按代码页整体解码（多字节字符集需要）
+struct TextOut {
+    text: String,
+    bytes: Vec<u8>,
+    encoding: &'static Encoding,
+}
+
+impl TextOut {
+    fn push(&mut self, c: char) {
+        self.flush();
+        self.text.push(c);
+    }
+
+    fn push_byte(&mut self, byte: u8, encoding: &'static Encoding) {
+        if encoding != self.encoding {
+            self.flush();
+            self.encoding = encoding;
+        }
+        self.bytes.push(byte);
+    }
+
+    fn flush(&mut self) {
+        if !self.bytes.is_empty() {
+            let (decoded, _, _) = self.encoding.decode(&self.bytes);
+            self.text.push_str(&decoded);
+            self.bytes.clear();
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        self.text.trim_end().to_string()
+    }
+}
+
+// 将 RTF 转换为纯文本（用于没有文本格式的剪贴板内容）
+pub fn rtf_to_plain_text(rtf: &str) -> String {
+    let chars: Vec<char> = rtf.chars().collect();
+    let mut out = TextOut { text: String::new(), bytes: Vec::new(), encoding: WINDOWS_1252 };
+    let mut stack: Vec<GroupState> = Vec::new();
+    let mut state = GroupState { skip: false, uc: 1, font: None };
+    let mut code_page = 1252u32;
+    let mut default_font: Option<i32> = None;
+    let mut font_charsets: HashMap<i32, i32> = HashMap::new();
+    let mut pending_skip = 0usize;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '{' => {
+                stack.push(state);
+                i += 1;
+            }
+            '}' => {
+                state = stack.pop().unwrap_or(state);
+                pending_skip = 0;
+                i += 1;
+            }
+            '\\' => {
+                i += 1;
+                let Some(&next) = chars.get(i) else { break };
+
+                if next.is_ascii_alphabetic() {
+                    let start = i;
+                    while i < chars.len() && chars[i].is_ascii_alphabetic() {
+                        i += 1;
+                    }
+                    let word: String = chars[start..i].iter().collect();
+
+                    let param_start = i;
+                    if i < chars.len() && chars[i] == '-' {
+                        i += 1;
+                    }
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                    let param: Option<i32> = chars[param_start..i].iter().collect::<String>().parse().ok();
+
+                    if i < chars.len() && chars[i] == ' ' {
+                        i += 1;
+                    }
+
+                    // 字体和代码页信息在字体表（跳过的组）中也需要记录
+                    match word.as_str() {
+                        "ansicpg" => code_page = param.map_or(code_page, |p| p as u32),
+                        "deff" => default_font = param,
+                        "f" => state.font = param,
+                        "fcharset" => {
+                            if let (Some(font), Some(charset)) = (state.font, param) {
+                                font_charsets.insert(font, charset);
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    if SKIP_DESTINATIONS.contains(&word.as_str()) {
+                        state.skip = true;
+                        continue;
+                    }
+                    if state.skip {
+                        continue;
+                    }
+
+                    match word.as_str() {
+                        "par" | "line" | "row" => out.push('\n'),
+                        "tab" | "cell" => out.push('\t'),
+                        "emdash" => out.push('—'),
+                        "endash" => out.push('–'),
+                        "bullet" => out.push('•'),
+                        "lquote" => out.push('‘'),
+                        "rquote" => out.push('’'),
+                        "ldblquote" => out.push('“'),
+                        "rdblquote" => out.push('”'),
+                        "uc" => state.uc = param.unwrap_or(1).max(0) as usize,
+                        "u" => {
+                            if let Some(value) = param {
+                                let code = if value < 0 { value + 65536 } else { value } as u32;
+                                if let Some(ch) = char::from_u32(code) {
+                                    out.push(ch);
+                                }
+                                pending_skip = state.uc;
+                            }
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                match next {
+                    '\'' => {
+                        let hex: String = chars.iter().skip(i + 1).take(2).collect();
+                        i += 1 + hex.len();
+                        if pending_skip > 0 {
+                            pending_skip -= 1;
+                        } else if !state.skip {
+                            // 按当前字体的字符集（或文档的 \ansicpg）解码
+                            if let Ok(byte) = u8::from_str_radix(&hex, 16) {
+                                let font_page = state.font.or(default_font)
+                                    .and_then(|f| font_charsets.get(&f))
+                                    .and_then(|&charset| charset_code_page(charset));
+                                out.push_byte(byte, code_page_encoding(font_page.unwrap_or(code_page)));
+                            }
+                        }
+                    }
+                    '*' => {
+                        state.skip = true;
+                        i += 1;
+                    }
+                    '~' => {
+                        if !state.skip {
+                            out.push('\u{00A0}');
+                        }
+                        i += 1;
+                    }
+                    '\n' | '\r' => {
+                        if !state.skip {
+                            out.push('\n');
+                        }
+                        i += 1;
+                    }
+                    _ => {
+                        // 转义字符：\\ \{ \} 以及 \- \_ 等
+                        if !state.skip {
+                            match next {
+                                '\\' | '{' | '}' => out.push(next),
+                                '_' => out.push('-'),
+                                _ => {}
+                            }
+                        }
+                        i += 1;
+                    }
+                }
+            }
+            '\r' | '\n' => i += 1,
+            _ => {
+                if pending_skip > 0 {
+                    pending_skip -= 1;
+                } else if !state.skip {
+                    out.push(c);
+                }
+                i += 1;
+            }
+        }
+    }
+
+    out.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_text_and_skips_tables() {
+        let rtf = r"{\rtf1\ansi\deff0{\fonttbl{\f0 Calibri;}}{\colortbl;\red255\green0\blue0;}
+\f0\fs22 Hello \b bold\b0  world\par
+Second\tab line\par}";
+        assert_eq!(rtf_to_plain_text(rtf), "Hello bold world\nSecond\tline");
+    }
+
+    #[test]
+    fn decodes_unicode_and_escapes() {
+        let rtf = r"{\rtf1\ansi\uc1 \u20320?\u22909? \{x\} \'e9{\*\generator Riched20;}}";
+        assert_eq!(rtf_to_plain_text(rtf), "你好 {x} é");
+    }
+
+    #[test]
+    fn decodes_code_page_bytes() {
+        let rtf = r"{\rtf1\ansi\ansicpg936\deff0{\fonttbl{\f0\fnil\fcharset134 \'cb\'ce\'cc\'e5;}}\f0 \'c4\'e3\'ba\'c3 ok\par}";
+        assert_eq!(rtf_to_plain_text(rtf), "你好 ok");
+
+        // 字体字符集优先于文档代码页
+        let rtf = r"{\rtf1\ansi\ansicpg1252{\fonttbl{\f0 Arial;}{\f1\fcharset204 Arial;}}\f0 \'e9 \f1 \'c4\'e0}";
+        assert_eq!(rtf_to_plain_text(rtf), "é Да");
+    }
+}
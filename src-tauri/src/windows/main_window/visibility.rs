@@ -1,8 +1,9 @@
 use super::state::{set_window_state, WindowState};
-use tauri::{AppHandle, WebviewWindow};
+use tauri::{AppHandle, Manager, WebviewWindow};
 
 // 显示主窗口
 pub fn show_main_window(window: &WebviewWindow) {
+    let _ = crate::services::system::save_current_focus(window.app_handle().clone());
     let state = super::state::get_window_state();
 
     if state.is_snapped && state.is_hidden {
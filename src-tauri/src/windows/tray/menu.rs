@@ -146,6 +146,12 @@ pub async fn show_tray_menu(app: AppHandle) -> Result<(), String> {
     
     let hotkeys_label = if settings.hotkeys_enabled { "禁用快捷键" } else { "启用快捷键" };
     let monitor_label = if settings.clipboard_monitor { "禁用剪贴板监听" } else { "启用剪贴板监听" };
+    let queue_status = crate::services::paste::queue::status();
+    let queue_label = if queue_status.active {
+        format!("停止粘贴队列 ({})", queue_status.length)
+    } else {
+        "开始粘贴队列".to_string()
+    };
     
     let items = vec![
         menu_item_with_state("toggle", "显示/隐藏", Some("ti ti-app-window"), is_force_update),
@@ -166,6 +172,7 @@ pub async fn show_tray_menu(app: AppHandle) -> Result<(), String> {
         separator_item(),
         menu_item_with_state("toggle-hotkeys", hotkeys_label, Some("ti ti-keyboard"), is_force_update),
         menu_item_with_state("toggle-clipboard-monitor", monitor_label, Some("ti ti-clipboard"), is_force_update),
+        menu_item_with_state("toggle-paste-queue", &queue_label, Some("ti ti-list-numbers"), is_force_update),
         separator_item(),
         menu_item_with_state("low-memory-mode", "进入低占用模式", Some("ti ti-leaf"), is_force_update),
         separator_item(),
@@ -242,6 +249,9 @@ fn handle_tray_menu_selection(app: &AppHandle, selected_id: &str) {
                 eprintln!("切换剪贴板监听状态失败: {}", e);
             }
         }
+        "toggle-paste-queue" => {
+            crate::services::paste::queue::toggle();
+        }
         "low-memory-mode" => {
             if let Err(e) = crate::services::low_memory::enter_low_memory_mode(app) {
                 eprintln!("进入低占用模式失败: {}", e);
@@ -50,8 +50,7 @@ fn build_clipboard_items(app: &AppHandle) -> Result<Vec<MenuItem<tauri::Wry>>, S
     let items = query_clipboard_items(QueryParams {
         offset: current_page * PAGE_SIZE as i64,
         limit: PAGE_SIZE as i64,
-        search: None,
-        content_type: None,
+        ..Default::default()
     })?
     .items;
 
@@ -178,6 +177,22 @@ fn create_menu(app: &AppHandle) -> Result<Menu<tauri::Wry>, String> {
     .map_err(|e| e.to_string())?;
     menu.append(&toggle_format).map_err(|e| e.to_string())?;
 
+    let queue_status = crate::services::paste::queue::status();
+    let queue_label = if queue_status.active {
+        format!("停止粘贴队列 ({})", queue_status.length)
+    } else {
+        "开始粘贴队列".to_string()
+    };
+    let toggle_queue = MenuItem::with_id(
+        app,
+        "toggle-paste-queue",
+        &queue_label,
+        true,
+        None::<&str>,
+    )
+    .map_err(|e| e.to_string())?;
+    menu.append(&toggle_queue).map_err(|e| e.to_string())?;
+
     let sep3 = PredefinedMenuItem::separator(app).map_err(|e| e.to_string())?;
     menu.append(&sep3).map_err(|e| e.to_string())?;
 
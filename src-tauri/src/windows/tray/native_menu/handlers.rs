@@ -32,6 +32,10 @@ pub fn handle_native_menu_event(app: &AppHandle, event: &MenuEvent) {
             }
             let _ = update_native_menu(app);
         }
+        "toggle-paste-queue" => {
+            crate::services::paste::queue::toggle();
+            let _ = update_native_menu(app);
+        }
         "restart" => {
             app.restart();
         }
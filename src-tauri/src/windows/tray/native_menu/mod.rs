@@ -7,3 +7,4 @@ mod builder;
 pub use pagination::{is_menu_visible, set_menu_visible, scroll_page};
 pub use handlers::handle_native_menu_event;
 pub use builder::{create_native_menu, update_native_menu};
+pub use utils::format_item_label;
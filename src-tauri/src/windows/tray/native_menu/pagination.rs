@@ -84,8 +84,7 @@ pub fn update_clipboard_items_text() -> Result<(), String> {
     let items = query_clipboard_items(QueryParams {
         offset: current_page * PAGE_SIZE as i64,
         limit: PAGE_SIZE as i64,
-        search: None,
-        content_type: None,
+        ..Default::default()
     })?.items;
 
     let menu_items = state::get_menu_items();
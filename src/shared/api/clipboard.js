@@ -3,11 +3,12 @@ import { restoreLastFocus } from '@shared/api/window'
 // 获取剪贴板历史列表
 export async function getClipboardHistory(params = {}) {
   try {
-    const { offset = 0, limit = 50, search, contentType } = params
+    const { offset = 0, limit = 50, search, contentType, tags } = params
 
     const invokeParams = { offset, limit }
     if (search) invokeParams.search = search
     if (contentType) invokeParams.contentType = contentType
+    if (tags?.length) invokeParams.tags = tags
 
     return await invoke('get_clipboard_history', invokeParams)
   } catch (error) {
@@ -32,11 +33,11 @@ export async function getClipboardTotalCount() {
   }
 }
 
-// 粘贴剪贴板项
-export async function pasteClipboardItem(clipboardId, format = null) {
+// 粘贴剪贴板项（extraParams 为额外的粘贴参数，如 transform_chain）
+export async function pasteClipboardItem(clipboardId, format = null, extraParams = {}) {
   try {
     await restoreLastFocus()
-    const params = { clipboard_id: clipboardId }
+    const params = { clipboard_id: clipboardId, ...extraParams }
     if (format) {
       params.format = format
     }
@@ -195,9 +196,35 @@ export async function saveImageFromPath(filePath) {
   return await invoke('save_image_from_path', { filePath })
 }
 
+// 设置剪贴板项的标题和备注（传空字符串清除）
+export async function updateClipboardItemNote(id, title, note) {
+  await invoke('update_clipboard_item_note', { id, title, note })
+  await invoke('emit_clipboard_updated')
+}
+
 // 切换剪贴板项置顶状态
 export async function togglePinClipboardItem(id) {
   const isPinned = await invoke('toggle_pin_clipboard_item', { id })
   await invoke('emit_clipboard_updated')
   return isPinned
 }
+
+// 批量删除剪贴板项（移到回收站，后端统一发送更新事件）
+export async function deleteClipboardItems(ids) {
+  return await invoke('delete_clipboard_items', { ids })
+}
+
+// 批量置顶/取消置顶剪贴板项
+export async function setClipboardItemsPinned(ids, pinned) {
+  return await invoke('set_clipboard_items_pinned', { ids, pinned })
+}
+
+// 批量添加剪贴板项到收藏分组
+export async function addClipboardItemsToFavorites(ids, groupName) {
+  return await invoke('add_clipboard_items_to_favorites', { ids, groupName })
+}
+
+// 导出选中的剪贴板项为 zip 文件（items.json 及引用的图片、文件快照）
+export async function exportClipboardItems(ids, targetPath) {
+  return await invoke('export_clipboard_items', { ids, targetPath })
+}
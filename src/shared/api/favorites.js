@@ -3,12 +3,13 @@ import { restoreLastFocus } from '@shared/api/window'
 
 // 分页查询收藏列表
 export async function getFavoritesHistory(params = {}) {
-  const { offset = 0, limit = 50, groupName, search, contentType } = params
+  const { offset = 0, limit = 50, groupName, search, contentType, tags } = params
 
   const invokeParams = { offset, limit }
   if (groupName && groupName !== '全部') invokeParams.groupName = groupName
   if (search) invokeParams.search = search
   if (contentType) invokeParams.contentType = contentType
+  if (tags?.length) invokeParams.tags = tags
 
   return await invoke('get_favorites_history', invokeParams)
 }
@@ -34,6 +35,12 @@ export async function updateFavorite(id, title, content, groupName) {
   return result
 }
 
+// 设置收藏备注（传空字符串清除）
+export async function updateFavoriteNote(id, note) {
+  await invoke('update_quick_text_note', { id: String(id), note })
+  await invoke('emit_quick_texts_updated')
+}
+
 // 删除收藏
 export async function deleteFavorite(id) {
   return await invoke('delete_quick_text', { id })
@@ -48,11 +55,11 @@ export async function moveFavoriteItemById(groupName, fromId, toId) {
   })
 }
 
-// 粘贴收藏内容
-export async function pasteFavorite(id, format = null) {
+// 粘贴收藏内容（extraParams 为额外的粘贴参数，如 transform_chain）
+export async function pasteFavorite(id, format = null, extraParams = {}) {
   try {
     await restoreLastFocus()
-    const params = { favorite_id: id }
+    const params = { favorite_id: id, ...extraParams }
     if (format) {
       params.format = format
     }
@@ -82,3 +89,13 @@ export async function pasteFavorite(id, format = null) {
   }
 }
 
+
+// 批量删除收藏（移到回收站）
+export async function deleteFavorites(ids) {
+  return await invoke('delete_quick_texts', { ids: ids.map(String) })
+}
+
+// 导出选中的收藏为 zip 文件（items.json 及引用的图片、文件快照）
+export async function exportFavorites(ids, targetPath) {
+  return await invoke('export_quick_texts', { ids: ids.map(String), targetPath })
+}
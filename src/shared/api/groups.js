@@ -1,13 +1,13 @@
 import { invoke } from '@tauri-apps/api/core'
 
-// 获取所有分组
+// 获取所有分组（树形，子分组在 children 中）
 export async function getGroups() {
   return await invoke('get_groups')
 }
 
 // 添加分组
-export async function addGroup(name, icon = 'ti ti-folder', color = '#dc2626') {
-  return await invoke('add_group', { name, icon, color })
+export async function addGroup(name, icon = 'ti ti-folder', color = '#dc2626', parent = null) {
+  return await invoke('add_group', { name, icon, color, parent })
 }
 
 // 更新分组
@@ -20,6 +20,16 @@ export async function updateGroup(oldName, newName, newIcon, newColor) {
   })
 }
 
+// 移动分组到新的父分组下（parent 为 null 时移到顶层）
+export async function moveGroup(name, parent = null) {
+  return await invoke('move_group', { name, parent })
+}
+
+// 合并分组（源分组的收藏项和子分组并入目标分组）
+export async function mergeGroups(source, target) {
+  return await invoke('merge_groups', { source, target })
+}
+
 // 删除分组
 export async function deleteGroup(name) {
   return await invoke('delete_group', { name })
@@ -30,8 +40,44 @@ export async function moveFavoriteToGroup(id, groupName) {
   return await invoke('move_quick_text_to_group', { id, groupName })
 }
 
+// 批量移动收藏项到分组
+export async function moveFavoritesToGroup(ids, groupName) {
+  return await invoke('move_quick_texts_to_group', { ids: ids.map(String), groupName })
+}
+
 // 从剪贴板添加到分组
 export async function addClipboardToGroup(index, groupName) {
   return await invoke('add_clipboard_to_group', { index, groupName })
 }
 
+// 获取所有智能分组（与 getGroups 分开返回，ID 与普通分组互不相关）
+export async function getSmartGroups() {
+  return await invoke('get_smart_groups')
+}
+
+// 添加智能分组（保存的搜索），query: { scope: 'history' | 'favorites', search, contentType, sourceApp, withinDays, ... }
+export async function addSmartGroup(name, icon = 'ti ti-filter', color = '#dc2626', query = {}) {
+  return await invoke('add_smart_group', { name, icon, color, query: toSmartQuery(query) })
+}
+
+// 更新智能分组
+export async function updateSmartGroup(id, name, icon, color, query = {}) {
+  return await invoke('update_smart_group', { id, name, icon, color, query: toSmartQuery(query) })
+}
+
+// 删除智能分组
+export async function deleteSmartGroup(id) {
+  return await invoke('delete_smart_group', { id })
+}
+
+// 分页获取智能分组匹配的项目，返回 { scope, result }
+export async function getSmartGroupItems(id, offset = 0, limit = 50) {
+  return await invoke('get_smart_group_items', { id, offset, limit })
+}
+
+// 查询条件字段转换为后端的 snake_case
+function toSmartQuery(query) {
+  return Object.fromEntries(
+    Object.entries(query).map(([key, value]) => [key.replace(/[A-Z]/g, c => '_' + c.toLowerCase()), value])
+  )
+}
@@ -1,6 +1,9 @@
 export * from './clipboard'
 export * from './favorites'
 export * from './groups'
+export * from './tags'
+export * from './revisions'
+export * from './trash'
 export * from './textEditor'
 export * from './window'
 export * from './settings'
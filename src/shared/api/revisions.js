@@ -0,0 +1,16 @@
+import { invoke } from '@tauri-apps/api/core'
+
+// 获取条目的历史版本（itemType: 'clipboard' | 'favorite'），最新的在前
+export async function getItemRevisions(itemType, itemId) {
+  return await invoke('get_item_revisions', { itemType, itemId: String(itemId) })
+}
+
+// 对比两个历史版本，toId 为空时与当前内容对比
+export async function diffItemRevisions(fromId, toId = null) {
+  return await invoke('diff_item_revisions', { fromId, toId })
+}
+
+// 恢复到指定历史版本
+export async function restoreItemRevision(revisionId) {
+  return await invoke('restore_item_revision', { revisionId })
+}
@@ -0,0 +1,26 @@
+import { invoke } from '@tauri-apps/api/core'
+
+// 获取所有标签（含剪贴板和收藏中的使用次数）
+export async function getTags() {
+  return await invoke('get_tags')
+}
+
+// 为条目添加标签（itemType: 'clipboard' | 'favorite'），返回条目当前的标签
+export async function addItemTags(itemType, itemId, tags) {
+  return await invoke('add_item_tags', { itemType, itemId: String(itemId), tags })
+}
+
+// 移除条目的标签，返回条目当前的标签
+export async function removeItemTags(itemType, itemId, tags) {
+  return await invoke('remove_item_tags', { itemType, itemId: String(itemId), tags })
+}
+
+// 重命名标签（新名称已存在时合并）
+export async function renameTag(oldName, newName) {
+  return await invoke('rename_tag', { oldName, newName })
+}
+
+// 删除标签
+export async function deleteTag(name) {
+  return await invoke('delete_tag', { name })
+}
@@ -0,0 +1,26 @@
+import { invoke } from '@tauri-apps/api/core'
+
+// 分页获取回收站中的条目
+export async function getTrashItems(offset = 0, limit = 50) {
+  return await invoke('get_trash_items', { offset, limit })
+}
+
+// 从回收站恢复条目（itemType: 'clipboard' | 'favorite'）
+export async function restoreTrashItem(itemType, itemId) {
+  return await invoke('restore_trash_item', { itemType, itemId: String(itemId) })
+}
+
+// 彻底删除回收站中的条目
+export async function purgeTrashItem(itemType, itemId) {
+  return await invoke('purge_trash_item', { itemType, itemId: String(itemId) })
+}
+
+// 清空回收站
+export async function emptyTrash() {
+  return await invoke('empty_trash')
+}
+
+// 撤销最近一次删除，没有可撤销的操作时返回 null
+export async function undoLastDelete() {
+  return await invoke('undo_last_delete')
+}
@@ -49,12 +49,31 @@ export const defaultSettings = {
   toggleClipboardMonitorShortcut: 'Ctrl+Shift+Z',
   togglePasteWithFormatShortcut: 'Ctrl+Shift+X',
   pastePlainTextShortcut: '',
+  pasteTransformChains: [],
+  pasteQueueShortcut: '',
+  pasteQueueOrder: 'fifo',
+  pasteQueueAutoStop: true,
+  pasteQueueCaptureOnCopy: true,
+  typePasteSpeed: 50,
+  typePasteNewlineMode: 'enter',
+  typePasteCancelShortcut: 'Escape',
+  typePasteTargetApps: [],
+  typePasteSourceApps: [],
   
   // 剪贴板设置
   clipboardMonitor: true,
   ignoreDuplicates: true,
   saveImages: true,
   imagePreview: false,
+  fetchRemoteImages: false,
+  remoteImageMaxBytes: 5242880,
+  remoteImageTotalMaxBytes: 20971520,
+  remoteImageConcurrency: 2,
+  remoteImageTimeoutSecs: 10,
+  fileSnapshotEnabled: false,
+  fileSnapshotMaxBytes: 10485760,
+  fileSnapshotTotalMaxBytes: 52428800,
+  trashRetentionDays: 30,
   autoScrollToTopOnShow: false,
   autoClearSearch: false,
   windowPositionMode: 'smart',
@@ -65,8 +84,12 @@ export const defaultSettings = {
   edgeHideOffset: 3,
   autoFocusSearch: false,
   pasteWithFormat: true,
+  pasteHtmlSanitizeLevel: 'original',
   pasteShortcutMode: 'ctrl_v',
   pasteToTop: false,
+  restoreClipboardAfterPaste: false,
+  restoreClipboardDelayMs: 500,
+  pasteProfiles: [],
   showBadges: true,
   showSourceIcon: true,
   
@@ -120,6 +143,14 @@ export const defaultSettings = {
   appFilterMode: 'blacklist',
   appFilterList: [],
   
+  // 链接预览
+  linkPreviewEnabled: false,
+  linkPreviewTimeoutSecs: 5,
+  linkPreviewMaxPageBytes: 524288,
+  linkPreviewMaxFaviconBytes: 65536,
+  linkPreviewDomainMode: 'blacklist',
+  linkPreviewDomainList: [],
+  
   // 保存的窗口状态
   savedWindowPosition: null,
   savedWindowSize: null,
@@ -99,11 +99,20 @@ function App() {
           favoritesTabRef.current.executePlainTextPaste();
         }
       });
+      const unlisten4 = await listen('paste-transform-selected', event => {
+        const tabRef = activeTab === 'clipboard' ? clipboardTabRef : activeTab === 'favorites' ? favoritesTabRef : null;
+        if (tabRef?.current?.executeTransformPaste) {
+          tabRef.current.executeTransformPaste(event.payload);
+        } else {
+          console.warn('转换粘贴：当前标签页不支持', activeTab);
+        }
+      });
       
       return () => {
         unlisten1();
         unlisten2();
         unlisten3();
+        unlisten4();
       };
     };
     let cleanup = setupListeners();
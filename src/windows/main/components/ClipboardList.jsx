@@ -210,6 +210,19 @@ const ClipboardList = forwardRef(({
         }
       }
     },
+    executeTransformPaste: async chainId => {
+      const item = itemsWithId[currentSelectedIndex];
+      if (!item || item._isPlaceholder) {
+        console.warn('转换粘贴：没有选中的剪贴板项');
+        return;
+      }
+      try {
+        const { pasteClipboardItem } = await import('@shared/api/clipboard');
+        await pasteClipboardItem(item.id, null, { transform_chain: chainId });
+      } catch (error) {
+        console.error('转换粘贴失败:', error);
+      }
+    },
     scrollToTop: () => {
       virtuosoRef.current?.scrollToIndex({
         index: 0,
@@ -196,6 +196,19 @@ const FavoritesList = forwardRef(({
         }
       }
     },
+    executeTransformPaste: async chainId => {
+      const item = itemsWithId[currentSelectedIndex];
+      if (!item || item._isPlaceholder) {
+        console.warn('转换粘贴：没有选中的收藏项');
+        return;
+      }
+      try {
+        const { pasteFavorite } = await import('@shared/api/favorites');
+        await pasteFavorite(item.id, null, { transform_chain: chainId });
+      } catch (error) {
+        console.error('转换粘贴收藏失败:', error);
+      }
+    },
     scrollToTop: () => {
       virtuosoRef.current?.scrollToIndex({
         index: 0,
@@ -49,7 +49,8 @@ const FavoritesTab = forwardRef(({
     navigateUp: () => listRef.current?.navigateUp?.(),
     navigateDown: () => listRef.current?.navigateDown?.(),
     executeCurrentItem: () => listRef.current?.executeCurrentItem?.(),
-    executePlainTextPaste: () => listRef.current?.executePlainTextPaste?.()
+    executePlainTextPaste: () => listRef.current?.executePlainTextPaste?.(),
+    executeTransformPaste: chainId => listRef.current?.executeTransformPaste?.(chainId)
   }));
 
   // 处理滚动状态变化